@@ -0,0 +1,267 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use crate::file_reader::PuzzleInput;
+use crate::solution::DaySolution;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Stage {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Stage {
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Stage::Parse => "parse",
+            Stage::Part1 => "part_1",
+            Stage::Part2 => "part_2",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Statistics {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Statistics {
+    pub fn from_samples(samples: &[Duration]) -> Option<Statistics> {
+        let mut samples = samples.to_vec();
+
+        samples.sort();
+
+        let middle = samples.len() / 2;
+
+        let median = if samples.len() % 2 == 0 {
+            (*samples.get(middle.checked_sub(1)?)? + samples[middle]) / 2
+        } else {
+            samples[middle]
+        };
+
+        Some(Statistics {
+            min: *samples.first()?,
+            median,
+            max: *samples.last()?,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct StageTiming {
+    pub day: u8,
+    pub stage: Stage,
+    pub statistics: Statistics,
+}
+
+// Runs parse, part 1 and part 2 `runs` times each. Every run parses the input
+// again, and both parts are timed on that run's parsed input.
+pub fn benchmark_day(
+    solution: &dyn DaySolution,
+    input: &PuzzleInput,
+    runs: usize,
+) -> Result<Vec<StageTiming>, String> {
+    let day = solution.get_day();
+    let mut samples: Vec<Vec<Duration>> = (0..3).map(|_| Vec::with_capacity(runs)).collect();
+
+    for _ in 0..runs {
+        let start = Instant::now();
+        let prepared_solution = solution.prepare(input)?;
+        samples[0].push(start.elapsed());
+
+        for part in 1..=2 {
+            let start = Instant::now();
+            prepared_solution.solve(part)?;
+            samples[part as usize].push(start.elapsed());
+        }
+    }
+
+    [Stage::Parse, Stage::Part1, Stage::Part2]
+        .iter()
+        .zip(samples.iter())
+        .map(|(&stage, stage_samples)| {
+            Statistics::from_samples(stage_samples)
+                .map(|statistics| StageTiming {
+                    day,
+                    stage,
+                    statistics,
+                })
+                .ok_or_else(|| String::from("Benchmarks need at least one run"))
+        })
+        .collect()
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct BenchmarkReport {
+    runs: usize,
+    timings: Vec<StageTiming>,
+}
+
+impl BenchmarkReport {
+    pub fn new(runs: usize) -> BenchmarkReport {
+        BenchmarkReport {
+            runs,
+            timings: Vec::new(),
+        }
+    }
+
+    pub fn get_timings(&self) -> &[StageTiming] {
+        &self.timings
+    }
+
+    pub fn add_timings(&mut self, timings: &[StageTiming]) {
+        self.timings.extend_from_slice(timings);
+    }
+
+    // Durations are in nanoseconds so reports can be compared over time.
+    pub fn to_json(&self) -> String {
+        let timings: Vec<String> = self
+            .timings
+            .iter()
+            .map(|timing| {
+                format!(
+                    "    {{\"day\": {}, \"stage\": \"{}\", \"min_ns\": {}, \"median_ns\": {}, \"max_ns\": {}}}",
+                    timing.day,
+                    timing.stage.get_name(),
+                    timing.statistics.min.as_nanos(),
+                    timing.statistics.median.as_nanos(),
+                    timing.statistics.max.as_nanos()
+                )
+            })
+            .collect();
+
+        format!(
+            "{{\n  \"runs\": {},\n  \"timings\": [\n{}\n  ]\n}}",
+            self.runs,
+            timings.join(",\n")
+        )
+    }
+}
+
+impl fmt::Display for BenchmarkReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{} runs each", self.runs)?;
+        writeln!(f, "Day | Stage  |        Min |     Median |        Max")?;
+        write!(f, "----+--------+------------+------------+-----------")?;
+
+        for timing in &self.timings {
+            write!(
+                f,
+                "\n{:>3} | {:<6} | {:>10} | {:>10} | {:>10}",
+                timing.day,
+                timing.stage.get_name(),
+                format_duration(timing.statistics.min),
+                format_duration(timing.statistics.median),
+                format_duration(timing.statistics.max)
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+
+    if nanos < 1_000 {
+        format!("{}ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.1}us", nanos as f64 / 1e3)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.2}ms", nanos as f64 / 1e6)
+    } else {
+        format!("{:.2}s", nanos as f64 / 1e9)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::solution::find_solution;
+
+    #[test]
+    fn test_statistics_from_samples() {
+        let samples: Vec<Duration> = [5, 1, 4, 2]
+            .iter()
+            .map(|&ms| Duration::from_millis(ms))
+            .collect();
+
+        let expected = Statistics {
+            min: Duration::from_millis(1),
+            median: Duration::from_millis(3),
+            max: Duration::from_millis(5),
+        };
+
+        assert_eq!(Statistics::from_samples(&samples), Some(expected));
+        assert_eq!(
+            Statistics::from_samples(&samples[..3]).map(|statistics| statistics.median),
+            Some(Duration::from_millis(4))
+        );
+        assert_eq!(Statistics::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_nanos(999)), "999ns");
+        assert_eq!(format_duration(Duration::from_nanos(12_345)), "12.3us");
+        assert_eq!(format_duration(Duration::from_micros(4_560)), "4.56ms");
+        assert_eq!(format_duration(Duration::from_millis(2_500)), "2.50s");
+    }
+
+    #[test]
+    fn test_benchmark_day() {
+        let input = PuzzleInput::from_file("test_inputs/day_1_part_1.txt").unwrap();
+        let solution = find_solution(1).unwrap();
+
+        let result = benchmark_day(solution.as_ref(), &input, 3).unwrap();
+
+        let stages: Vec<Stage> = result.iter().map(|timing| timing.stage).collect();
+
+        assert_eq!(stages, vec![Stage::Parse, Stage::Part1, Stage::Part2]);
+        assert!(result
+            .iter()
+            .all(|timing| timing.day == 1 && timing.statistics.min <= timing.statistics.max));
+        assert_eq!(
+            benchmark_day(solution.as_ref(), &input, 0),
+            Err(String::from("Benchmarks need at least one run"))
+        );
+        assert!(benchmark_day(
+            solution.as_ref(),
+            &PuzzleInput::from_string("masses", "one"),
+            1
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_report() {
+        let statistics = Statistics {
+            min: Duration::from_micros(10),
+            median: Duration::from_micros(20),
+            max: Duration::from_millis(3),
+        };
+
+        let mut report = BenchmarkReport::new(5);
+
+        report.add_timings(&[StageTiming {
+            day: 12,
+            stage: Stage::Part2,
+            statistics,
+        }]);
+
+        let expected_table = "5 runs each\n\
+                              Day | Stage  |        Min |     Median |        Max\n\
+                              ----+--------+------------+------------+-----------\n \
+                              12 | part_2 |     10.0us |     20.0us |     3.00ms";
+        let expected_json = "{\n  \"runs\": 5,\n  \"timings\": [\n    \
+                             {\"day\": 12, \"stage\": \"part_2\", \"min_ns\": 10000, \
+                             \"median_ns\": 20000, \"max_ns\": 3000000}\n  ]\n}";
+
+        assert_eq!(report.to_string(), expected_table);
+        assert_eq!(report.to_json(), expected_json);
+    }
+}
@@ -0,0 +1,323 @@
+use crate::output::OutputFormat;
+use crate::solution::ARG_NEEDS_PART;
+use crate::verify::DEFAULT_ANSWERS_FILE;
+
+pub const USAGE: &str = "Usage:
+    aoc run <day> [--part 1|2] [--input path|-] [--arg value] [--format text|json|csv]
+    aoc run --all [--part 1|2] [--format text|json|csv]
+    aoc verify [--answers path]
+    aoc bench <day>|--all [--runs count] [--json]
+    aoc new-day <day>
+    aoc help";
+
+pub const DEFAULT_BENCHMARK_RUNS: usize = 5;
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct RunOptions {
+    pub part: Option<u8>,
+    pub input: Option<String>,
+    // Day specific parameter, e.g. the day 5 system ID or the day 9 BOOST mode
+    pub arg: Option<String>,
+    pub format: OutputFormat,
+}
+
+impl RunOptions {
+    pub fn new() -> RunOptions {
+        RunOptions::default()
+    }
+
+    pub fn includes_part(&self, part: u8) -> bool {
+        self.part.is_none_or(|selected_part| selected_part == part)
+    }
+
+    pub fn get_input_file<'a>(&'a self, default_file: &'a str) -> &'a str {
+        self.input.as_deref().unwrap_or(default_file)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct BenchOptions {
+    // None benchmarks every day
+    pub day: Option<u8>,
+    pub runs: usize,
+    pub json: bool,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Command {
+    Run(u8, RunOptions),
+    RunAll(RunOptions),
+    // Checks every day against a file of known answers
+    Verify(String),
+    Bench(BenchOptions),
+    // Creates the files for a new day and registers it
+    NewDay(u8),
+    Help,
+}
+
+// `args` should not include the program name.
+pub fn parse_args(args: &[String]) -> Result<Command, String> {
+    let mut args = args.iter().map(String::as_str);
+
+    match args.next() {
+        Some("run") => {}
+        Some("verify") => return parse_verify_args(args),
+        Some("bench") => return parse_bench_args(args),
+        Some("new-day") => return parse_new_day_args(args),
+        Some("help") | Some("--help") | Some("-h") | None => return Ok(Command::Help),
+        Some(command) => return Err(format!("Unknown command {}", command)),
+    }
+
+    let mut day = None;
+    let mut all = false;
+    let mut options = RunOptions::new();
+
+    while let Some(arg) = args.next() {
+        match arg {
+            "--all" => all = true,
+            "--part" => {
+                let part = next_value(&mut args, arg)?;
+
+                match part {
+                    "1" | "2" => options.part = part.parse().ok(),
+                    _ => return Err(format!("Part should be 1 or 2, got {}", part)),
+                }
+            }
+            "--input" => options.input = Some(String::from(next_value(&mut args, arg)?)),
+            "--arg" => options.arg = Some(String::from(next_value(&mut args, arg)?)),
+            "--format" => options.format = next_value(&mut args, arg)?.parse()?,
+            _ if arg.starts_with("--") => return Err(format!("Unknown option {}", arg)),
+            _ if day.is_some() => return Err(format!("Unexpected argument {}", arg)),
+            _ => day = Some(parse_day(arg)?),
+        }
+    }
+
+    match (day, all) {
+        (Some(_), false) if options.arg.is_some() && options.part.is_none() => {
+            Err(String::from(ARG_NEEDS_PART))
+        }
+        (Some(day), false) => Ok(Command::Run(day, options)),
+        (None, true) if options.input.is_some() || options.arg.is_some() => Err(String::from(
+            "--input and --arg can only be used when running a single day",
+        )),
+        (None, true) => Ok(Command::RunAll(options)),
+        (Some(_), true) => Err(String::from("Choose either a day or --all")),
+        (None, false) => Err(String::from("Missing the day to run")),
+    }
+}
+
+fn parse_verify_args<'a, I>(mut args: I) -> Result<Command, String>
+where
+    I: Iterator<Item = &'a str>,
+{
+    let mut answers_file = String::from(DEFAULT_ANSWERS_FILE);
+
+    while let Some(arg) = args.next() {
+        match arg {
+            "--answers" => answers_file = String::from(next_value(&mut args, arg)?),
+            _ => return Err(format!("Unexpected argument {}", arg)),
+        }
+    }
+
+    Ok(Command::Verify(answers_file))
+}
+
+fn parse_bench_args<'a, I>(mut args: I) -> Result<Command, String>
+where
+    I: Iterator<Item = &'a str>,
+{
+    let mut day = None;
+    let mut all = false;
+    let mut runs = DEFAULT_BENCHMARK_RUNS;
+    let mut json = false;
+
+    while let Some(arg) = args.next() {
+        match arg {
+            "--all" => all = true,
+            "--json" => json = true,
+            "--runs" => {
+                let value = next_value(&mut args, arg)?;
+
+                runs = match value.parse() {
+                    Ok(parsed_runs) if parsed_runs > 0 => parsed_runs,
+                    _ => return Err(format!("Runs should be a positive number, got {}", value)),
+                };
+            }
+            _ if arg.starts_with("--") => return Err(format!("Unknown option {}", arg)),
+            _ if day.is_some() => return Err(format!("Unexpected argument {}", arg)),
+            _ => day = Some(parse_day(arg)?),
+        }
+    }
+
+    match (day, all) {
+        (Some(_), true) => Err(String::from("Choose either a day or --all")),
+        (None, false) => Err(String::from("Missing the day to benchmark")),
+        _ => Ok(Command::Bench(BenchOptions { day, runs, json })),
+    }
+}
+
+fn parse_new_day_args<'a, I>(mut args: I) -> Result<Command, String>
+where
+    I: Iterator<Item = &'a str>,
+{
+    let day = parse_day(args.next().ok_or("Missing the day to create")?)?;
+
+    match args.next() {
+        Some(arg) => Err(format!("Unexpected argument {}", arg)),
+        None => Ok(Command::NewDay(day)),
+    }
+}
+
+fn parse_day(arg: &str) -> Result<u8, String> {
+    match arg.parse::<u8>() {
+        Ok(day) if (1..=25).contains(&day) => Ok(day),
+        _ => Err(format!("Day should be between 1 and 25, got {}", arg)),
+    }
+}
+
+fn next_value<'a, I>(args: &mut I, option: &str) -> Result<&'a str, String>
+where
+    I: Iterator<Item = &'a str>,
+{
+    args.next()
+        .ok_or_else(|| format!("Missing a value for {}", option))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_parse_args_run_day() {
+        let expected = Command::Run(
+            5,
+            RunOptions {
+                part: Some(2),
+                input: Some(String::from("my_input.txt")),
+                arg: Some(String::from("8")),
+                format: OutputFormat::Json,
+            },
+        );
+
+        let result = parse_args(&to_args(
+            "run 5 --part 2 --input my_input.txt --arg 8 --format json",
+        ));
+
+        assert_eq!(result, Ok(expected));
+        assert_eq!(
+            parse_args(&to_args("run 5 --arg 5")),
+            Err(String::from(ARG_NEEDS_PART))
+        );
+    }
+
+    #[test]
+    fn test_parse_args_run_all() {
+        let expected = Command::RunAll(RunOptions {
+            part: Some(1),
+            ..RunOptions::new()
+        });
+
+        let result = parse_args(&to_args("run --all --part 1"));
+
+        assert_eq!(result, Ok(expected));
+        assert_eq!(parse_args(&[]), Ok(Command::Help));
+    }
+
+    #[test]
+    fn test_parse_args_verify() {
+        assert_eq!(
+            parse_args(&to_args("verify")),
+            Ok(Command::Verify(String::from("answers.toml")))
+        );
+        assert_eq!(
+            parse_args(&to_args("verify --answers old_answers.toml")),
+            Ok(Command::Verify(String::from("old_answers.toml")))
+        );
+    }
+
+    #[test]
+    fn test_parse_args_errors() {
+        assert_eq!(
+            parse_args(&to_args("run 26")),
+            Err(String::from("Day should be between 1 and 25, got 26"))
+        );
+        assert_eq!(
+            parse_args(&to_args("run 3 --part 3")),
+            Err(String::from("Part should be 1 or 2, got 3"))
+        );
+        assert_eq!(
+            parse_args(&to_args("run 3 --input")),
+            Err(String::from("Missing a value for --input"))
+        );
+        assert_eq!(
+            parse_args(&to_args("run")),
+            Err(String::from("Missing the day to run"))
+        );
+        assert!(parse_args(&to_args("run --all --arg 1")).is_err());
+        assert!(parse_args(&to_args("verify 1")).is_err());
+        assert_eq!(
+            parse_args(&to_args("run 1 --format xml")),
+            Err(String::from("Format should be text, json or csv, got xml"))
+        );
+        assert!(parse_args(&to_args("run 1 --verbose")).is_err());
+        assert!(parse_args(&to_args("start 1")).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_bench() {
+        let expected = Command::Bench(BenchOptions {
+            day: Some(12),
+            runs: 20,
+            json: true,
+        });
+
+        let result = parse_args(&to_args("bench 12 --runs 20 --json"));
+
+        assert_eq!(result, Ok(expected));
+        assert_eq!(
+            parse_args(&to_args("bench --all")),
+            Ok(Command::Bench(BenchOptions {
+                day: None,
+                runs: DEFAULT_BENCHMARK_RUNS,
+                json: false,
+            }))
+        );
+        assert_eq!(
+            parse_args(&to_args("bench 1 --runs 0")),
+            Err(String::from("Runs should be a positive number, got 0"))
+        );
+        assert!(parse_args(&to_args("bench")).is_err());
+        assert!(parse_args(&to_args("bench 1 --all")).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_new_day() {
+        assert_eq!(parse_args(&to_args("new-day 15")), Ok(Command::NewDay(15)));
+        assert_eq!(
+            parse_args(&to_args("new-day")),
+            Err(String::from("Missing the day to create"))
+        );
+        assert!(parse_args(&to_args("new-day 26")).is_err());
+        assert!(parse_args(&to_args("new-day 15 16")).is_err());
+    }
+
+    #[test]
+    fn test_run_options() {
+        let options = RunOptions {
+            part: Some(2),
+            ..RunOptions::new()
+        };
+
+        assert!(!options.includes_part(1));
+        assert!(options.includes_part(2));
+        assert!(RunOptions::new().includes_part(1));
+        assert_eq!(
+            options.get_input_file("inputs/day_5.txt"),
+            "inputs/day_5.txt"
+        );
+    }
+}
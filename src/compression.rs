@@ -0,0 +1,188 @@
+use std::fmt::Display;
+
+// A sequence split into a main routine of calls to a few functions, like the
+// day 17 movement routines. Tokens are written out separated by commas.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Compression<T> {
+    // Indexes into `functions`
+    pub main_routine: Vec<usize>,
+    pub functions: Vec<Vec<T>>,
+}
+
+impl<T> Compression<T>
+where
+    T: Clone + Display,
+{
+    pub fn expand(&self) -> Vec<T> {
+        self.main_routine
+            .iter()
+            .flat_map(|&function| self.functions[function].iter().cloned())
+            .collect()
+    }
+
+    // Functions are named A, B, C...
+    pub fn get_main_routine_string(&self) -> String {
+        self.main_routine
+            .iter()
+            .map(|&function| get_function_name(function).to_string())
+            .collect::<Vec<String>>()
+            .join(",")
+    }
+
+    pub fn get_function_strings(&self) -> Vec<String> {
+        self.functions
+            .iter()
+            .map(|function| join_tokens(function))
+            .collect()
+    }
+}
+
+pub fn get_function_name(function: usize) -> char {
+    (b'A' + function as u8) as char
+}
+
+pub fn join_tokens<T: Display>(tokens: &[T]) -> String {
+    tokens
+        .iter()
+        .map(|token| token.to_string())
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+// Finds a way to write `tokens` with at most `max_functions` functions, where
+// the main routine and every function are at most `max_length` characters
+// long. Tries to reuse functions before defining new ones.
+pub fn compress<T>(tokens: &[T], max_functions: usize, max_length: usize) -> Option<Compression<T>>
+where
+    T: PartialEq + Clone + Display,
+{
+    let mut compression = Compression {
+        main_routine: Vec::new(),
+        functions: Vec::new(),
+    };
+
+    if search(tokens, max_functions, max_length, &mut compression) {
+        Some(compression)
+    } else {
+        None
+    }
+}
+
+fn search<T>(
+    tokens: &[T],
+    max_functions: usize,
+    max_length: usize,
+    compression: &mut Compression<T>,
+) -> bool
+where
+    T: PartialEq + Clone + Display,
+{
+    if tokens.is_empty() {
+        return true;
+    }
+
+    // Every call takes a letter and a comma
+    if (compression.main_routine.len() + 1) * 2 - 1 > max_length {
+        return false;
+    }
+
+    for function in 0..compression.functions.len() {
+        if tokens.starts_with(&compression.functions[function]) {
+            let rest = &tokens[compression.functions[function].len()..];
+
+            compression.main_routine.push(function);
+
+            if search(rest, max_functions, max_length, compression) {
+                return true;
+            }
+
+            compression.main_routine.pop();
+        }
+    }
+
+    if compression.functions.len() == max_functions {
+        return false;
+    }
+
+    for function_length in 1..=tokens.len() {
+        let function = &tokens[..function_length];
+
+        if join_tokens(function).len() > max_length {
+            break;
+        }
+
+        compression.main_routine.push(compression.functions.len());
+        compression.functions.push(function.to_vec());
+
+        if search(
+            &tokens[function_length..],
+            max_functions,
+            max_length,
+            compression,
+        ) {
+            return true;
+        }
+
+        compression.functions.pop();
+        compression.main_routine.pop();
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_tokens(tokens: &str) -> Vec<String> {
+        tokens.split(',').map(String::from).collect()
+    }
+
+    #[test]
+    fn test_compress() {
+        let tokens = to_tokens("R,8,R,8,R,4,R,4,R,8,L,6,L,2,R,4,R,4,R,8,R,8,R,8,L,6,L,2");
+
+        let result = compress(&tokens, 3, 20).unwrap();
+
+        assert_eq!(result.expand(), tokens);
+        assert!(result.functions.len() <= 3);
+        assert!(result.get_main_routine_string().len() <= 20);
+        assert!(result
+            .get_function_strings()
+            .iter()
+            .all(|function| function.len() <= 20));
+    }
+
+    #[test]
+    fn test_compress_limits() {
+        let tokens = to_tokens("1,2,3,4");
+
+        let expected = Compression {
+            main_routine: vec![0, 1],
+            functions: vec![to_tokens("1,2"), to_tokens("3,4")],
+        };
+
+        assert_eq!(compress(&tokens, 1, 3), None);
+        assert_eq!(compress(&tokens, 2, 3), Some(expected));
+        assert_eq!(compress(&to_tokens("1,2,1,2,1,2"), 1, 3), None);
+    }
+
+    #[test]
+    fn test_compression_strings() {
+        let compression = Compression {
+            main_routine: vec![0, 1, 0],
+            functions: vec![to_tokens("L,10"), to_tokens("R,4,L,2")],
+        };
+
+        assert_eq!(compression.get_main_routine_string(), "A,B,A");
+        assert_eq!(
+            compression.get_function_strings(),
+            vec![String::from("L,10"), String::from("R,4,L,2")]
+        );
+        assert_eq!(join_tokens(&compression.expand()), "L,10,R,4,L,2,L,10");
+        assert_eq!(
+            compress::<String>(&[], 3, 20).unwrap().expand(),
+            Vec::<String>::new()
+        );
+    }
+}
@@ -0,0 +1,270 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+// A sequence of states where the first `prefix_length` states are never seen
+// again and everything after them repeats every `cycle_length` steps.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Cycle {
+    pub prefix_length: usize,
+    pub cycle_length: usize,
+}
+
+impl Cycle {
+    // The earliest step with the same state as `step`
+    pub fn get_equivalent_step(&self, step: u128) -> usize {
+        let prefix_length = self.prefix_length as u128;
+
+        if step < prefix_length {
+            step as usize
+        } else {
+            (prefix_length + (step - prefix_length) % self.cycle_length as u128) as usize
+        }
+    }
+
+    // The first step whose state has been seen before
+    pub fn get_first_repeat(&self) -> usize {
+        self.prefix_length + self.cycle_length
+    }
+}
+
+// Floyd's tortoise and hare, which only keeps a couple of states around. Like
+// brent, it never returns if the states don't cycle.
+pub fn floyd<T, F>(initial: &T, next: F) -> Cycle
+where
+    T: PartialEq + Clone,
+    F: Fn(&T) -> T,
+{
+    let mut tortoise = next(initial);
+    let mut hare = next(&tortoise);
+
+    while tortoise != hare {
+        tortoise = next(&tortoise);
+        hare = next(&next(&hare));
+    }
+
+    let mut prefix_length = 0;
+
+    tortoise = initial.clone();
+
+    while tortoise != hare {
+        tortoise = next(&tortoise);
+        hare = next(&hare);
+        prefix_length += 1;
+    }
+
+    let mut cycle_length = 1;
+
+    hare = next(&tortoise);
+
+    while tortoise != hare {
+        hare = next(&hare);
+        cycle_length += 1;
+    }
+
+    Cycle {
+        prefix_length,
+        cycle_length,
+    }
+}
+
+// Brent's algorithm, which usually calls `next` fewer times than floyd.
+pub fn brent<T, F>(initial: &T, next: F) -> Cycle
+where
+    T: PartialEq + Clone,
+    F: Fn(&T) -> T,
+{
+    let mut power = 1;
+    let mut cycle_length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = next(initial);
+
+    while tortoise != hare {
+        if power == cycle_length {
+            tortoise = hare.clone();
+            power *= 2;
+            cycle_length = 0;
+        }
+
+        hare = next(&hare);
+        cycle_length += 1;
+    }
+
+    let mut prefix_length = 0;
+
+    tortoise = initial.clone();
+    hare = initial.clone();
+
+    for _ in 0..cycle_length {
+        hare = next(&hare);
+    }
+
+    while tortoise != hare {
+        tortoise = next(&tortoise);
+        hare = next(&hare);
+        prefix_length += 1;
+    }
+
+    Cycle {
+        prefix_length,
+        cycle_length,
+    }
+}
+
+// Remembers every state, so it finds the cycle as soon as a state repeats. None
+// if the states run out first.
+pub fn find_cycle<I>(states: I) -> Option<Cycle>
+where
+    I: IntoIterator,
+    I::Item: Hash + Eq + Clone,
+{
+    record_states(states, None).1
+}
+
+// The state `step` steps into `states`, without running every step if the
+// states cycle first. None if the states run out first.
+pub fn state_at_step<I>(states: I, step: u128) -> Option<I::Item>
+where
+    I: IntoIterator,
+    I::Item: Hash + Eq + Clone,
+{
+    let (mut seen_states, cycle) = record_states(states, Some(step));
+
+    let index = match cycle {
+        Some(cycle) => cycle.get_equivalent_step(step),
+        None => step as usize,
+    };
+
+    if index < seen_states.len() {
+        Some(seen_states.swap_remove(index))
+    } else {
+        None
+    }
+}
+
+// Same as state_at_step, for states made by calling `next` over and over.
+pub fn extrapolate<T, F>(initial: &T, next: F, step: u128) -> T
+where
+    T: PartialEq + Clone,
+    F: Fn(&T) -> T,
+{
+    let cycle = brent(initial, &next);
+
+    let mut state = initial.clone();
+
+    for _ in 0..cycle.get_equivalent_step(step) {
+        state = next(&state);
+    }
+
+    state
+}
+
+// Records states until one repeats, the states run out or `last_step` is seen.
+fn record_states<I>(states: I, last_step: Option<u128>) -> (Vec<I::Item>, Option<Cycle>)
+where
+    I: IntoIterator,
+    I::Item: Hash + Eq + Clone,
+{
+    let mut seen_states = Vec::new();
+    let mut first_seen_at = HashMap::new();
+
+    for (index, state) in states.into_iter().enumerate() {
+        if let Some(&prefix_length) = first_seen_at.get(&state) {
+            let cycle = Cycle {
+                prefix_length,
+                cycle_length: index - prefix_length,
+            };
+
+            return (seen_states, Some(cycle));
+        }
+
+        first_seen_at.insert(state.clone(), index);
+        seen_states.push(state);
+
+        if last_step == Some(index as u128) {
+            break;
+        }
+    }
+
+    (seen_states, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::iter;
+
+    // 0, 1, 2, 3, 4, 5, 6, 3, 4, 5, 6, 3, ...
+    fn next(state: &u32) -> u32 {
+        if *state < 3 {
+            state + 1
+        } else {
+            3 + (state - 2) % 4
+        }
+    }
+
+    fn states() -> impl Iterator<Item = u32> {
+        iter::successors(Some(0), |state| Some(next(state)))
+    }
+
+    const CYCLE: Cycle = Cycle {
+        prefix_length: 3,
+        cycle_length: 4,
+    };
+
+    #[test]
+    fn test_floyd() {
+        assert_eq!(floyd(&0, next), CYCLE);
+        assert_eq!(
+            floyd(&0, |state| (state + 1) % 5),
+            Cycle {
+                prefix_length: 0,
+                cycle_length: 5
+            }
+        );
+    }
+
+    #[test]
+    fn test_brent() {
+        assert_eq!(brent(&0, next), CYCLE);
+        assert_eq!(
+            brent(&7, |_| 7),
+            Cycle {
+                prefix_length: 0,
+                cycle_length: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        assert_eq!(find_cycle(states()), Some(CYCLE));
+        assert_eq!(find_cycle(0..10), None);
+    }
+
+    #[test]
+    fn test_cycle_steps() {
+        assert_eq!(CYCLE.get_equivalent_step(2), 2);
+        assert_eq!(CYCLE.get_equivalent_step(7), 3);
+        assert_eq!(CYCLE.get_equivalent_step(10), 6);
+        assert_eq!(CYCLE.get_first_repeat(), 7);
+    }
+
+    #[test]
+    fn test_state_at_step() {
+        assert_eq!(state_at_step(states(), 5), Some(5));
+        assert_eq!(state_at_step(states(), 10), Some(6));
+        assert_eq!(
+            state_at_step(states(), 100_000_000_000_000_000_000),
+            Some(4)
+        );
+        assert_eq!(state_at_step(0..10, 9), Some(9));
+        assert_eq!(state_at_step(0..10, 10), None);
+    }
+
+    #[test]
+    fn test_extrapolate() {
+        assert_eq!(extrapolate(&0, next, 10), 6);
+        assert_eq!(extrapolate(&0, next, 100_000_000_000_000_000_001), 5);
+    }
+}
@@ -10,7 +10,7 @@ impl FuelCounterUpper {
         let mut temp_modules = Vec::new();
 
         for mass_value in mass_values {
-            temp_modules.push(Module::from_str(&mass_value));
+            temp_modules.push(Module::from_str(mass_value));
         }
 
         FuelCounterUpper {
@@ -1,25 +1,33 @@
-mod fuel_counter_upper;
-mod module;
-
-use crate::file_reader::to_string_vector;
-use fuel_counter_upper::FuelCounterUpper;
-
-pub fn run_day_1() {
-    let module_masses_result = to_string_vector("inputs/day_1.txt");
-
-    match module_masses_result {
-        Ok(module_masses) => {
-            let fuel_counter_upper = FuelCounterUpper::new(&module_masses);
-
-            println!(
-                "Part 1 Solution is: {:?}",
-                fuel_counter_upper.total_fuel_required()
-            );
-            println!(
-                "Part 2 Solution is: {:?}",
-                fuel_counter_upper.total_fuel_required_recursive()
-            );
-        }
-        Err(error) => println!("Error parsing file: {:?}", error),
+pub mod fuel_counter_upper;
+pub mod module;
+
+use crate::file_reader::PuzzleInput;
+use crate::solution::Solution;
+pub use fuel_counter_upper::FuelCounterUpper;
+pub use module::Module;
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+
+    type Input = FuelCounterUpper;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(&self, input: &PuzzleInput) -> Result<FuelCounterUpper, String> {
+        let module_masses: Vec<i32> = input.parse_lines()?;
+
+        Ok(FuelCounterUpper {
+            modules: module_masses.into_iter().map(Module::new).collect(),
+        })
+    }
+
+    fn part_1(&self, fuel_counter_upper: &FuelCounterUpper) -> Result<i32, String> {
+        Ok(fuel_counter_upper.total_fuel_required())
+    }
+
+    fn part_2(&self, fuel_counter_upper: &FuelCounterUpper) -> Result<i32, String> {
+        Ok(fuel_counter_upper.total_fuel_required_recursive())
     }
 }
@@ -5,9 +5,10 @@ pub struct Module {
 
 impl Module {
     pub fn new(mass: i32) -> Module {
-        Module { mass: mass }
+        Module { mass }
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(mass_str: &str) -> Module {
         Module::new(mass_str.parse::<i32>().unwrap())
     }
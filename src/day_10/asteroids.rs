@@ -45,8 +45,8 @@ impl Asteroid {
             .collect();
 
         other_asteroids.sort_by(|a, b| {
-            let (r1, t1) = self.relative_polar_coordinates_of(&a);
-            let (r2, t2) = self.relative_polar_coordinates_of(&b);
+            let (r1, t1) = self.relative_polar_coordinates_of(a);
+            let (r2, t2) = self.relative_polar_coordinates_of(b);
 
             (-t1)
                 .partial_cmp(&(-t2))
@@ -58,7 +58,7 @@ impl Asteroid {
         let mut current_group: Vec<Asteroid> = vec![other_asteroids[0].clone()];
 
         for other_asteroid in other_asteroids.iter().skip(1) {
-            let (_, t2) = self.relative_polar_coordinates_of(&other_asteroid);
+            let (_, t2) = self.relative_polar_coordinates_of(other_asteroid);
 
             if (t1 - t2).abs() < EPSILON {
                 current_group.push(other_asteroid.clone());
@@ -1,40 +1,39 @@
-mod asteroids;
+pub mod asteroids;
 
-use crate::file_reader::to_string_vector;
-use asteroids::AsteroidMap;
+use crate::file_reader::PuzzleInput;
+use crate::solution::Solution;
+pub use asteroids::{Asteroid, AsteroidMap};
 
-pub fn run_day_10() {
-    let file_input = to_string_vector("inputs/day_10.txt");
+pub struct Day10;
 
-    match file_input {
-        Ok(input_lines) => {
-            let map_data: Vec<Vec<char>> =
-                input_lines.iter().map(|s| s.chars().collect()).collect();
+impl Solution for Day10 {
+    const DAY: u8 = 10;
 
-            let asteroid_map = AsteroidMap::new(&map_data);
+    type Input = AsteroidMap;
+    type Answer1 = i32;
+    type Answer2 = i32;
 
-            run_part_1(&asteroid_map);
-            run_part_2(&asteroid_map);
+    fn parse(&self, input: &PuzzleInput) -> Result<AsteroidMap, String> {
+        let map_data = input.to_char_grid();
+
+        if !map_data.iter().flatten().any(|&c| c == '#') {
+            return Err(String::from("The map has no asteroids!"));
         }
 
-        Err(error) => println!("Error parsing file: {:?}", error),
+        Ok(AsteroidMap::new(&map_data))
     }
-}
 
-fn run_part_1(asteroid_map: &AsteroidMap) {
-    let best_asteroid_for_monitoring_station = asteroid_map.best_monitoring_station_location();
+    fn part_1(&self, asteroid_map: &AsteroidMap) -> Result<i32, String> {
+        let best_asteroid_for_monitoring_station = asteroid_map.best_monitoring_station_location();
 
-    println!(
-        "Day 10 Part 1 Solution: {}",
-        best_asteroid_for_monitoring_station.number_of_asteroids_in_los
-    );
-}
-fn run_part_2(asteroid_map: &AsteroidMap) {
-    let nth_vaporized_asteroid = asteroid_map
-        .nth_vaporized_asteroid_from_best_monitoring_station(200)
-        .unwrap();
+        Ok(best_asteroid_for_monitoring_station.number_of_asteroids_in_los)
+    }
 
-    let result = nth_vaporized_asteroid.x_location * 100 + nth_vaporized_asteroid.y_location;
+    fn part_2(&self, asteroid_map: &AsteroidMap) -> Result<i32, String> {
+        let nth_vaporized_asteroid = asteroid_map
+            .nth_vaporized_asteroid_from_best_monitoring_station(200)
+            .ok_or_else(|| String::from("There aren't 200 asteroids to vaporize!"))?;
 
-    println!("Day 10 Part 2 Solution: {}", result);
+        Ok(nth_vaporized_asteroid.x_location * 100 + nth_vaporized_asteroid.y_location)
+    }
 }
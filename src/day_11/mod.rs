@@ -1,64 +1,54 @@
-mod robot;
+pub mod robot;
 
-use crate::file_reader::to_string_vector;
+use crate::file_reader::PuzzleInput;
 use crate::location::point_2d::Point2d;
+use crate::solution::Solution;
 
-use robot::{PaintColor, Robot};
+pub use robot::{PaintColor, Robot};
 
-pub fn run_day_11() {
-    let file_input = to_string_vector("inputs/day_11.txt");
+pub struct Day11;
 
-    match file_input {
-        Ok(programs) => {
-            if let Some(program) = programs.get(0) {
-                let program_values: Vec<String> =
-                    program.split(",").map(|s| String::from(s)).collect();
+impl Solution for Day11 {
+    const DAY: u8 = 11;
 
-                let mut robot = Robot::new(program_values.as_slice());
+    type Input = Vec<String>;
+    type Answer1 = usize;
+    type Answer2 = String;
 
-                run_part_1(&mut robot);
-                run_part_2(&mut robot);
-            }
-        }
-        Err(error) => println!("Error parsing file: {:?}", error),
+    fn parse(&self, input: &PuzzleInput) -> Result<Vec<String>, String> {
+        Ok(input.split_commas()?)
     }
-}
 
-fn run_part_1(robot: &mut Robot) {
-    robot.reset();
+    fn part_1(&self, program_values: &Vec<String>) -> Result<usize, String> {
+        let mut robot = Robot::new(program_values.as_slice());
 
-    robot.run_program();
+        robot.run_program();
 
-    let number_of_painted_panels = robot.get_painted_panels().len();
+        Ok(robot.get_painted_panels().len())
+    }
 
-    println!("Day 11 Part 1 Solution: {}", number_of_painted_panels);
-}
+    fn part_2(&self, program_values: &Vec<String>) -> Result<String, String> {
+        let mut robot = Robot::new(program_values.as_slice());
 
-fn run_part_2(robot: &mut Robot) {
-    robot.reset();
+        robot.set_starting_panel_color(PaintColor::White);
 
-    robot.set_starting_panel_color(PaintColor::White);
+        robot.run_program();
 
-    robot.run_program();
+        render_painted_panels(&robot)
+    }
+}
 
+fn render_painted_panels(robot: &Robot) -> Result<String, String> {
     let painted_panels = robot.get_painted_panels();
 
-    let max_x = match painted_panels
-        .keys()
-        .into_iter()
-        .max_by_key(|point| point.x.abs())
-    {
+    let max_x = match painted_panels.keys().max_by_key(|point| point.x.abs()) {
         Some(point) => point.x.abs() + 1,
-        None => panic!("There were no max points!!!!"),
+        None => return Err(String::from("There were no max points!!!!")),
     };
 
-    let max_y = match painted_panels
-        .keys()
-        .into_iter()
-        .max_by_key(|point| point.y.abs())
-    {
+    let max_y = match painted_panels.keys().max_by_key(|point| point.y.abs()) {
         Some(point) => point.y.abs() + 1,
-        None => panic!("There were no max points!!!!"),
+        None => return Err(String::from("There were no max points!!!!")),
     };
 
     let mut display = Vec::new();
@@ -80,11 +70,10 @@ fn run_part_2(robot: &mut Robot) {
         display.push(String::from(temp_string.trim_start().trim()));
     }
 
-    println!("Day 11 Part 2 Solution:");
-
-    for s in display.iter().rev() {
-        if !s.is_empty() {
-            println!("{}", s);
-        }
-    }
+    Ok(display
+        .into_iter()
+        .rev()
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<String>>()
+        .join("\n"))
 }
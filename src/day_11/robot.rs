@@ -89,7 +89,7 @@ impl Robot {
             let brain_outputs = self.brain.get_last_n_outputs(2);
 
             let (paint_output, direction_output) =
-                match (brain_outputs.get(0), brain_outputs.get(1)) {
+                match (brain_outputs.first(), brain_outputs.get(1)) {
                     (Some(&paint_output), Some(&direction_output)) => {
                         (paint_output, direction_output)
                     }
@@ -1,72 +1,62 @@
-mod moons;
+pub mod moons;
 
-use crate::file_reader::to_string_vector;
+use crate::file_reader::PuzzleInput;
 use crate::location::point_3d::Point3d;
+use crate::solution::Solution;
 
-use moons::System;
+pub use moons::System;
 
 const INVALID_CHARS: [char; 7] = ['<', '=', ' ', '>', 'x', 'y', 'z'];
 
-pub fn run_day_12() {
-    let file_input = to_string_vector("inputs/day_12.txt");
+pub struct Day12;
 
-    match file_input {
-        Ok(locations_vec_str) => {
-            let locations: Vec<Point3d<i32>> = locations_vec_str
-                .iter()
-                .map(|location_str| location_str_to_point(&location_str))
-                .collect();
+impl Solution for Day12 {
+    const DAY: u8 = 12;
 
-            let mut moon_system = System::new();
+    type Input = System;
+    type Answer1 = i32;
+    type Answer2 = u128;
 
-            for location in locations {
-                moon_system.add_moon_at(location);
-            }
+    fn parse(&self, input: &PuzzleInput) -> Result<System, String> {
+        let mut moon_system = System::new();
 
-            run_part_1(&mut moon_system);
-            run_part_2(&mut moon_system);
+        for location in input.parse_lines_with(location_str_to_point)? {
+            moon_system.add_moon_at(location);
         }
-        Err(error) => println!("Error parsing file: {:?}", error),
-    }
-}
-
-fn run_part_1(moon_system: &mut System) {
-    moon_system.reset();
 
-    let total_energy_of_system = moon_system.iter_mut().skip(999).next();
-
-    match total_energy_of_system {
-        Some(energy) => println!("Day 12 Part 1 Solution: {}", energy),
-        None => panic!("The moon system reached entropy!!!!!"),
+        Ok(moon_system)
     }
-}
 
-fn run_part_2(moon_system: &mut System) {
-    moon_system.reset();
+    fn part_1(&self, moon_system: &System) -> Result<i32, String> {
+        let mut moon_system = moon_system.clone();
 
-    let steps_to_origin = moon_system.steps_to_get_moons_at_original_positions_and_velocities();
+        moon_system
+            .iter_mut()
+            .nth(999)
+            .ok_or_else(|| String::from("The moon system reached entropy!!!!!"))
+    }
 
-    println!("Day 12 Part 2 Solution: {}", steps_to_origin);
+    fn part_2(&self, moon_system: &System) -> Result<u128, String> {
+        moon_system
+            .steps_to_get_moons_at_original_positions_and_velocities()
+            .ok_or_else(|| String::from("The moons never get back to where they started"))
+    }
 }
 
-fn location_str_to_point(location_str: &str) -> Point3d<i32> {
-    let temp: Vec<i32> = location_str
+fn location_str_to_point(location_str: &str) -> Result<Point3d<i32>, String> {
+    let temp: Vec<Option<i32>> = location_str
         .replace(&INVALID_CHARS[..], "")
         .split(',')
-        .collect::<Vec<&str>>()
-        .iter()
-        .map(|s| s.parse().unwrap())
+        .map(|s| s.parse().ok())
         .collect();
 
-    let (x, y, z) = match (temp.get(0), temp.get(1), temp.get(2)) {
-        (Some(&x), Some(&y), Some(&z)) => (x, y, z),
-        _ => panic!(
+    match temp.as_slice() {
+        [Some(x), Some(y), Some(z)] => Ok(Point3d::new(*x, *y, *z)),
+        _ => Err(format!(
             "Location string {} was not correctly formatted!",
             location_str
-        ),
-    };
-
-    Point3d::new(x, y, z)
+        )),
+    }
 }
 
 #[cfg(test)]
@@ -77,7 +67,7 @@ mod tests {
     fn test_location_str_to_point() {
         let location_str = String::from("<x=9, y=-16, z=-3>");
 
-        let expected = Point3d::new(9, -16, -3);
+        let expected = Ok(Point3d::new(9, -16, -3));
 
         let result = location_str_to_point(&location_str);
 
@@ -1,6 +1,7 @@
-use crate::lcm;
+use crate::cycle::{self, Cycle};
 use crate::location::point_3d::Point3d;
 use crate::location::Location;
+use crate::math::combine_cycle_lengths;
 
 #[derive(Debug, PartialEq, Clone)]
 struct Moon {
@@ -28,7 +29,7 @@ impl Moon {
     }
 }
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Default)]
 pub struct System {
     original_moons: Vec<Moon>,
     moons: Vec<Moon>,
@@ -60,27 +61,27 @@ impl<'a> System {
             .fold(0, |acc, moon| acc + moon.get_total_energy())
     }
 
-    pub fn steps_to_get_moons_at_original_positions_and_velocities(&mut self) -> u128 {
-        let mut self_clone = self.clone();
-
-        self_clone.reset();
-
-        let steps_to_same_x =
-            self_clone.steps_to_get_moons_at_original_x_positions_and_velocities();
-
-        self_clone.reset();
-
-        let steps_to_same_y =
-            self_clone.steps_to_get_moons_at_original_y_positions_and_velocities();
-
-        self_clone.reset();
+    // Each axis moves independently of the others, so the whole system is back
+    // where it started once every axis is. None if an axis never gets back to
+    // its original state, or if the number of steps doesn't fit in a u128
+    pub fn steps_to_get_moons_at_original_positions_and_velocities(&self) -> Option<u128> {
+        let axes: [fn(&Point3d<i32>) -> i32; 3] =
+            [|point| point.x, |point| point.y, |point| point.z];
 
-        let steps_to_same_z =
-            self_clone.steps_to_get_moons_at_original_z_positions_and_velocities();
-
-        let x_y_lcm = lcm(steps_to_same_x, steps_to_same_y);
+        let cycles: Vec<Cycle> = axes
+            .iter()
+            .map(|axis| {
+                let axis_state: Vec<(i32, i32)> = self
+                    .original_moons
+                    .iter()
+                    .map(|moon| (axis(&moon.position), axis(&moon.velocity)))
+                    .collect();
+
+                cycle::brent(&axis_state, |axis_state| Self::simulate_axis(axis_state))
+            })
+            .collect();
 
-        lcm(x_y_lcm, steps_to_same_z)
+        Self::steps_to_return_to_start(&cycles)
     }
 
     pub fn reset(&mut self) {
@@ -96,11 +97,11 @@ impl<'a> System {
                     continue;
                 }
 
-                let next_moon = self.moons.get(next_index).map(|moon| moon.clone()).unwrap();
+                let next_moon = self.moons.get(next_index).cloned().unwrap();
 
-                let mut current_moon = self.moons.get_mut(current_index).unwrap();
+                let current_moon = self.moons.get_mut(current_index).unwrap();
 
-                Self::appy_gravity_between(&mut current_moon, &next_moon);
+                Self::appy_gravity_between(current_moon, &next_moon);
             }
         }
 
@@ -135,50 +136,36 @@ impl<'a> System {
         first.velocity = Point3d::new(new_x_velocity, new_y_velocity, new_z_velocity);
     }
 
-    fn steps_to_get_moons_at_original_axis_positions_and_velocities<F>(&mut self, f: F) -> u128
-    where
-        F: Fn(&Moon, &Moon) -> bool,
-    {
-        let mut system_output = self.iter_mut().next();
-        let mut steps = 1;
-
-        while let Some(_) = system_output {
-            let has_same_x_values = self
-                .original_moons
-                .iter()
-                .zip(self.moons.iter())
-                .all(|(original_moon, moon)| f(original_moon, moon));
-
-            if has_same_x_values {
-                break;
-            }
-
-            system_output = self.iter_mut().next();
-            steps += 1;
+    // Returns None when an axis never cycles back to its start.
+    fn steps_to_return_to_start(cycles: &[Cycle]) -> Option<u128> {
+        // states before the cycle starts never come back
+        if cycles.iter().any(|cycle| cycle.prefix_length != 0) {
+            return None;
         }
 
-        steps
-    }
-
-    fn steps_to_get_moons_at_original_x_positions_and_velocities(&mut self) -> u128 {
-        self.steps_to_get_moons_at_original_axis_positions_and_velocities(|original_moon, moon| {
-            (original_moon.position.x == moon.position.x)
-                && (original_moon.velocity.x == moon.velocity.x)
-        })
-    }
+        let cycle_lengths: Vec<u128> = cycles
+            .iter()
+            .map(|cycle| cycle.cycle_length as u128)
+            .collect();
 
-    fn steps_to_get_moons_at_original_y_positions_and_velocities(&mut self) -> u128 {
-        self.steps_to_get_moons_at_original_axis_positions_and_velocities(|original_moon, moon| {
-            (original_moon.position.y == moon.position.y)
-                && (original_moon.velocity.y == moon.velocity.y)
-        })
+        combine_cycle_lengths(&cycle_lengths)
     }
 
-    fn steps_to_get_moons_at_original_z_positions_and_velocities(&mut self) -> u128 {
-        self.steps_to_get_moons_at_original_axis_positions_and_velocities(|original_moon, moon| {
-            (original_moon.position.z == moon.position.z)
-                && (original_moon.velocity.z == moon.velocity.z)
-        })
+    // One step of the simulation along a single axis, with the moons given as
+    // (position, velocity).
+    fn simulate_axis(axis_state: &[(i32, i32)]) -> Vec<(i32, i32)> {
+        axis_state
+            .iter()
+            .map(|&(position, velocity)| {
+                let velocity = velocity
+                    + axis_state
+                        .iter()
+                        .map(|&(other_position, _)| (other_position - position).signum())
+                        .sum::<i32>();
+
+                (position + velocity, velocity)
+            })
+            .collect()
     }
 }
 
@@ -330,15 +317,32 @@ mod tests {
             .map(|location| Point3d::new(location[0], location[1], location[2]))
             .map(|point| Moon::new_at(point))
             .collect();
-        let mut system = System {
+        let system = System {
             original_moons: moons.clone(),
             moons: moons.clone(),
         };
 
-        let expected = 4_686_774_924;
+        let expected = Some(4_686_774_924);
 
         let result = system.steps_to_get_moons_at_original_positions_and_velocities();
 
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_steps_to_return_to_start() {
+        let cycle = |prefix_length, cycle_length| Cycle {
+            prefix_length,
+            cycle_length,
+        };
+
+        assert_eq!(
+            System::steps_to_return_to_start(&[cycle(0, 4), cycle(0, 6)]),
+            Some(12)
+        );
+        assert_eq!(
+            System::steps_to_return_to_start(&[cycle(0, 4), cycle(1, 6)]),
+            None
+        );
+    }
 }
@@ -24,7 +24,6 @@ impl Tile {
         }
     }
 
-    #[allow(dead_code)]
     pub fn to_char(&self) -> char {
         match self {
             Tile::Empty => ' ',
@@ -36,7 +35,7 @@ impl Tile {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Default)]
 pub struct Game {
     map: HashMap<Point2d<i32>, Tile>,
     score: i128,
@@ -76,8 +75,8 @@ impl Game {
 
             match tile {
                 Tile::Block => self.number_of_blocks += 1,
-                Tile::HorizontalPaddle => self.paddle_location = Some(point.clone()),
-                Tile::Ball => self.ball_location = Some(point.clone()),
+                Tile::HorizontalPaddle => self.paddle_location = Some(point),
+                Tile::Ball => self.ball_location = Some(point),
                 _ => (),
             };
 
@@ -97,18 +96,17 @@ impl Game {
     }
 
     pub fn get_paddle_location(&self) -> Option<Point2d<i32>> {
-        self.paddle_location.clone()
+        self.paddle_location
     }
 
     pub fn get_ball_location(&self) -> Option<Point2d<i32>> {
-        self.ball_location.clone()
+        self.ball_location
     }
 
     pub fn get_score(&self) -> i128 {
         self.score
     }
 
-    #[allow(dead_code)]
     pub fn print_map(&self) {
         let min_x = self.map.keys().min_by_key(|point| point.x).unwrap().x;
         let min_y = self.map.keys().min_by_key(|point| point.y).unwrap().y;
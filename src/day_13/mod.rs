@@ -1,52 +1,67 @@
-mod game;
+pub mod game;
 
 use std::cmp::Ordering;
 
-use crate::file_reader::to_string_vector;
+use crate::file_reader::PuzzleInput;
 use crate::intcode_computer::IntcodeComputer;
+use crate::solution::Solution;
 
-use game::Game;
+pub use game::{Game, Tile};
 
-pub fn run_day_13() {
-    let file_input = to_string_vector("inputs/day_13.txt");
+pub struct Day13;
 
-    match file_input {
-        Ok(programs) => {
-            if let Some(program) = programs.get(0) {
-                let program_values: Vec<String> =
-                    program.split(",").map(|s| String::from(s)).collect();
+impl Solution for Day13 {
+    const DAY: u8 = 13;
 
-                let mut intcode_computer = IntcodeComputer::from(program_values.as_slice());
+    type Input = Vec<String>;
+    type Answer1 = i32;
+    type Answer2 = i128;
 
-                // insert two quarters
-                intcode_computer.replace_code_in_program(0, 2);
+    fn parse(&self, input: &PuzzleInput) -> Result<Vec<String>, String> {
+        Ok(input.split_commas()?)
+    }
+
+    fn part_1(&self, program_values: &Vec<String>) -> Result<i32, String> {
+        let (game, _) = start_game(program_values);
 
-                intcode_computer.execute_program();
+        Ok(game.get_number_of_blocks())
+    }
 
-                let mut game = Game::new();
+    fn part_2(&self, program_values: &Vec<String>) -> Result<i128, String> {
+        let (mut game, mut intcode_computer) = start_game(program_values);
 
-                game.initialize_map(&intcode_computer.get_output_cache());
+        play_game(&mut game, &mut intcode_computer)?;
 
-                run_part_1(&game);
-                run_part_2(&mut game, &mut intcode_computer);
-            }
-        }
-        Err(error) => println!("Error parsing file: {:?}", error),
+        Ok(game.get_score())
     }
 }
 
-pub fn run_part_1(game: &Game) {
-    println!("Day 13 Part 1 Solution: {}", game.get_number_of_blocks());
+fn start_game(program_values: &[String]) -> (Game, IntcodeComputer) {
+    let mut intcode_computer = IntcodeComputer::from(program_values);
+
+    // insert two quarters
+    intcode_computer.replace_code_in_program(0, 2);
+
+    intcode_computer.execute_program();
+
+    let mut game = Game::new();
+
+    game.initialize_map(&intcode_computer.get_output_cache());
+
+    (game, intcode_computer)
 }
 
-pub fn run_part_2(game: &mut Game, intcode_computer: &mut IntcodeComputer) {
+fn play_game(game: &mut Game, intcode_computer: &mut IntcodeComputer) -> Result<(), String> {
     let mut number_of_blocks = game.get_number_of_blocks();
 
     while 0 < number_of_blocks {
-        let paddle_x_location = game.get_paddle_location().unwrap().x;
-        let ball_x_location = game.get_ball_location().unwrap().x;
+        let (paddle_location, ball_location) =
+            match (game.get_paddle_location(), game.get_ball_location()) {
+                (Some(paddle_location), Some(ball_location)) => (paddle_location, ball_location),
+                _ => return Err(String::from("The game has no paddle or ball!")),
+            };
 
-        let paddle_input = match ball_x_location.cmp(&paddle_x_location) {
+        let paddle_input = match ball_location.x.cmp(&paddle_location.x) {
             Ordering::Less => -1,
             Ordering::Equal => 0,
             Ordering::Greater => 1,
@@ -64,5 +79,5 @@ pub fn run_part_2(game: &mut Game, intcode_computer: &mut IntcodeComputer) {
         // game.print_map();
     }
 
-    println!("Day 13 Part 2 Solution: {}", game.get_score());
+    Ok(())
 }
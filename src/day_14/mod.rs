@@ -1,37 +1,49 @@
-mod nanofactory;
+pub mod nanofactory;
 
-use crate::file_reader::to_string_vector;
+use crate::file_reader::PuzzleInput;
+use crate::solution::Solution;
 
-use nanofactory::Nanofactory;
+pub use nanofactory::Nanofactory;
 
-pub fn run_day_14() {
-    let file_input = to_string_vector("inputs/day_14.txt");
+const ORE_IN_CARGO_HOLD: u64 = 1_000_000_000_000;
 
-    match file_input {
-        Ok(reactions) => {
-            let mut nanofactory = Nanofactory::new();
+pub struct Day14;
 
-            for reaction in reactions {
-                nanofactory.add_reaction_from_str(&reaction);
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+
+    type Input = Nanofactory;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(&self, input: &PuzzleInput) -> Result<Nanofactory, String> {
+        let mut nanofactory = Nanofactory::new();
+
+        let reactions = input.parse_lines_with(|line| {
+            if line.contains("=>") {
+                Ok(String::from(line))
+            } else {
+                Err(String::from("expected a reaction like 7 A, 1 E => 1 FUEL"))
             }
+        })?;
 
-            run_part_1(&nanofactory);
-            run_part_2(&nanofactory);
+        for reaction in reactions {
+            nanofactory.add_reaction_from_str(&reaction);
         }
-        Err(error) => println!("Error parsing file: {:?}", error),
+
+        Ok(nanofactory)
     }
-}
 
-pub fn run_part_1(nanofactory: &Nanofactory) {
-    println!(
-        "Day 14 Part 1 Solution: {}",
-        nanofactory.number_of_ore_to_make_n_fuel(1)
-    );
-}
+    fn part_1(&self, nanofactory: &Nanofactory) -> Result<u64, String> {
+        Ok(nanofactory.number_of_ore_to_make_n_fuel(1))
+    }
 
-pub fn run_part_2(nanofactory: &Nanofactory) {
-    let ore_to_use: u64 = 1_000_000_000_000;
+    fn part_2(&self, nanofactory: &Nanofactory) -> Result<u64, String> {
+        Ok(max_fuel_for(nanofactory, ORE_IN_CARGO_HOLD))
+    }
+}
 
+fn max_fuel_for(nanofactory: &Nanofactory, ore_to_use: u64) -> u64 {
     let number_of_ore_to_make_1_fuel = nanofactory.number_of_ore_to_make_n_fuel(1);
 
     let mut floor = ore_to_use / number_of_ore_to_make_1_fuel;
@@ -58,5 +70,5 @@ pub fn run_part_2(nanofactory: &Nanofactory) {
         mid = (ciel + floor) / 2;
     }
 
-    println!("Day 14 Part 2 Solution: {}", mid);
+    mid
 }
@@ -17,7 +17,7 @@ impl Ingredient {
     pub fn new_from_str(ingredient_str: &str) -> Ingredient {
         let split_str: Vec<&str> = ingredient_str.split(' ').collect();
 
-        let quantity = split_str.get(0).unwrap().parse().unwrap();
+        let quantity = split_str.first().unwrap().parse().unwrap();
         let name = split_str.get(1).unwrap().to_string();
 
         Ingredient { name, quantity }
@@ -40,11 +40,10 @@ impl Reaction {
         let (reaction_type, produces) = (reaction_ingredient.name, reaction_ingredient.quantity);
 
         let ingredients: Vec<Ingredient> = temp
-            .get(0)
+            .first()
             .unwrap()
             .split(", ")
-            .into_iter()
-            .map(|s| Ingredient::new_from_str(s))
+            .map(Ingredient::new_from_str)
             .collect();
 
         Reaction {
@@ -55,7 +54,7 @@ impl Reaction {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct Nanofactory {
     reactions: HashMap<String, Reaction>,
     leftovers: HashMap<String, u64>,
@@ -0,0 +1,74 @@
+pub mod repair_droid;
+pub mod ship_map;
+
+use crate::file_reader::PuzzleInput;
+use crate::solution::Solution;
+
+pub use repair_droid::{Droid, MovementCommand, RepairDroid, StatusCode};
+pub use ship_map::{ShipMap, Tile};
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+
+    type Input = ShipMap;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    // Both parts use the same map, so the droid only explores the ship once.
+    fn parse(&self, input: &PuzzleInput) -> Result<ShipMap, String> {
+        let program_values = input.split_commas()?;
+        let mut droid = RepairDroid::new(program_values.as_slice());
+
+        ShipMap::explore(&mut droid)
+    }
+
+    fn part_1(&self, ship_map: &ShipMap) -> Result<usize, String> {
+        ship_map
+            .shortest_path_to_oxygen_system()
+            .ok_or_else(|| String::from("The droid never found the oxygen system"))
+    }
+
+    fn part_2(&self, ship_map: &ShipMap) -> Result<usize, String> {
+        ship_map
+            .minutes_to_fill_with_oxygen()
+            .ok_or_else(|| String::from("The droid never found the oxygen system"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load_fixture(file_name: &str) -> ShipMap {
+        let input = PuzzleInput::from_file(&format!("test_inputs/{}", file_name)).unwrap();
+
+        Day15.parse(&input).unwrap()
+    }
+
+    // The fixture is a droid in a two tile ship, with the oxygen system just
+    // north of the start.
+    #[test]
+    fn test_part_1() {
+        let ship_map = load_fixture("day_15_part_1.txt");
+
+        let expected = 1;
+
+        let result = Day15.part_1(&ship_map).unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_part_2() {
+        let ship_map = load_fixture("day_15_part_1.txt");
+
+        let expected = 1;
+
+        let result = Day15.part_2(&ship_map).unwrap();
+
+        assert_eq!(result, expected);
+        assert_eq!(ship_map.render_to_string(), " # \n#O#\n#D#\n # ");
+    }
+}
@@ -0,0 +1,140 @@
+use crate::intcode_computer::{IntcodeComputer, IntcodeComputerStatus};
+use crate::location::point_2d::Point2d;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum MovementCommand {
+    North = 1,
+    South = 2,
+    West = 3,
+    East = 4,
+}
+
+impl MovementCommand {
+    pub const ALL: [MovementCommand; 4] = [
+        MovementCommand::North,
+        MovementCommand::South,
+        MovementCommand::West,
+        MovementCommand::East,
+    ];
+
+    pub fn get_opposite(&self) -> MovementCommand {
+        match self {
+            MovementCommand::North => MovementCommand::South,
+            MovementCommand::South => MovementCommand::North,
+            MovementCommand::West => MovementCommand::East,
+            MovementCommand::East => MovementCommand::West,
+        }
+    }
+
+    // North is up the screen, so it's towards smaller y values
+    pub fn move_from(&self, location: Point2d<i32>) -> Point2d<i32> {
+        match self {
+            MovementCommand::North => Point2d::new(location.x, location.y - 1),
+            MovementCommand::South => Point2d::new(location.x, location.y + 1),
+            MovementCommand::West => Point2d::new(location.x - 1, location.y),
+            MovementCommand::East => Point2d::new(location.x + 1, location.y),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum StatusCode {
+    HitWall = 0,
+    Moved = 1,
+    FoundOxygenSystem = 2,
+}
+
+impl StatusCode {
+    pub fn from_output(output: i128) -> Result<StatusCode, String> {
+        match output {
+            0 => Ok(StatusCode::HitWall),
+            1 => Ok(StatusCode::Moved),
+            2 => Ok(StatusCode::FoundOxygenSystem),
+            _ => Err(format!("Unexpected status code from the droid: {}", output)),
+        }
+    }
+}
+
+// Anything that can be sent movement commands, so the map can be explored
+// without an Intcode program in tests.
+pub trait Droid {
+    fn move_droid(&mut self, command: MovementCommand) -> Result<StatusCode, String>;
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RepairDroid {
+    brain: IntcodeComputer,
+}
+
+impl RepairDroid {
+    pub fn new<T>(computer_program: T) -> RepairDroid
+    where
+        T: Into<IntcodeComputer>,
+    {
+        let mut brain = computer_program.into();
+
+        brain.execute_program();
+
+        RepairDroid { brain }
+    }
+}
+
+impl Droid for RepairDroid {
+    fn move_droid(&mut self, command: MovementCommand) -> Result<StatusCode, String> {
+        if self.brain.get_status() != IntcodeComputerStatus::WaitingForInput {
+            return Err(String::from("The droid stopped taking commands"));
+        }
+
+        self.brain.set_input(command as i128);
+        self.brain.execute_program();
+
+        match self.brain.get_output_cache().as_slice() {
+            [output] => StatusCode::from_output(*output),
+            outputs => Err(format!(
+                "Expected one status code from the droid, got {}",
+                outputs.len()
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_movement_command() {
+        let location = Point2d::new(3, -2);
+
+        for command in MovementCommand::ALL.iter() {
+            let result = command
+                .get_opposite()
+                .move_from(command.move_from(location));
+
+            assert_eq!(result, location);
+        }
+
+        assert_eq!(
+            MovementCommand::North.move_from(location),
+            Point2d::new(3, -3)
+        );
+    }
+
+    #[test]
+    fn test_repair_droid() {
+        // Reads a command, answers that it hit a wall and loops forever
+        let program = [3, 100, 104, 0, 1105, 1, 0];
+
+        let mut droid = RepairDroid::new(&program[..]);
+
+        assert_eq!(
+            droid.move_droid(MovementCommand::East),
+            Ok(StatusCode::HitWall)
+        );
+        assert_eq!(
+            RepairDroid::new(&[99][..]).move_droid(MovementCommand::East),
+            Err(String::from("The droid stopped taking commands"))
+        );
+        assert!(StatusCode::from_output(3).is_err());
+    }
+}
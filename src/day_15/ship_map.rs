@@ -0,0 +1,278 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use super::repair_droid::{Droid, MovementCommand, StatusCode};
+use crate::graph_search;
+use crate::location::point_2d::Point2d;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Tile {
+    Wall,
+    Open,
+    OxygenSystem,
+}
+
+// The area around the droid's starting point at (0, 0), as far as it's known.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct ShipMap {
+    tiles: HashMap<Point2d<i32>, Tile>,
+}
+
+impl ShipMap {
+    pub fn new() -> ShipMap {
+        ShipMap::default()
+    }
+
+    // Walks the droid down every corridor and back again, so the droid ends up
+    // where it started.
+    pub fn explore<D: Droid>(droid: &mut D) -> Result<ShipMap, String> {
+        let start = Point2d::new(0, 0);
+
+        let mut ship_map = ShipMap::new();
+
+        ship_map.tiles.insert(start, Tile::Open);
+        ship_map.explore_from(droid, start)?;
+
+        Ok(ship_map)
+    }
+
+    pub fn get_tile(&self, location: &Point2d<i32>) -> Option<Tile> {
+        self.tiles.get(location).copied()
+    }
+
+    pub fn get_oxygen_system(&self) -> Option<Point2d<i32>> {
+        self.tiles
+            .iter()
+            .find(|(_, &tile)| tile == Tile::OxygenSystem)
+            .map(|(&location, _)| location)
+    }
+
+    pub fn shortest_path_to_oxygen_system(&self) -> Option<usize> {
+        let oxygen_system = self.get_oxygen_system()?;
+
+        self.distances_from(Point2d::new(0, 0))
+            .get(&oxygen_system)
+            .copied()
+    }
+
+    // Oxygen spreads to every neighbouring open tile each minute.
+    pub fn minutes_to_fill_with_oxygen(&self) -> Option<usize> {
+        let oxygen_system = self.get_oxygen_system()?;
+
+        self.distances_from(oxygen_system).values().max().copied()
+    }
+
+    // Unexplored tiles are blank and the droid's starting point is a D.
+    pub fn render_to_string(&self) -> String {
+        let (min_x, max_x, min_y, max_y) = match self.get_bounds() {
+            Some(bounds) => bounds,
+            None => return String::new(),
+        };
+
+        (min_y..=max_y)
+            .map(|y| {
+                (min_x..=max_x)
+                    .map(|x| match (x, y, self.get_tile(&Point2d::new(x, y))) {
+                        (_, _, Some(Tile::Wall)) => '#',
+                        (_, _, Some(Tile::OxygenSystem)) => 'O',
+                        (0, 0, Some(Tile::Open)) => 'D',
+                        (_, _, Some(Tile::Open)) => '.',
+                        (_, _, None) => ' ',
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    fn explore_from<D: Droid>(
+        &mut self,
+        droid: &mut D,
+        location: Point2d<i32>,
+    ) -> Result<(), String> {
+        for &command in MovementCommand::ALL.iter() {
+            let next_location = command.move_from(location);
+
+            if self.tiles.contains_key(&next_location) {
+                continue;
+            }
+
+            let tile = match droid.move_droid(command)? {
+                StatusCode::HitWall => {
+                    self.tiles.insert(next_location, Tile::Wall);
+
+                    continue;
+                }
+                StatusCode::Moved => Tile::Open,
+                StatusCode::FoundOxygenSystem => Tile::OxygenSystem,
+            };
+
+            self.tiles.insert(next_location, tile);
+            self.explore_from(droid, next_location)?;
+
+            if droid.move_droid(command.get_opposite())? == StatusCode::HitWall {
+                return Err(format!(
+                    "The droid couldn't move back to ({}, {})",
+                    location.x, location.y
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    // Distances to everything that isn't a wall
+    fn distances_from(&self, start: Point2d<i32>) -> HashMap<Point2d<i32>, usize> {
+        graph_search::bfs(start, |&location| {
+            MovementCommand::ALL
+                .iter()
+                .map(|command| command.move_from(location))
+                .filter(|next_location| {
+                    matches!(
+                        self.get_tile(next_location),
+                        Some(Tile::Open) | Some(Tile::OxygenSystem)
+                    )
+                })
+                .collect::<Vec<Point2d<i32>>>()
+        })
+    }
+
+    fn get_bounds(&self) -> Option<(i32, i32, i32, i32)> {
+        let min_x = self.tiles.keys().map(|location| location.x).min()?;
+        let max_x = self.tiles.keys().map(|location| location.x).max()?;
+        let min_y = self.tiles.keys().map(|location| location.y).min()?;
+        let max_y = self.tiles.keys().map(|location| location.y).max()?;
+
+        Some((min_x, max_x, min_y, max_y))
+    }
+}
+
+// Reads maps in the same format render_to_string writes them, with the D
+// marking (0, 0).
+impl FromStr for ShipMap {
+    type Err = String;
+
+    fn from_str(map_str: &str) -> Result<ShipMap, String> {
+        let rows: Vec<Vec<char>> = map_str.lines().map(|line| line.chars().collect()).collect();
+
+        let (start_x, start_y) = rows
+            .iter()
+            .enumerate()
+            .find_map(|(y, row)| row.iter().position(|&c| c == 'D').map(|x| (x, y)))
+            .ok_or("The map doesn't have a droid")?;
+
+        let mut ship_map = ShipMap::new();
+
+        for (y, row) in rows.iter().enumerate() {
+            for (x, &c) in row.iter().enumerate() {
+                let tile = match c {
+                    '#' => Tile::Wall,
+                    '.' | 'D' => Tile::Open,
+                    'O' => Tile::OxygenSystem,
+                    ' ' => continue,
+                    _ => return Err(format!("Unexpected tile {} in the map", c)),
+                };
+
+                let location = Point2d::new(x as i32 - start_x as i32, y as i32 - start_y as i32);
+
+                ship_map.tiles.insert(location, tile);
+            }
+        }
+
+        Ok(ship_map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAZE: &str = "#######
+#D..#.#
+#.#.#.#
+#.#...#
+#.###O#
+#######";
+
+    // Walks around a known map instead of running an Intcode program
+    struct MazeDroid {
+        ship_map: ShipMap,
+        location: Point2d<i32>,
+    }
+
+    impl Droid for MazeDroid {
+        fn move_droid(&mut self, command: MovementCommand) -> Result<StatusCode, String> {
+            let next_location = command.move_from(self.location);
+
+            match self.ship_map.get_tile(&next_location) {
+                Some(Tile::Wall) => Ok(StatusCode::HitWall),
+                Some(tile) => {
+                    self.location = next_location;
+
+                    match tile {
+                        Tile::OxygenSystem => Ok(StatusCode::FoundOxygenSystem),
+                        _ => Ok(StatusCode::Moved),
+                    }
+                }
+                None => Err(String::from("The droid fell off the map")),
+            }
+        }
+    }
+
+    #[test]
+    fn test_shortest_path_to_oxygen_system() {
+        let ship_map: ShipMap = MAZE.parse().unwrap();
+
+        assert_eq!(ship_map.shortest_path_to_oxygen_system(), Some(7));
+        assert_eq!(ShipMap::new().shortest_path_to_oxygen_system(), None);
+    }
+
+    #[test]
+    fn test_minutes_to_fill_with_oxygen() {
+        let ship_map: ShipMap = " ##   \n#D.## \n#.#..#\n#.O.# \n ###  ".parse().unwrap();
+
+        let expected = Some(4);
+
+        let result = ship_map.minutes_to_fill_with_oxygen();
+
+        assert_eq!(result, expected);
+        assert_eq!(
+            MAZE.parse::<ShipMap>()
+                .unwrap()
+                .minutes_to_fill_with_oxygen(),
+            Some(10)
+        );
+    }
+
+    #[test]
+    fn test_explore() {
+        let mut droid = MazeDroid {
+            ship_map: MAZE.parse().unwrap(),
+            location: Point2d::new(0, 0),
+        };
+
+        // The corners are never next to an open tile, so they're never explored
+        let expected = " ### # \n\
+                        #D..#.#\n\
+                        #.#.#.#\n\
+                        #.#...#\n\
+                        #.###O#\n \
+                        #   # ";
+
+        let result = ShipMap::explore(&mut droid).unwrap();
+
+        assert_eq!(result.render_to_string(), expected);
+        assert_eq!(result.shortest_path_to_oxygen_system(), Some(7));
+        assert_eq!(droid.location, Point2d::new(0, 0));
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(
+            ".O.".parse::<ShipMap>(),
+            Err(String::from("The map doesn't have a droid"))
+        );
+        assert!("D?".parse::<ShipMap>().is_err());
+        assert_eq!(MAZE.parse::<ShipMap>().unwrap().render_to_string(), MAZE);
+    }
+}
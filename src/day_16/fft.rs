@@ -0,0 +1,216 @@
+const BASE_PATTERN: [i32; 4] = [0, 1, 0, -1];
+
+const MESSAGE_LENGTH: usize = 8;
+const OFFSET_LENGTH: usize = 7;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Algorithm {
+    // Works anywhere in the signal, but is O(n²) per phase
+    Naive,
+    // Only works in the second half of the signal, where the pattern is all
+    // 0s and then all 1s, but is O(n) per phase
+    SuffixSum,
+}
+
+impl Algorithm {
+    pub fn choose(offset: usize, signal_length: usize) -> Algorithm {
+        if offset >= signal_length / 2 {
+            Algorithm::SuffixSum
+        } else {
+            Algorithm::Naive
+        }
+    }
+}
+
+// Flawed Frequency Transmission
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Fft {
+    signal: Vec<i32>,
+}
+
+impl Fft {
+    pub fn new(signal: Vec<i32>) -> Fft {
+        Fft { signal }
+    }
+
+    pub fn from_digits(digits: &str) -> Result<Fft, String> {
+        digits
+            .trim()
+            .chars()
+            .map(|c| {
+                c.to_digit(10)
+                    .map(|digit| digit as i32)
+                    .ok_or_else(|| format!("Signals should only have digits, got {}", c))
+            })
+            .collect::<Result<Vec<i32>, String>>()
+            .map(Fft::new)
+    }
+
+    pub fn get_signal(&self) -> &[i32] {
+        &self.signal
+    }
+
+    pub fn run_phases(&self, phases: usize) -> Vec<i32> {
+        (0..phases).fold(self.signal.clone(), |signal, _| naive_phase(&signal))
+    }
+
+    // The first seven digits of the signal
+    pub fn get_message_offset(&self) -> Option<usize> {
+        if self.signal.len() < OFFSET_LENGTH {
+            return None;
+        }
+
+        Some(
+            self.signal[..OFFSET_LENGTH]
+                .iter()
+                .fold(0, |offset, &digit| offset * 10 + digit as usize),
+        )
+    }
+
+    // The eight digit message at `offset` once the signal, repeated `repeat`
+    // times, has been through `phases` phases.
+    pub fn decode_message(
+        &self,
+        repeat: usize,
+        phases: usize,
+        offset: usize,
+    ) -> Result<Vec<i32>, String> {
+        let signal_length = self.signal.len() * repeat;
+
+        if offset + MESSAGE_LENGTH > signal_length {
+            return Err(format!(
+                "The message offset {} is past the end of the signal",
+                offset
+            ));
+        }
+
+        let message = match Algorithm::choose(offset, signal_length) {
+            Algorithm::Naive => {
+                let repeated_fft = Fft::new(self.signal.repeat(repeat));
+
+                repeated_fft.run_phases(phases)[offset..].to_vec()
+            }
+            Algorithm::SuffixSum => {
+                let mut tail: Vec<i32> = (offset..signal_length)
+                    .map(|index| self.signal[index % self.signal.len()])
+                    .collect();
+
+                for _ in 0..phases {
+                    suffix_sum_phase(&mut tail);
+                }
+
+                tail
+            }
+        };
+
+        Ok(message[..MESSAGE_LENGTH].to_vec())
+    }
+}
+
+pub fn get_pattern_value(output_index: usize, input_index: usize) -> i32 {
+    BASE_PATTERN[((input_index + 1) / (output_index + 1)) % BASE_PATTERN.len()]
+}
+
+pub fn naive_phase(signal: &[i32]) -> Vec<i32> {
+    (0..signal.len())
+        .map(|output_index| {
+            // the pattern is 0 before the output index
+            let total: i32 = signal
+                .iter()
+                .enumerate()
+                .skip(output_index)
+                .map(|(input_index, &value)| value * get_pattern_value(output_index, input_index))
+                .sum();
+
+            total.abs() % 10
+        })
+        .collect()
+}
+
+// For digits in the second half of the signal, each output digit is the sum of
+// every digit from it to the end of the signal.
+pub fn suffix_sum_phase(tail: &mut [i32]) {
+    let mut sum = 0;
+
+    for value in tail.iter_mut().rev() {
+        sum = (sum + *value) % 10;
+        *value = sum;
+    }
+}
+
+pub fn digits_to_string(digits: &[i32]) -> String {
+    digits.iter().map(|digit| digit.to_string()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_pattern_value() {
+        let expected = vec![0, 1, 1, 0, 0, -1, -1, 0, 0, 1];
+
+        let result: Vec<i32> = (0..10).map(|index| get_pattern_value(1, index)).collect();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_run_phases() {
+        let fft = Fft::from_digits("12345678").unwrap();
+
+        assert_eq!(digits_to_string(&fft.run_phases(1)), "48226158");
+        assert_eq!(digits_to_string(&fft.run_phases(4)), "01029498");
+    }
+
+    #[test]
+    fn test_suffix_sum_phase() {
+        let fft = Fft::from_digits("12345678").unwrap();
+
+        let expected = fft.run_phases(1)[4..].to_vec();
+
+        let mut result = fft.get_signal()[4..].to_vec();
+        suffix_sum_phase(&mut result);
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_decode_message() {
+        let fft = Fft::from_digits("03036732577212944063491565474664").unwrap();
+
+        let offset = fft.get_message_offset().unwrap();
+
+        let result = fft.decode_message(10_000, 100, offset).unwrap();
+
+        assert_eq!(offset, 303_673);
+        assert_eq!(digits_to_string(&result), "84462026");
+    }
+
+    #[test]
+    fn test_decode_message_algorithms_agree() {
+        let fft = Fft::from_digits("69317163492948606335995924319873").unwrap();
+
+        let expected = Fft::new(fft.get_signal().repeat(4)).run_phases(10)[70..78].to_vec();
+
+        let result = fft.decode_message(4, 10, 70).unwrap();
+
+        assert_eq!(Algorithm::choose(70, 128), Algorithm::SuffixSum);
+        assert_eq!(Algorithm::choose(10, 128), Algorithm::Naive);
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(Fft::from_digits("12a4").is_err());
+        assert_eq!(Fft::from_digits("123").unwrap().get_message_offset(), None);
+        assert_eq!(
+            Fft::from_digits("12345678")
+                .unwrap()
+                .decode_message(1, 1, 1),
+            Err(String::from(
+                "The message offset 1 is past the end of the signal"
+            ))
+        );
+    }
+}
@@ -0,0 +1,84 @@
+pub mod fft;
+
+use crate::file_reader::PuzzleInput;
+use crate::solution::Solution;
+
+pub use fft::{Algorithm, Fft};
+
+const PHASES: usize = 100;
+const SIGNAL_REPEATS: usize = 10_000;
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+
+    type Input = Fft;
+    type Answer1 = String;
+    type Answer2 = String;
+
+    fn parse(&self, input: &PuzzleInput) -> Result<Fft, String> {
+        Fft::from_digits(input.get_first_line()?)
+    }
+
+    fn part_1(&self, fft: &Fft) -> Result<String, String> {
+        let output = fft.run_phases(PHASES);
+
+        Ok(fft::digits_to_string(&output[..output.len().min(8)]))
+    }
+
+    fn part_2(&self, fft: &Fft) -> Result<String, String> {
+        let offset = fft
+            .get_message_offset()
+            .ok_or_else(|| String::from("The signal is too short to have a message offset"))?;
+
+        let message = fft.decode_message(SIGNAL_REPEATS, PHASES, offset)?;
+
+        Ok(fft::digits_to_string(&message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::benchmark::{self, Stage};
+
+    fn load_fixture(file_name: &str) -> Fft {
+        let input = PuzzleInput::from_file(&format!("test_inputs/{}", file_name)).unwrap();
+
+        Day16.parse(&input).unwrap()
+    }
+
+    #[test]
+    fn test_part_1() {
+        let fft = load_fixture("day_16_part_1.txt");
+
+        let expected = "24176176";
+
+        let result = Day16.part_1(&fft).unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_part_2() {
+        let input = PuzzleInput::from_string("signal", "02935109699940807407585447034323\n");
+
+        let expected = "78725270";
+
+        let result = Day16.part_2(&Day16.parse(&input).unwrap()).unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_benchmark() {
+        let input = PuzzleInput::from_string("signal", "03036732577212944063491565474664\n");
+
+        let result = benchmark::benchmark_day(&Day16, &input, 2).unwrap();
+
+        assert_eq!(result.len(), 3);
+        assert_eq!(result[2].stage, Stage::Part2);
+    }
+}
@@ -0,0 +1,83 @@
+pub mod scaffold_map;
+pub mod vacuum_robot;
+
+use crate::compression;
+use crate::file_reader::PuzzleInput;
+use crate::solution::Solution;
+
+pub use scaffold_map::{Command, ScaffoldMap};
+pub use vacuum_robot::VacuumRobot;
+
+const MAX_FUNCTIONS: usize = 3;
+const MAX_ROUTINE_LENGTH: usize = 20;
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+
+    type Input = Vec<String>;
+    type Answer1 = i32;
+    type Answer2 = i128;
+
+    fn parse(&self, input: &PuzzleInput) -> Result<Vec<String>, String> {
+        Ok(input.split_commas()?)
+    }
+
+    fn part_1(&self, program_values: &Vec<String>) -> Result<i32, String> {
+        let scaffold_map = get_scaffold_map(program_values)?;
+
+        Ok(scaffold_map.get_alignment_parameters_sum())
+    }
+
+    fn part_2(&self, program_values: &Vec<String>) -> Result<i128, String> {
+        let path = get_scaffold_map(program_values)?.trace_path()?;
+
+        let movement_routine = compression::compress(&path, MAX_FUNCTIONS, MAX_ROUTINE_LENGTH)
+            .ok_or_else(|| String::from("The path doesn't fit in the movement routine"))?;
+
+        VacuumRobot::new(program_values.as_slice()).run_movement_routine(&movement_routine, false)
+    }
+}
+
+fn get_scaffold_map(program_values: &[String]) -> Result<ScaffoldMap, String> {
+    VacuumRobot::new(program_values).get_camera_view()?.parse()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load_fixture(file_name: &str) -> Vec<String> {
+        let input = PuzzleInput::from_file(&format!("test_inputs/{}", file_name)).unwrap();
+
+        Day17.parse(&input).unwrap()
+    }
+
+    // The fixture prints the camera view from the puzzle description
+    #[test]
+    fn test_part_1() {
+        let program_values = load_fixture("day_17_part_1.txt");
+
+        let expected = 76;
+
+        let result = Day17.part_1(&program_values).unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    // The fixture prints the part 2 camera view from the puzzle description.
+    // Once woken up it checks every value of the movement routine against
+    // A,A,B,B,C,B,B,A,A,C / R,8 / R,4 / R,8,L,6,L,2 / n and only reports the
+    // dust if they all match.
+    #[test]
+    fn test_part_2() {
+        let program_values = load_fixture("day_17_part_2.txt");
+
+        let expected = 1234;
+
+        let result = Day17.part_2(&program_values).unwrap();
+
+        assert_eq!(result, expected);
+    }
+}
@@ -0,0 +1,265 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::location::point_2d::Point2d;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Command {
+    TurnLeft,
+    TurnRight,
+    Forward(usize),
+}
+
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Command::TurnLeft => write!(f, "L"),
+            Command::TurnRight => write!(f, "R"),
+            Command::Forward(distance) => write!(f, "{}", distance),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Facing {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Facing {
+    fn from_char(c: char) -> Option<Facing> {
+        match c {
+            '^' => Some(Facing::Up),
+            '>' => Some(Facing::Right),
+            'v' => Some(Facing::Down),
+            '<' => Some(Facing::Left),
+            _ => None,
+        }
+    }
+
+    fn turn_left(&self) -> Facing {
+        match self {
+            Facing::Up => Facing::Left,
+            Facing::Left => Facing::Down,
+            Facing::Down => Facing::Right,
+            Facing::Right => Facing::Up,
+        }
+    }
+
+    fn turn_right(&self) -> Facing {
+        match self {
+            Facing::Up => Facing::Right,
+            Facing::Right => Facing::Down,
+            Facing::Down => Facing::Left,
+            Facing::Left => Facing::Up,
+        }
+    }
+
+    fn step_from(&self, location: Point2d<i32>) -> Point2d<i32> {
+        match self {
+            Facing::Up => Point2d::new(location.x, location.y - 1),
+            Facing::Right => Point2d::new(location.x + 1, location.y),
+            Facing::Down => Point2d::new(location.x, location.y + 1),
+            Facing::Left => Point2d::new(location.x - 1, location.y),
+        }
+    }
+}
+
+// The ASCII camera view of the scaffolding, with (0, 0) in the top left.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct ScaffoldMap {
+    grid: Vec<Vec<char>>,
+}
+
+impl ScaffoldMap {
+    pub fn new(grid: Vec<Vec<char>>) -> ScaffoldMap {
+        ScaffoldMap { grid }
+    }
+
+    pub fn is_scaffold(&self, location: &Point2d<i32>) -> bool {
+        match self.get_char(location) {
+            Some(c) => c == '#' || Facing::from_char(c).is_some(),
+            None => false,
+        }
+    }
+
+    pub fn get_intersections(&self) -> Vec<Point2d<i32>> {
+        let mut intersections = Vec::new();
+
+        for (y, row) in self.grid.iter().enumerate() {
+            for x in 0..row.len() {
+                let location = Point2d::new(x as i32, y as i32);
+
+                let is_intersection = self.is_scaffold(&location)
+                    && [Facing::Up, Facing::Right, Facing::Down, Facing::Left]
+                        .iter()
+                        .all(|facing| self.is_scaffold(&facing.step_from(location)));
+
+                if is_intersection {
+                    intersections.push(location);
+                }
+            }
+        }
+
+        intersections
+    }
+
+    pub fn get_alignment_parameters_sum(&self) -> i32 {
+        self.get_intersections()
+            .iter()
+            .map(|location| location.x * location.y)
+            .sum()
+    }
+
+    // Follows the scaffold from the robot to the end, going straight through
+    // every intersection.
+    pub fn trace_path(&self) -> Result<Vec<Command>, String> {
+        let (mut location, mut facing) =
+            self.find_robot().ok_or("The robot isn't on the scaffold")?;
+
+        let mut commands = Vec::new();
+
+        loop {
+            let mut distance = 0;
+
+            while self.is_scaffold(&facing.step_from(location)) {
+                location = facing.step_from(location);
+                distance += 1;
+            }
+
+            if distance > 0 {
+                commands.push(Command::Forward(distance));
+            }
+
+            if self.is_scaffold(&facing.turn_left().step_from(location)) {
+                facing = facing.turn_left();
+                commands.push(Command::TurnLeft);
+            } else if self.is_scaffold(&facing.turn_right().step_from(location)) {
+                facing = facing.turn_right();
+                commands.push(Command::TurnRight);
+            } else {
+                return Ok(commands);
+            }
+        }
+    }
+
+    fn find_robot(&self) -> Option<(Point2d<i32>, Facing)> {
+        self.grid.iter().enumerate().find_map(|(y, row)| {
+            row.iter().enumerate().find_map(|(x, &c)| {
+                Facing::from_char(c).map(|facing| (Point2d::new(x as i32, y as i32), facing))
+            })
+        })
+    }
+
+    fn get_char(&self, location: &Point2d<i32>) -> Option<char> {
+        if location.x < 0 || location.y < 0 {
+            return None;
+        }
+
+        self.grid
+            .get(location.y as usize)?
+            .get(location.x as usize)
+            .copied()
+    }
+}
+
+impl FromStr for ScaffoldMap {
+    type Err = String;
+
+    fn from_str(camera_view: &str) -> Result<ScaffoldMap, String> {
+        let grid: Vec<Vec<char>> = camera_view
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(|line| line.chars().collect())
+            .collect();
+
+        if let Some(c) = grid
+            .iter()
+            .flatten()
+            .find(|&&c| c != '.' && c != '#' && c != 'X' && Facing::from_char(c).is_none())
+        {
+            return Err(format!("Unexpected character {} in the camera view", c));
+        }
+
+        Ok(ScaffoldMap::new(grid))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::compression;
+
+    const CAMERA_VIEW: &str = "#######...#####
+#.....#...#...#
+#.....#...#...#
+......#...#...#
+......#...###.#
+......#.....#.#
+^########...#.#
+......#.#...#.#
+......#########
+........#...#..
+....#########..
+....#...#......
+....#...#......
+....#...#......
+....#####......
+";
+
+    #[test]
+    fn test_get_alignment_parameters_sum() {
+        let scaffold_map: ScaffoldMap = "..#..........
+..#..........
+#######...###
+#.#...#...#.#
+#############
+..#...#...#..
+..#####...^..
+"
+        .parse()
+        .unwrap();
+
+        let expected = vec![
+            Point2d::new(2, 2),
+            Point2d::new(2, 4),
+            Point2d::new(6, 4),
+            Point2d::new(10, 4),
+        ];
+
+        assert_eq!(scaffold_map.get_intersections(), expected);
+        assert_eq!(scaffold_map.get_alignment_parameters_sum(), 76);
+    }
+
+    #[test]
+    fn test_trace_path() {
+        let scaffold_map: ScaffoldMap = CAMERA_VIEW.parse().unwrap();
+
+        let expected = "R,8,R,8,R,4,R,4,R,8,L,6,L,2,R,4,R,4,R,8,R,8,R,8,L,6,L,2";
+
+        let result = scaffold_map.trace_path().unwrap();
+
+        assert_eq!(compression::join_tokens(&result), expected);
+    }
+
+    #[test]
+    fn test_trace_path_starting_forwards() {
+        let scaffold_map: ScaffoldMap = ">##\n..#\n".parse().unwrap();
+
+        let expected = vec![Command::Forward(2), Command::TurnRight, Command::Forward(1)];
+
+        assert_eq!(scaffold_map.trace_path(), Ok(expected));
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert!("#.?".parse::<ScaffoldMap>().is_err());
+        assert_eq!(
+            "#.X".parse::<ScaffoldMap>().unwrap().trace_path(),
+            Err(String::from("The robot isn't on the scaffold"))
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use std::fmt::Display;
+
+use crate::compression::Compression;
+use crate::intcode_computer::{IntcodeComputer, IntcodeComputerStatus};
+
+const NUMBER_OF_FUNCTIONS: usize = 3;
+
+#[derive(Debug, PartialEq)]
+pub struct VacuumRobot {
+    brain: IntcodeComputer,
+}
+
+impl VacuumRobot {
+    pub fn new<T>(computer_program: T) -> VacuumRobot
+    where
+        T: Into<IntcodeComputer>,
+    {
+        VacuumRobot {
+            brain: computer_program.into(),
+        }
+    }
+
+    pub fn get_camera_view(&mut self) -> Result<String, String> {
+        self.brain.execute_program();
+
+        if self.brain.get_status() != IntcodeComputerStatus::Finished {
+            return Err(String::from("The camera wanted input"));
+        }
+
+        Ok(to_ascii_string(&self.brain.get_outputs()))
+    }
+
+    // Wakes the robot up, enters the movement routine and returns the amount
+    // of dust it collected.
+    pub fn run_movement_routine<T>(
+        &mut self,
+        compression: &Compression<T>,
+        video_feed: bool,
+    ) -> Result<i128, String>
+    where
+        T: Clone + Display,
+    {
+        self.brain.replace_code_in_program(0, 2);
+        self.brain.execute_program();
+
+        for value in get_movement_input(compression, video_feed).bytes() {
+            if self.brain.get_status() != IntcodeComputerStatus::WaitingForInput {
+                return Err(String::from(
+                    "The robot stopped reading the movement routine",
+                ));
+            }
+
+            self.brain.set_input(value as i128);
+            self.brain.execute_program();
+        }
+
+        self.brain
+            .get_latest_output()
+            .ok_or_else(|| String::from("The robot didn't report any dust"))
+    }
+
+    pub fn get_ascii_output(&self) -> String {
+        to_ascii_string(&self.brain.get_outputs())
+    }
+}
+
+// The main routine, then each function, then whether to show the video feed,
+// each on its own line. Unused functions are left empty.
+pub fn get_movement_input<T>(compression: &Compression<T>, video_feed: bool) -> String
+where
+    T: Clone + Display,
+{
+    let mut lines = vec![compression.get_main_routine_string()];
+
+    lines.extend(compression.get_function_strings());
+    lines.resize(NUMBER_OF_FUNCTIONS + 1, String::new());
+    lines.push(String::from(if video_feed { "y" } else { "n" }));
+
+    lines.join("\n") + "\n"
+}
+
+// Values outside of ASCII, like the amount of dust, are skipped.
+fn to_ascii_string(outputs: &[i128]) -> String {
+    outputs
+        .iter()
+        .filter(|&&value| (0..128).contains(&value))
+        .map(|&value| value as u8 as char)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compression() -> Compression<&'static str> {
+        Compression {
+            main_routine: vec![0, 1, 0],
+            functions: vec![vec!["R", "8"], vec!["L", "10"]],
+        }
+    }
+
+    #[test]
+    fn test_get_movement_input() {
+        let expected = "A,B,A\nR,8\nL,10\n\nn\n";
+
+        assert_eq!(get_movement_input(&compression(), false), expected);
+    }
+
+    #[test]
+    fn test_get_camera_view() {
+        let mut robot = VacuumRobot::new(&[104, 35, 104, 46, 104, 10, 99][..]);
+
+        assert_eq!(robot.get_camera_view(), Ok(String::from("#.\n")));
+        assert!(VacuumRobot::new(&[3, 0, 99][..]).get_camera_view().is_err());
+    }
+
+    #[test]
+    fn test_run_movement_routine() {
+        // Waking the robot up overwrites the throwaway first instruction. Then it
+        // echoes every value it reads and stops after the fifth newline
+        let program = [
+            1, 0, 0, 0, 3, 100, 4, 100, 1008, 100, 10, 101, 1, 101, 102, 102, 1008, 102, 5, 101,
+            1006, 101, 4, 99,
+        ];
+
+        let mut robot = VacuumRobot::new(&program[..]);
+
+        let result = robot.run_movement_routine(&compression(), false);
+
+        assert_eq!(result, Ok(10));
+        assert_eq!(
+            robot.get_ascii_output(),
+            get_movement_input(&compression(), false)
+        );
+    }
+}
@@ -0,0 +1,56 @@
+pub mod vault;
+
+use crate::file_reader::PuzzleInput;
+use crate::solution::Solution;
+
+pub use vault::Vault;
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+
+    type Input = Vault;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &PuzzleInput) -> Result<Vault, String> {
+        input.get_lines().join("\n").parse()
+    }
+
+    fn part_1(&self, vault: &Vault) -> Result<usize, String> {
+        get_fewest_steps(vault)
+    }
+
+    fn part_2(&self, vault: &Vault) -> Result<usize, String> {
+        get_fewest_steps(&vault.split_into_quadrants()?)
+    }
+}
+
+fn get_fewest_steps(vault: &Vault) -> Result<usize, String> {
+    vault
+        .fewest_steps_to_collect_all_keys()
+        .ok_or_else(|| String::from("Some of the keys can't be collected"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load_fixture(file_name: &str) -> Vault {
+        let input = PuzzleInput::from_file(&format!("test_inputs/{}", file_name)).unwrap();
+
+        Day18.parse(&input).unwrap()
+    }
+
+    #[test]
+    fn test_part_1() {
+        let vault = load_fixture("day_18_part_1.txt");
+
+        let expected = 136;
+
+        let result = Day18.part_1(&vault).unwrap();
+
+        assert_eq!(result, expected);
+    }
+}
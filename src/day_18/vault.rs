@@ -0,0 +1,317 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use crate::graph_search;
+use crate::grid::Grid;
+use crate::location::point_2d::Point2d;
+
+const ENTRANCE: char = '@';
+const WALL: char = '#';
+const OPEN: char = '.';
+
+// Keys collected so far, with one bit per key from a to z
+pub type KeySet = u32;
+
+pub fn get_key_bit(key: char) -> KeySet {
+    1 << (key.to_ascii_lowercase() as u8 - b'a')
+}
+
+// A key that can be reached from somewhere else in the vault
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+struct Route {
+    key: char,
+    distance: usize,
+    // Keys for the doors along the way
+    required_keys: KeySet,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+struct SearchState {
+    // Where each robot is, either an entrance or a key it picked up
+    positions: Vec<char>,
+    collected_keys: KeySet,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Vault {
+    grid: Grid<char>,
+}
+
+impl Vault {
+    pub fn new(grid: Grid<char>) -> Vault {
+        Vault { grid }
+    }
+
+    pub fn get_entrances(&self) -> Vec<Point2d<i32>> {
+        self.grid
+            .iter()
+            .filter(|(_, &c)| c == ENTRANCE)
+            .map(|(location, _)| location)
+            .collect()
+    }
+
+    pub fn get_all_keys(&self) -> KeySet {
+        self.grid
+            .iter()
+            .filter(|(_, c)| c.is_ascii_lowercase())
+            .fold(0, |keys, (_, &key)| keys | get_key_bit(key))
+    }
+
+    // Walls off the middle of a vault with a single entrance, leaving a
+    // separate entrance in each corner for the four robots.
+    pub fn split_into_quadrants(&self) -> Result<Vault, String> {
+        let entrance = match self.get_entrances().as_slice() {
+            [entrance] => *entrance,
+            entrances => {
+                return Err(format!(
+                    "Only vaults with one entrance can be split, this one has {}",
+                    entrances.len()
+                ))
+            }
+        };
+
+        let mut grid = self.grid.clone();
+
+        for y_offset in -1..=1 {
+            for x_offset in -1..=1 {
+                let location = Point2d::new(entrance.x + x_offset, entrance.y + y_offset);
+
+                let expected = if (x_offset, y_offset) == (0, 0) {
+                    ENTRANCE
+                } else {
+                    OPEN
+                };
+
+                if self.grid.get(&location) != Some(&expected) {
+                    return Err(String::from("The entrance isn't in an open 3x3 area"));
+                }
+
+                let c = if x_offset != 0 && y_offset != 0 {
+                    ENTRANCE
+                } else {
+                    WALL
+                };
+
+                grid.set(&location, c);
+            }
+        }
+
+        Ok(Vault::new(grid))
+    }
+
+    // Robots move one at a time, and only the robot that's moving needs a path
+    // to the next key, so each robot only ever walks around its own part of
+    // the vault.
+    pub fn fewest_steps_to_collect_all_keys(&self) -> Option<usize> {
+        let entrances = self.get_entrances();
+        let all_keys = self.get_all_keys();
+
+        // Entrances are labelled with digits so they can share the route table
+        // with the keys.
+        let start_labels: Vec<char> = (0..entrances.len())
+            .map(|index| (b'0' + index as u8) as char)
+            .collect();
+
+        let mut routes: HashMap<char, Vec<Route>> = HashMap::new();
+
+        for (&label, entrance) in start_labels.iter().zip(entrances.iter()) {
+            routes.insert(label, self.find_routes_from(entrance));
+        }
+
+        for (location, &c) in self.grid.iter() {
+            if c.is_ascii_lowercase() {
+                routes.insert(c, self.find_routes_from(&location));
+            }
+        }
+
+        let start = SearchState {
+            positions: start_labels,
+            collected_keys: 0,
+        };
+
+        let get_successors = |state: &SearchState| {
+            let mut successors = Vec::new();
+
+            for (robot, position) in state.positions.iter().enumerate() {
+                for route in &routes[position] {
+                    let key_bit = get_key_bit(route.key);
+
+                    let can_take_route = state.collected_keys & key_bit == 0
+                        && route.required_keys & !state.collected_keys == 0;
+
+                    if can_take_route {
+                        let mut positions = state.positions.clone();
+                        positions[robot] = route.key;
+
+                        let successor = SearchState {
+                            positions,
+                            collected_keys: state.collected_keys | key_bit,
+                        };
+
+                        successors.push((successor, route.distance));
+                    }
+                }
+            }
+
+            successors
+        };
+
+        graph_search::dijkstra(start, get_successors, |state| {
+            state.collected_keys == all_keys
+        })
+        .map(|(steps, _)| steps)
+    }
+
+    // Shortest routes to every key reachable from `start`, going through doors
+    // whether or not their keys have been found yet.
+    fn find_routes_from(&self, start: &Point2d<i32>) -> Vec<Route> {
+        let mut required_keys = HashMap::new();
+
+        required_keys.insert(*start, 0);
+
+        let distances = graph_search::bfs(*start, |location| {
+            let keys_so_far = required_keys[location];
+
+            let neighbours: Vec<Point2d<i32>> = self
+                .grid
+                .get_neighbours(location)
+                .into_iter()
+                .filter(|neighbour| self.grid.get(neighbour) != Some(&WALL))
+                .collect();
+
+            for neighbour in &neighbours {
+                let keys = match self.grid.get(neighbour) {
+                    Some(&door) if door.is_ascii_uppercase() => keys_so_far | get_key_bit(door),
+                    _ => keys_so_far,
+                };
+
+                required_keys.entry(*neighbour).or_insert(keys);
+            }
+
+            neighbours
+        });
+
+        let mut routes: Vec<Route> = distances
+            .iter()
+            .filter_map(|(location, &distance)| match self.grid.get(location) {
+                Some(&key) if key.is_ascii_lowercase() && distance > 0 => Some(Route {
+                    key,
+                    distance,
+                    required_keys: required_keys[location],
+                }),
+                _ => None,
+            })
+            .collect();
+
+        routes.sort_by_key(|route| route.key);
+
+        routes
+    }
+}
+
+impl FromStr for Vault {
+    type Err = String;
+
+    fn from_str(map_str: &str) -> Result<Vault, String> {
+        let grid = Grid::from_text(map_str)?;
+
+        if let Some((location, c)) = grid
+            .iter()
+            .find(|(_, &c)| c != WALL && c != OPEN && c != ENTRANCE && !c.is_ascii_alphabetic())
+        {
+            return Err(format!(
+                "Unexpected {} at ({}, {}) in the vault",
+                c, location.x, location.y
+            ));
+        }
+
+        Ok(Vault::new(grid))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fewest_steps(map_str: &str) -> Option<usize> {
+        map_str
+            .parse::<Vault>()
+            .unwrap()
+            .fewest_steps_to_collect_all_keys()
+    }
+
+    #[test]
+    fn test_fewest_steps_to_collect_all_keys() {
+        assert_eq!(fewest_steps("#########\n#b.A.@.a#\n#########"), Some(8));
+        assert_eq!(
+            fewest_steps(
+                "########################
+#f.D.E.e.C.b.A.@.a.B.c.#
+######################.#
+#d.....................#
+########################"
+            ),
+            Some(86)
+        );
+        assert_eq!(
+            fewest_steps(
+                "########################
+#@..............ac.GI.b#
+###d#e#f################
+###A#B#C################
+###g#h#i################
+########################"
+            ),
+            Some(81)
+        );
+        assert_eq!(fewest_steps("#####\n#@A.#\n#####"), Some(0));
+        assert_eq!(fewest_steps("#######\n#@#.a.#\n#######"), None);
+    }
+
+    #[test]
+    fn test_fewest_steps_with_four_robots() {
+        let vault: Vault = "#############
+#g#f.D#..h#l#
+#F###e#E###.#
+#dCba...BcIJ#
+#####.@.#####
+#nK.L...G...#
+#M###N#H###.#
+#o#m..#i#jk.#
+#############"
+            .parse()
+            .unwrap();
+
+        let result = vault
+            .split_into_quadrants()
+            .unwrap()
+            .fewest_steps_to_collect_all_keys();
+
+        assert_eq!(result, Some(72));
+    }
+
+    #[test]
+    fn test_split_into_quadrants() {
+        let vault: Vault = "#######\n#a.#Cd#\n##...##\n##.@.##\n##...##\n#cB#Ab#\n#######"
+            .parse()
+            .unwrap();
+
+        let expected = "#######\n#a.#Cd#\n##@#@##\n#######\n##@#@##\n#cB#Ab#\n#######";
+
+        let result = vault.split_into_quadrants().unwrap();
+
+        assert_eq!(result.grid.render_to_string(), expected);
+        assert_eq!(result.get_entrances().len(), 4);
+        assert_eq!(result.fewest_steps_to_collect_all_keys(), Some(8));
+        assert!(result.split_into_quadrants().is_err());
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(
+            "#@?#".parse::<Vault>(),
+            Err(String::from("Unexpected ? at (2, 0) in the vault"))
+        );
+        assert_eq!("##@ab".parse::<Vault>().unwrap().get_all_keys(), 0b11);
+    }
+}
@@ -1,85 +1,57 @@
-use crate::file_reader::to_string_vector;
+use crate::file_reader::PuzzleInput;
+use crate::intcode_computer::parameter_search::ParameterSearch;
 use crate::intcode_computer::IntcodeComputer;
+use crate::solution::Solution;
 
-pub fn run_day_2() {
-    let file_input = to_string_vector("inputs/day_2.txt");
+const TARGET_OUTPUT: i128 = 19690720;
 
-    match file_input {
-        Ok(programs) => {
-            if let Some(program) = programs.get(0) {
-                find_first_solution(&program);
-                find_second_solution(&program, 19690720, 99, 99);
-            }
-        }
-        Err(error) => println!("Error parsing file: {:?}", error),
-    }
-}
-
-fn find_first_solution(program: &str) {
-    let program_values: Vec<String> = program.split(",").map(|s| String::from(s)).collect();
+pub struct Day2;
 
-    let mut intcode_computer = IntcodeComputer::from(program_values.as_slice());
+impl Solution for Day2 {
+    const DAY: u8 = 2;
 
-    intcode_computer.replace_code_in_program(1, 12);
-    intcode_computer.replace_code_in_program(2, 2);
+    type Input = Vec<String>;
+    type Answer1 = i128;
+    type Answer2 = i32;
 
-    intcode_computer.execute_program();
+    fn parse(&self, input: &PuzzleInput) -> Result<Vec<String>, String> {
+        Ok(input.split_commas()?)
+    }
 
-    let first_value = intcode_computer.get_current_memory()[&0];
+    fn part_1(&self, program_values: &Vec<String>) -> Result<i128, String> {
+        Ok(find_first_solution(program_values))
+    }
 
-    println!("Part 1 Solution is: {:?}", first_value);
+    fn part_2(&self, program_values: &Vec<String>) -> Result<i32, String> {
+        find_second_solution(program_values, TARGET_OUTPUT, 99, 99)?
+            .ok_or_else(|| String::from("Couldn't find a solution!"))
+    }
 }
 
-fn find_second_solution(program: &str, target: i128, largest_noun: i32, largest_verb: i32) {
-    let program_values: Vec<String> = program.split(",").map(|s| String::from(s)).collect();
-
-    let mut intcode_computer = IntcodeComputer::from(program_values.as_slice());
-
-    let mut solution: Result<i32, &str> = Err("Couldn't find a solution!");
-
-    let mut noun = 0;
+fn find_first_solution(program_values: &[String]) -> i128 {
+    let mut intcode_computer = IntcodeComputer::from(program_values);
 
-    while (noun <= largest_noun) && solution.is_err() {
-        let mut verb_upper_bound = largest_verb;
-        let mut verb_lower_bound = 0;
-
-        let mut verb = int_between(verb_lower_bound, verb_upper_bound);
-
-        while verb_lower_bound < verb && verb < verb_upper_bound && solution.is_err() {
-            intcode_computer.replace_code_in_program(1, noun);
-            intcode_computer.replace_code_in_program(2, verb);
-
-            intcode_computer.execute_program();
-
-            let output = intcode_computer.get_current_memory()[&0];
-
-            if output == target {
-                solution = Ok(100 * noun + verb);
-            } else if output > target {
-                verb_upper_bound = verb;
-                verb = int_between(verb_lower_bound, verb_upper_bound);
-            } else {
-                verb_lower_bound = verb;
-                verb = int_between(verb_lower_bound, verb_upper_bound);
-            }
-
-            intcode_computer.reset();
-        }
+    intcode_computer.replace_code_in_program(1, 12);
+    intcode_computer.replace_code_in_program(2, 2);
 
-        noun += 1;
-    }
+    intcode_computer.execute_program();
 
-    match solution {
-        Ok(value) => println!("Part 2 Solution is: {:?}", value),
-        Err(error) => panic!(error),
-    }
+    intcode_computer.get_current_memory()[&0]
 }
 
-fn int_between(lower_bound: i32, upper_bound: i32) -> i32 {
-    let new_lower_bound = lower_bound as f64;
-    let new_upper_bound = upper_bound as f64;
+fn find_second_solution(
+    program_values: &[String],
+    target: i128,
+    largest_noun: i32,
+    largest_verb: i32,
+) -> Result<Option<i32>, String> {
+    let mut parameter_search = ParameterSearch::new(program_values);
+
+    parameter_search.add_patch(1, 0..=largest_noun)?;
+    parameter_search.add_patch(2, 0..=largest_verb)?;
 
-    let between_value = (new_upper_bound + new_lower_bound) / 2.0;
+    let values = parameter_search
+        .find_first(|intcode_computer| intcode_computer.get_current_memory()[&0] == target)?;
 
-    between_value.round() as i32
+    Ok(values.map(|values| 100 * values[0] + values[1]))
 }
@@ -25,10 +25,10 @@ impl Line {
         };
 
         Line {
-            start_range: start_range,
-            end_range: end_range,
-            offset: offset,
-            direction: direction,
+            start_range,
+            end_range,
+            offset,
+            direction,
         }
     }
 
@@ -1,37 +1,40 @@
-mod line;
-mod wire;
+pub mod line;
+pub mod wire;
 
-use crate::file_reader::to_string_vector;
-use wire::Wire;
+use crate::file_reader::PuzzleInput;
+use crate::solution::Solution;
+pub use line::{Direction, Line};
+pub use wire::Wire;
 
-pub fn run_day_3() {
-    let file_input = to_string_vector("inputs/day_3.txt");
+pub struct Day3;
 
-    match file_input {
-        Ok(moves) => {
-            run_part_1(&moves);
-            run_part_2(&moves);
-        }
-        Err(error) => panic!("Error parsing file: {:?}", error),
-    }
-}
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+
+    type Input = (Wire, Wire);
+    type Answer1 = i32;
+    type Answer2 = i32;
 
-fn run_part_1(moves: &[String]) {
-    let first_wire = Wire::new(moves[0].as_str());
-    let second_wire = Wire::new(moves[1].as_str());
+    fn parse(&self, input: &PuzzleInput) -> Result<(Wire, Wire), String> {
+        let moves = input.get_lines();
 
-    match first_wire.intersection_distance(&second_wire) {
-        Some(distance) => println!("Day 3 Part 1 Solution: {:?}", distance),
-        None => println!("Day 3 Part 1 Solution not found!"),
+        match moves.as_slice() {
+            [first_moves, second_moves, ..] => {
+                Ok((Wire::new(*first_moves), Wire::new(*second_moves)))
+            }
+            _ => Err(String::from("Expected the moves for two wires!")),
+        }
     }
-}
 
-fn run_part_2(moves: &[String]) {
-    let first_wire = Wire::new(moves[0].as_str());
-    let second_wire = Wire::new(moves[1].as_str());
+    fn part_1(&self, (first_wire, second_wire): &(Wire, Wire)) -> Result<i32, String> {
+        first_wire
+            .intersection_distance(second_wire)
+            .ok_or_else(|| String::from("The wires don't intersect!"))
+    }
 
-    match first_wire.best_time_intersection_distance(&second_wire) {
-        Some(distance) => println!("Day 3 Part 2 Solution: {:?}", distance),
-        None => println!("Day 3 Part 2 Solution not found!"),
+    fn part_2(&self, (first_wire, second_wire): &(Wire, Wire)) -> Result<i32, String> {
+        first_wire
+            .best_time_intersection_distance(second_wire)
+            .ok_or_else(|| String::from("The wires don't intersect!"))
     }
 }
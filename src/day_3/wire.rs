@@ -19,9 +19,7 @@ impl Wire {
         other
             .lines
             .iter()
-            .map(|(temp_line, _)| self.line_intersection_distance(temp_line))
-            .filter(|distance| distance.is_some())
-            .map(|distance| distance.unwrap())
+            .filter_map(|(temp_line, _)| self.line_intersection_distance(temp_line))
             .min()
     }
 
@@ -29,9 +27,9 @@ impl Wire {
         other
             .lines
             .iter()
-            .map(|(temp_line, temp_time)| self.best_time_line_intersection(temp_line, *temp_time))
-            .filter(|distance| distance.is_some())
-            .map(|distance| distance.unwrap())
+            .filter_map(|(temp_line, temp_time)| {
+                self.best_time_line_intersection(temp_line, *temp_time)
+            })
             .min()
     }
 
@@ -110,8 +108,8 @@ impl From<&[String]> for Wire {
 
             total_distance += distance;
 
-            match direction_str {
-                &"R" => {
+            match *direction_str {
+                "R" => {
                     let next_x_endpoint = current_end_x_y_endpoint.0 + distance;
 
                     let line = Line::new(
@@ -126,7 +124,7 @@ impl From<&[String]> for Wire {
 
                     current_end_x_y_endpoint.0 = next_x_endpoint;
                 }
-                &"L" => {
+                "L" => {
                     let next_x_endpoint = current_end_x_y_endpoint.0 - distance;
 
                     let line = Line::new(
@@ -141,7 +139,7 @@ impl From<&[String]> for Wire {
 
                     current_end_x_y_endpoint.0 = next_x_endpoint;
                 }
-                &"U" => {
+                "U" => {
                     let next_y_endpoint = current_end_x_y_endpoint.1 + distance;
 
                     let line = Line::new(
@@ -156,7 +154,7 @@ impl From<&[String]> for Wire {
 
                     current_end_x_y_endpoint.1 = next_y_endpoint;
                 }
-                &"D" => {
+                "D" => {
                     let next_y_endpoint = current_end_x_y_endpoint.1 - distance;
 
                     let line = Line::new(
@@ -179,16 +177,16 @@ impl From<&[String]> for Wire {
         vertical_lines.sort();
 
         Wire {
-            lines: lines,
-            horizontal_lines: horizontal_lines,
-            vertical_lines: vertical_lines,
+            lines,
+            horizontal_lines,
+            vertical_lines,
         }
     }
 }
 
 impl From<&str> for Wire {
     fn from(a: &str) -> Wire {
-        let moves: Vec<String> = a.split(",").map(|s| String::from(s)).collect();
+        let moves: Vec<String> = a.split(",").map(String::from).collect();
 
         Wire::new(moves.as_slice())
     }
@@ -1,18 +1,64 @@
-mod possible_password_finder;
-mod possible_passwords;
+pub mod possible_password_finder;
+pub mod possible_passwords;
 
-use possible_password_finder::PossiblePasswordFinder;
+use std::ops::RangeInclusive;
 
-pub fn run_day_4() {
-    let possible_password_finder = PossiblePasswordFinder::new(6);
+use crate::file_reader::PuzzleInput;
+use crate::solution::Solution;
 
-    let mut number_of_possible_passwords =
-        possible_password_finder.number_of_double_digit_passwords_between(231832, 767346);
+pub use possible_password_finder::PossiblePasswordFinder;
 
-    println!("Day 4 Part 1 Solution: {:?}", number_of_possible_passwords);
+const PASSWORD_LENGTH: usize = 6;
 
-    number_of_possible_passwords = possible_password_finder
-        .number_of_non_triple_double_digit_passwords_between(231832, 767346);
+pub struct Day4;
 
-    println!("Day 4 Part 2 Solution: {:?}", number_of_possible_passwords);
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+
+    type Input = RangeInclusive<i32>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    // The input is the password range, e.g. 231832-767346
+    fn parse(&self, input: &PuzzleInput) -> Result<RangeInclusive<i32>, String> {
+        let range = input.get_first_line()?;
+
+        let bounds: Vec<Option<i32>> = range.split('-').map(|bound| bound.parse().ok()).collect();
+
+        match bounds.as_slice() {
+            [Some(start), Some(end)] => Ok(*start..=*end),
+            _ => Err(format!("Invalid password range: {}", range)),
+        }
+    }
+
+    fn part_1(&self, range: &RangeInclusive<i32>) -> Result<i32, String> {
+        let possible_password_finder = PossiblePasswordFinder::new(PASSWORD_LENGTH);
+
+        Ok(possible_password_finder
+            .number_of_double_digit_passwords_between(*range.start(), *range.end()))
+    }
+
+    fn part_2(&self, range: &RangeInclusive<i32>) -> Result<i32, String> {
+        let possible_password_finder = PossiblePasswordFinder::new(PASSWORD_LENGTH);
+
+        Ok(possible_password_finder
+            .number_of_non_triple_double_digit_passwords_between(*range.start(), *range.end()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let input = PuzzleInput::from_string("range", "231832-767346\n");
+        let bad_input = PuzzleInput::from_string("range", "231832");
+
+        assert_eq!(Day4.parse(&input), Ok(231832..=767346));
+        assert_eq!(
+            Day4.parse(&bad_input),
+            Err(String::from("Invalid password range: 231832"))
+        );
+    }
 }
@@ -10,7 +10,7 @@ impl PossiblePasswordFinder {
     pub fn new(password_length: usize) -> PossiblePasswordFinder {
         PossiblePasswordFinder {
             map: PossiblePasswordMap::new(password_length),
-            password_length: password_length,
+            password_length,
         }
     }
 
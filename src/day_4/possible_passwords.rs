@@ -20,7 +20,7 @@ impl PossiblePasswords {
         let start_range = starting_digit * power_of_ten;
 
         PossiblePasswords {
-            start_range: start_range,
+            start_range,
             end_range: start_range + power_of_ten,
             extended_end_range: 10_i32.pow(magnitude_of_ten + 1),
             increasing_digits_in_range: 0,
@@ -32,7 +32,7 @@ impl PossiblePasswords {
         }
     }
 
-    #[allow(dead_code)]
+    #[cfg(test)]
     fn new_all(
         start_range: i32,
         end_range: i32,
@@ -45,15 +45,15 @@ impl PossiblePasswords {
         triple_digits_in_extended_range: i32,
     ) -> PossiblePasswords {
         PossiblePasswords {
-            start_range: start_range,
-            end_range: end_range,
-            extended_end_range: extended_end_range,
-            increasing_digits_in_range: increasing_digits_in_range,
-            increasing_digits_in_extended_range: increasing_digits_in_extended_range,
-            double_digits_in_range: double_digits_in_range,
-            double_digits_in_extended_range: double_digits_in_extended_range,
-            triple_digits_in_range: triple_digits_in_range,
-            triple_digits_in_extended_range: triple_digits_in_extended_range,
+            start_range,
+            end_range,
+            extended_end_range,
+            increasing_digits_in_range,
+            increasing_digits_in_extended_range,
+            double_digits_in_range,
+            double_digits_in_extended_range,
+            triple_digits_in_range,
+            triple_digits_in_extended_range,
         }
     }
 }
@@ -104,7 +104,7 @@ impl PossiblePasswordMap {
             self.get_number_of_increasing_digits_for(starting_digit, length);
 
         let increasing_digits_in_extended_range = match starting_digit {
-            0 | 1 | 2 | 3 | 4 | 5 | 6 | 7 | 8 => {
+            0..=8 => {
                 increasing_digits_in_range
                     + self.map[starting_digit + 1][length].increasing_digits_in_extended_range
             }
@@ -114,7 +114,7 @@ impl PossiblePasswordMap {
         let double_digits_in_range = self.get_number_of_double_digits_for(starting_digit, length);
 
         let double_digits_in_extended_range = match starting_digit {
-            0 | 1 | 2 | 3 | 4 | 5 | 6 | 7 | 8 => {
+            0..=8 => {
                 double_digits_in_range
                     + self.map[starting_digit + 1][length].double_digits_in_extended_range
             }
@@ -124,7 +124,7 @@ impl PossiblePasswordMap {
         let triple_digits_in_range = self.get_number_of_triple_digits_for(starting_digit, length);
 
         let triple_digits_in_extended_range = match starting_digit {
-            0 | 1 | 2 | 3 | 4 | 5 | 6 | 7 | 8 => {
+            0..=8 => {
                 triple_digits_in_range
                     + self.map[starting_digit + 1][length].triple_digits_in_extended_range
             }
@@ -149,10 +149,7 @@ impl PossiblePasswordMap {
             return 1;
         }
 
-        let number_of_increasing_digits_in_extend_range_one_magnitude_less =
-            self.map[starting_digit][length - 1].increasing_digits_in_extended_range;
-
-        number_of_increasing_digits_in_extend_range_one_magnitude_less
+        self.map[starting_digit][length - 1].increasing_digits_in_extended_range
     }
 
     fn get_number_of_double_digits_for(&self, starting_digit: usize, length: usize) -> i32 {
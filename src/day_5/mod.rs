@@ -1,28 +1,62 @@
-use crate::file_reader::to_string_vector;
-use crate::get_user_input;
+use crate::file_reader::PuzzleInput;
 use crate::intcode_computer::IntcodeComputer;
+use crate::solution::Solution;
 
-pub fn run_day_5() {
-    let file_input = to_string_vector("inputs/day_5.txt");
+// System IDs for the air conditioner and the thermal radiator controller
+const PART_1_SYSTEM_ID: i128 = 1;
+const PART_2_SYSTEM_ID: i128 = 5;
 
-    match file_input {
-        Ok(programs) => {
-            if let Some(program) = programs.get(0) {
-                let program_values: Vec<String> =
-                    program.split(",").map(|s| String::from(s)).collect();
+#[derive(Debug, Default)]
+pub struct Day5 {
+    // (part, system ID) to use instead of that part's usual system ID
+    system_id: Option<(u8, i128)>,
+}
+
+impl Day5 {
+    fn get_system_id(&self, part: u8, default_system_id: i128) -> i128 {
+        match self.system_id {
+            Some((system_id_part, system_id)) if system_id_part == part => system_id,
+            _ => default_system_id,
+        }
+    }
+}
 
-                print!("Please input a system ID to diagnose: ");
-                let user_input = get_user_input();
+impl Solution for Day5 {
+    const DAY: u8 = 5;
 
-                let mut intcode_computer = IntcodeComputer::from(program_values.as_slice());
+    type Input = Vec<String>;
+    type Answer1 = i128;
+    type Answer2 = i128;
 
-                intcode_computer.set_input(user_input);
+    fn parse(&self, input: &PuzzleInput) -> Result<Vec<String>, String> {
+        Ok(input.split_commas()?)
+    }
 
-                let output = intcode_computer.execute_program();
+    fn part_1(&self, program_values: &Vec<String>) -> Result<i128, String> {
+        run_diagnostic(program_values, self.get_system_id(1, PART_1_SYSTEM_ID))
+    }
 
-                println!("System output is: {}", output.unwrap());
-            }
-        }
-        Err(error) => println!("Error parsing file: {:?}", error),
+    fn part_2(&self, program_values: &Vec<String>) -> Result<i128, String> {
+        run_diagnostic(program_values, self.get_system_id(2, PART_2_SYSTEM_ID))
     }
+
+    fn set_arg(&mut self, part: u8, arg: &str) -> Result<(), String> {
+        let system_id = arg
+            .parse()
+            .map_err(|_| format!("Expected a system ID, got {}", arg))?;
+
+        self.system_id = Some((part, system_id));
+
+        Ok(())
+    }
+}
+
+fn run_diagnostic(program_values: &[String], system_id: i128) -> Result<i128, String> {
+    let mut intcode_computer = IntcodeComputer::from(program_values);
+
+    intcode_computer.set_input(system_id);
+
+    intcode_computer
+        .execute_program()
+        .ok_or_else(|| String::from("The diagnostic program had no output!"))
 }
@@ -1,35 +1,42 @@
 pub mod orbit;
 
-use crate::file_reader::to_string_vector;
-use orbit::OrbitMap;
+use crate::file_reader::PuzzleInput;
+use crate::solution::Solution;
 
-pub fn run_day_6() {
-    let file_input = to_string_vector("inputs/day_6.txt");
+pub use orbit::{OrbitMap, OrbitObject};
 
-    match file_input {
-        Ok(orbit_description) => {
-            let mut orbit_map = OrbitMap::new();
+pub struct Day6;
 
-            orbit_map.add_orbit_description(&orbit_description);
+impl Solution for Day6 {
+    const DAY: u8 = 6;
 
-            run_part_1(&orbit_map);
-            run_part_2(&orbit_map);
-        }
-        Err(error) => panic!("Error parsing file: {:?}", error),
-    }
-}
+    type Input = OrbitMap;
+    type Answer1 = u32;
+    type Answer2 = u32;
 
-fn run_part_1(orbit_map: &OrbitMap) {
-    let total_number_of_direct_and_indirect_orbits = orbit_map.total_number_of_orbits();
+    fn parse(&self, input: &PuzzleInput) -> Result<OrbitMap, String> {
+        let orbit_description = input.parse_lines_with(|line| {
+            if line.contains(')') {
+                Ok(String::from(line))
+            } else {
+                Err(String::from("expected an orbit like COM)B"))
+            }
+        })?;
 
-    println!(
-        "Day 6 Part 1 Solution: {}",
-        total_number_of_direct_and_indirect_orbits
-    );
-}
+        let mut orbit_map = OrbitMap::new();
+
+        orbit_map.add_orbit_description(&orbit_description);
 
-fn run_part_2(orbit_map: &OrbitMap) {
-    let orbit_distance_between = orbit_map.number_of_objects_between("YOU", "SAN").unwrap();
+        Ok(orbit_map)
+    }
+
+    fn part_1(&self, orbit_map: &OrbitMap) -> Result<u32, String> {
+        Ok(orbit_map.total_number_of_orbits())
+    }
 
-    println!("Day 6 Part 2 Solution: {}", orbit_distance_between);
+    fn part_2(&self, orbit_map: &OrbitMap) -> Result<u32, String> {
+        orbit_map
+            .number_of_objects_between("YOU", "SAN")
+            .ok_or_else(|| String::from("YOU and SAN aren't orbiting anything in common!"))
+    }
 }
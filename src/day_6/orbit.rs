@@ -1,4 +1,5 @@
 use std::collections::{HashMap, HashSet};
+#[cfg(test)]
 use std::panic;
 
 #[derive(Debug, PartialEq, Clone)]
@@ -19,8 +20,7 @@ impl OrbitObject {
             .map(|&s| String::from(s))
             .collect();
 
-        let temp_object_name_this_is_orbiting =
-            object_name_this_is_orbiting.map(|s| String::from(s));
+        let temp_object_name_this_is_orbiting = object_name_this_is_orbiting.map(String::from);
 
         OrbitObject {
             name: String::from(name),
@@ -44,7 +44,7 @@ impl OrbitObject {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct OrbitMap {
     object_orbiting_map: HashMap<String, OrbitObject>,
 }
@@ -59,7 +59,7 @@ impl OrbitMap {
     pub fn add_orbit_description(&mut self, orbit_description: &[String]) {
         orbit_description
             .iter()
-            .for_each(|s| self.add_orbit_description_line(&s));
+            .for_each(|s| self.add_orbit_description_line(s));
     }
 
     pub fn total_number_of_orbits(&self) -> u32 {
@@ -95,7 +95,7 @@ impl OrbitMap {
     fn add_orbit_description_line(&mut self, orbit_description_line: &str) {
         let object_names: Vec<String> = orbit_description_line
             .split(')')
-            .map(|s| String::from(s))
+            .map(String::from)
             .collect();
 
         assert!(object_names.len() == 2);
@@ -168,7 +168,7 @@ impl OrbitMap {
         }
 
         while let Some(next_object_name) = optional_next_object_name.clone() {
-            result.insert(String::from(next_object_name.clone()), distance_to);
+            result.insert(next_object_name.clone(), distance_to);
 
             if let Some(object) = self.object_orbiting_map.get(&next_object_name) {
                 optional_next_object_name = object.object_name_this_is_orbiting.clone();
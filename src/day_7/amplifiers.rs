@@ -19,8 +19,8 @@ impl Amplifier {
     ) -> Amplifier {
         Amplifier {
             name: String::from(name),
-            phase_setting: phase_setting,
-            input_signal: input_signal,
+            phase_setting,
+            input_signal,
             intcode_computer: intcode_computer.clone(),
         }
     }
@@ -86,7 +86,7 @@ impl AmplifierCircuit {
         let number_of_amplifiers = self.amplifiers.len();
 
         let mut best_phase_settings = Vec::new();
-        let mut best_output_signal = i128::min_value();
+        let mut best_output_signal = i128::MIN;
 
         let variations = Self::get_all_phase_signal_variations(phase_settings);
 
@@ -98,7 +98,7 @@ impl AmplifierCircuit {
             loop {
                 let phase_setting = phase_settings[amplifier_index];
 
-                let mut amplifier = self.amplifiers.get_mut(amplifier_index).unwrap();
+                let amplifier = self.amplifiers.get_mut(amplifier_index).unwrap();
 
                 amplifier.phase_setting = phase_setting;
                 amplifier.input_signal = next_input_signal;
@@ -138,7 +138,7 @@ impl AmplifierCircuit {
     fn get_all_phase_signal_variations(phase_settings: &[i32]) -> Vec<Vec<i32>> {
         let mut variations = Vec::new();
         let mut used = Vec::new();
-        let mut unused = phase_settings.iter().map(|x| *x).collect::<VecDeque<i32>>();
+        let mut unused = phase_settings.iter().copied().collect::<VecDeque<i32>>();
 
         permutate(&mut used, &mut unused, &mut variations);
 
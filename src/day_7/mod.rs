@@ -1,47 +1,38 @@
-mod amplifiers;
-
-use crate::file_reader::to_string_vector;
-use amplifiers::AmplifierCircuit;
-
-pub fn run_day_7() {
-    let file_input = to_string_vector("inputs/day_7.txt");
-
-    match file_input {
-        Ok(programs) => {
-            if let Some(program) = programs.get(0) {
-                let amplifier_names = ["A", "B", "C", "D", "E"];
-                let program_values: Vec<i32> = program
-                    .split(",")
-                    .map(|s| s.parse::<i32>().unwrap())
-                    .collect();
-
-                let mut amplifier_circuit =
-                    AmplifierCircuit::new(&amplifier_names, &program_values);
-
-                run_part_1(&mut amplifier_circuit);
-                run_part_2(&mut amplifier_circuit);
-            }
-        }
-        Err(error) => println!("Error parsing file: {:?}", error),
-    }
-}
+pub mod amplifiers;
 
-fn run_part_1(amplifier_circuit: &mut AmplifierCircuit) {
-    let phase_settings = [0, 1, 2, 3, 4];
+use crate::file_reader::PuzzleInput;
+use crate::solution::Solution;
 
-    let result = amplifier_circuit
-        .get_largest_output_signal(&phase_settings)
-        .unwrap();
+pub use amplifiers::{Amplifier, AmplifierCircuit};
 
-    println!("Day 7 Part 1: {:?}", result);
-}
+const AMPLIFIER_NAMES: [&str; 5] = ["A", "B", "C", "D", "E"];
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+
+    type Input = Vec<i32>;
+    type Answer1 = i128;
+    type Answer2 = i128;
 
-fn run_part_2(amplifier_circuit: &mut AmplifierCircuit) {
-    let phase_settings = [5, 6, 7, 8, 9];
+    fn parse(&self, input: &PuzzleInput) -> Result<Vec<i32>, String> {
+        Ok(input.parse_comma_separated()?)
+    }
+
+    fn part_1(&self, program_values: &Vec<i32>) -> Result<i128, String> {
+        largest_output_signal(program_values, &[0, 1, 2, 3, 4])
+    }
+
+    fn part_2(&self, program_values: &Vec<i32>) -> Result<i128, String> {
+        largest_output_signal(program_values, &[5, 6, 7, 8, 9])
+    }
+}
 
-    let result = amplifier_circuit
-        .get_largest_output_signal(&phase_settings)
-        .unwrap();
+fn largest_output_signal(program_values: &[i32], phase_settings: &[i32]) -> Result<i128, String> {
+    let mut amplifier_circuit = AmplifierCircuit::new(&AMPLIFIER_NAMES, program_values);
 
-    println!("Day 7 Part 2: {:?}", result);
+    amplifier_circuit
+        .get_largest_output_signal(phase_settings)
+        .map(|(_, output_signal)| output_signal)
 }
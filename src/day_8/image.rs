@@ -10,8 +10,8 @@ pub struct Image {
 impl Image {
     pub fn new(width: usize, height: usize, image_data: &[u32]) -> Image {
         Image {
-            width: width,
-            height: height,
+            width,
+            height,
             layers: image_data
                 .chunks(width * height)
                 .map(|layer_data| Layer::new(width, layer_data))
@@ -32,6 +32,10 @@ impl Image {
         self.get_flattened_layer().render();
     }
 
+    pub fn render_to_string(&self) -> String {
+        self.get_flattened_layer().render_to_string()
+    }
+
     fn get_flattened_layer(&self) -> Layer {
         let mut flattened_layer = self.layers.first().unwrap().clone();
 
@@ -39,19 +39,19 @@ impl Layer {
     }
 
     pub fn render(&self) {
-        for row in self.pixels.iter() {
-            let mut rendered_row = String::new();
-
-            for pixel_value in row.iter() {
-                if *pixel_value == 1 {
-                    rendered_row.push('#');
-                } else {
-                    rendered_row.push(' ');
-                }
-            }
+        println!("{}", self.render_to_string());
+    }
 
-            println!("{}", rendered_row);
-        }
+    pub fn render_to_string(&self) -> String {
+        self.pixels
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|&pixel_value| if pixel_value == 1 { '#' } else { ' ' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
     }
 }
 
@@ -99,6 +99,17 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_render_to_string() {
+        run_tests(|layer| {
+            let expected = String::from("   \n## ");
+
+            let result = layer.render_to_string();
+
+            assert_eq!(result, expected);
+        });
+    }
+
     fn run_tests<T>(test: T)
     where
         T: FnOnce(Layer) -> () + panic::UnwindSafe,
@@ -1,46 +1,46 @@
-mod image;
-mod layer;
-
-use crate::file_reader::to_string_vector;
-use image::Image;
-
-pub fn run_day_8() {
-    let file_input = to_string_vector("inputs/day_8.txt");
-
-    match file_input {
-        Ok(images_data) => {
-            if let Some(image_text_data) = images_data.get(0) {
-                let width = 25;
-                let height = 6;
-                let image_data: Vec<u32> = image_text_data
-                    .chars()
-                    .map(|c| c.to_digit(10).unwrap())
-                    .collect();
-
-                let image = Image::new(width, height, &image_data);
-
-                run_part_1(&image);
-                run_part_2(&image);
-            }
-        }
-        Err(error) => println!("Error parsing file: {:?}", error),
-    }
-}
+pub mod image;
+pub mod layer;
 
-fn run_part_1(image: &Image) {
-    let layer_with_least_zeroes = image.layer_with_least_amount_of_pixel_value(0).unwrap();
+use crate::file_reader::PuzzleInput;
+use crate::solution::Solution;
 
-    let number_of_ones_in_layer = layer_with_least_zeroes.amount_of_pixels_with_value(1);
-    let number_of_twos_in_layer = layer_with_least_zeroes.amount_of_pixels_with_value(2);
+pub use image::Image;
+pub use layer::Layer;
 
-    println!(
-        "Day 8 Part 1 Solution: {}",
-        number_of_twos_in_layer * number_of_ones_in_layer
-    );
-}
+const IMAGE_WIDTH: usize = 25;
+const IMAGE_HEIGHT: usize = 6;
+
+pub struct Day8;
 
-fn run_part_2(image: &Image) {
-    println!("Day 8 Part 2 Solution: ");
+impl Solution for Day8 {
+    const DAY: u8 = 8;
 
-    image.render();
+    type Input = Image;
+    type Answer1 = u32;
+    type Answer2 = String;
+
+    fn parse(&self, input: &PuzzleInput) -> Result<Image, String> {
+        let image_data: Option<Vec<u32>> = input
+            .get_first_line()?
+            .chars()
+            .map(|c| c.to_digit(10))
+            .collect();
+
+        image_data
+            .map(|image_data| Image::new(IMAGE_WIDTH, IMAGE_HEIGHT, &image_data))
+            .ok_or_else(|| String::from("The image data should be a line of digits!"))
+    }
+
+    fn part_1(&self, image: &Image) -> Result<u32, String> {
+        let layer_with_least_zeroes = image.layer_with_least_amount_of_pixel_value(0).unwrap();
+
+        let number_of_ones_in_layer = layer_with_least_zeroes.amount_of_pixels_with_value(1);
+        let number_of_twos_in_layer = layer_with_least_zeroes.amount_of_pixels_with_value(2);
+
+        Ok(number_of_twos_in_layer * number_of_ones_in_layer)
+    }
+
+    fn part_2(&self, image: &Image) -> Result<String, String> {
+        Ok(image.render_to_string())
+    }
 }
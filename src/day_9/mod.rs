@@ -1,28 +1,67 @@
-use crate::file_reader::to_string_vector;
-
-use crate::get_user_input;
+use crate::file_reader::PuzzleInput;
 use crate::intcode_computer::IntcodeComputer;
+use crate::solution::Solution;
 
-pub fn run_day_9() {
-    let file_input = to_string_vector("inputs/day_9.txt");
+// BOOST inputs for test mode and sensor boost mode
+const PART_1_MODE: i128 = 1;
+const PART_2_MODE: i128 = 2;
 
-    match file_input {
-        Ok(programs) => {
-            if let Some(program) = programs.get(0) {
-                let program_values: Vec<String> =
-                    program.split(",").map(|s| String::from(s)).collect();
+#[derive(Debug, Default)]
+pub struct Day9 {
+    // (part, BOOST mode) to use instead of that part's usual mode
+    mode: Option<(u8, i128)>,
+}
 
-                print!("Please input for the BOOST program: ");
-                let user_input = get_user_input();
+impl Day9 {
+    fn get_mode(&self, part: u8, default_mode: i128) -> i128 {
+        match self.mode {
+            Some((mode_part, mode)) if mode_part == part => mode,
+            _ => default_mode,
+        }
+    }
+}
 
-                let mut intcode_computer = IntcodeComputer::new(program_values.as_slice());
+impl Solution for Day9 {
+    const DAY: u8 = 9;
 
-                intcode_computer.set_input(user_input);
-                intcode_computer.execute_program();
+    type Input = Vec<String>;
+    type Answer1 = String;
+    type Answer2 = String;
 
-                println!("System outputs are: {:?}", intcode_computer.get_outputs());
-            }
-        }
-        Err(error) => println!("Error parsing file: {:?}", error),
+    fn parse(&self, input: &PuzzleInput) -> Result<Vec<String>, String> {
+        Ok(input.split_commas()?)
+    }
+
+    fn part_1(&self, program_values: &Vec<String>) -> Result<String, String> {
+        Ok(run_boost(program_values, self.get_mode(1, PART_1_MODE)))
+    }
+
+    fn part_2(&self, program_values: &Vec<String>) -> Result<String, String> {
+        Ok(run_boost(program_values, self.get_mode(2, PART_2_MODE)))
+    }
+
+    fn set_arg(&mut self, part: u8, arg: &str) -> Result<(), String> {
+        let mode = arg
+            .parse()
+            .map_err(|_| format!("Expected a BOOST mode, got {}", arg))?;
+
+        self.mode = Some((part, mode));
+
+        Ok(())
     }
 }
+
+// The BOOST keycode on success, otherwise every opcode that failed
+fn run_boost(program_values: &[String], mode: i128) -> String {
+    let mut intcode_computer = IntcodeComputer::new(program_values);
+
+    intcode_computer.set_input(mode);
+    intcode_computer.execute_program();
+
+    intcode_computer
+        .get_outputs()
+        .iter()
+        .map(|output| output.to_string())
+        .collect::<Vec<String>>()
+        .join(",")
+}
@@ -1,13 +1,229 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::str::FromStr;
 
-pub fn to_string_vector(file_name: &str) -> Result<Vec<String>, String> {
-    let file = BufReader::new(File::open(file_name).expect("File not found!"));
+pub const STDIN_NAME: &str = "<stdin>";
 
-    Ok(file
-        .lines()
-        .map(|line| line.expect("The file is bad!"))
-        .collect())
+#[derive(Debug)]
+pub enum InputError {
+    MissingFile {
+        path: String,
+    },
+    Io {
+        path: String,
+        error: io::Error,
+    },
+    // The input isn't valid UTF-8
+    Encoding {
+        path: String,
+        line: usize,
+    },
+    Empty {
+        path: String,
+    },
+    Parse {
+        path: String,
+        line: usize,
+        value: String,
+        reason: String,
+    },
+}
+
+impl InputError {
+    pub fn get_path(&self) -> &str {
+        match self {
+            InputError::MissingFile { path }
+            | InputError::Io { path, .. }
+            | InputError::Encoding { path, .. }
+            | InputError::Empty { path }
+            | InputError::Parse { path, .. } => path,
+        }
+    }
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputError::MissingFile { path } => write!(f, "{}: file not found", path),
+            InputError::Io { path, error } => write!(f, "{}: {}", path, error),
+            InputError::Encoding { path, line } => {
+                write!(f, "{}:{}: input is not valid UTF-8", path, line)
+            }
+            InputError::Empty { path } => write!(f, "{}: input is empty", path),
+            InputError::Parse {
+                path,
+                line,
+                value,
+                reason,
+            } => write!(f, "{}:{}: can't parse {:?}: {}", path, line, value, reason),
+        }
+    }
+}
+
+impl Error for InputError {}
+
+impl From<InputError> for String {
+    fn from(error: InputError) -> String {
+        error.to_string()
+    }
+}
+
+// Puzzle input read from a file, stdin or a string, along with where it came
+// from so errors can point at it.
+#[derive(Debug, PartialEq, Clone)]
+pub struct PuzzleInput {
+    path: String,
+    text: String,
+}
+
+impl PuzzleInput {
+    pub fn from_file(path: &str) -> Result<PuzzleInput, InputError> {
+        let bytes = fs::read(path).map_err(|error| match error.kind() {
+            io::ErrorKind::NotFound => InputError::MissingFile {
+                path: String::from(path),
+            },
+            _ => InputError::Io {
+                path: String::from(path),
+                error,
+            },
+        })?;
+
+        PuzzleInput::from_bytes(path, bytes)
+    }
+
+    pub fn from_stdin() -> Result<PuzzleInput, InputError> {
+        let mut bytes = Vec::new();
+
+        io::stdin()
+            .read_to_end(&mut bytes)
+            .map_err(|error| InputError::Io {
+                path: String::from(STDIN_NAME),
+                error,
+            })?;
+
+        PuzzleInput::from_bytes(STDIN_NAME, bytes)
+    }
+
+    // "-" reads from stdin
+    pub fn from_path_or_stdin(path: &str) -> Result<PuzzleInput, InputError> {
+        match path {
+            "-" => PuzzleInput::from_stdin(),
+            _ => PuzzleInput::from_file(path),
+        }
+    }
+
+    pub fn from_string(path: &str, text: &str) -> PuzzleInput {
+        PuzzleInput {
+            path: String::from(path),
+            text: String::from(text),
+        }
+    }
+
+    fn from_bytes(path: &str, bytes: Vec<u8>) -> Result<PuzzleInput, InputError> {
+        String::from_utf8(bytes)
+            .map(|text| PuzzleInput::from_string(path, &text))
+            .map_err(|error| {
+                let valid_bytes = &error.as_bytes()[..error.utf8_error().valid_up_to()];
+
+                InputError::Encoding {
+                    path: String::from(path),
+                    line: valid_bytes.iter().filter(|&&byte| byte == b'\n').count() + 1,
+                }
+            })
+    }
+
+    pub fn get_path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn get_text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn get_lines(&self) -> Vec<&str> {
+        self.text.lines().collect()
+    }
+
+    pub fn get_first_line(&self) -> Result<&str, InputError> {
+        match self.text.lines().next().map(str::trim) {
+            Some(line) if !line.is_empty() => Ok(line),
+            _ => Err(InputError::Empty {
+                path: self.path.clone(),
+            }),
+        }
+    }
+
+    // The first line split on commas, e.g. an Intcode program
+    pub fn split_commas(&self) -> Result<Vec<String>, InputError> {
+        Ok(self
+            .get_first_line()?
+            .split(',')
+            .map(|value| String::from(value.trim()))
+            .collect())
+    }
+
+    pub fn parse_comma_separated<T>(&self) -> Result<Vec<T>, InputError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        self.split_commas()?
+            .iter()
+            .map(|value| {
+                value.parse().map_err(|error: T::Err| InputError::Parse {
+                    path: self.path.clone(),
+                    line: 1,
+                    value: value.clone(),
+                    reason: error.to_string(),
+                })
+            })
+            .collect()
+    }
+
+    pub fn to_char_grid(&self) -> Vec<Vec<char>> {
+        self.text
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(|line| line.chars().collect())
+            .collect()
+    }
+
+    // Blank lines are skipped.
+    pub fn parse_lines<T>(&self) -> Result<Vec<T>, InputError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        self.parse_lines_with(|line| line.parse().map_err(|error: T::Err| error.to_string()))
+    }
+
+    // Blank lines are skipped.
+    pub fn parse_lines_with<T, F>(&self, parse_line: F) -> Result<Vec<T>, InputError>
+    where
+        F: Fn(&str) -> Result<T, String>,
+    {
+        self.text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                parse_line(line.trim()).map_err(|reason| InputError::Parse {
+                    path: self.path.clone(),
+                    line: index + 1,
+                    value: String::from(line.trim()),
+                    reason,
+                })
+            })
+            .collect()
+    }
+}
+
+pub fn to_string_vector(file_name: &str) -> Result<Vec<String>, InputError> {
+    let input = PuzzleInput::from_file(file_name)?;
+
+    Ok(input.get_lines().into_iter().map(String::from).collect())
 }
 
 #[cfg(test)]
@@ -27,4 +243,72 @@ mod tests {
 
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_missing_file() {
+        let result = PuzzleInput::from_file("test_inputs/missing.txt").unwrap_err();
+
+        assert_eq!(result.get_path(), "test_inputs/missing.txt");
+        assert_eq!(
+            result.to_string(),
+            "test_inputs/missing.txt: file not found"
+        );
+    }
+
+    #[test]
+    fn test_encoding_error() {
+        let result = PuzzleInput::from_bytes("bytes", vec![b'1', b'\n', b'2', 0xff]).unwrap_err();
+
+        assert_eq!(result.to_string(), "bytes:2: input is not valid UTF-8");
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let input = PuzzleInput::from_string("masses", "12\n\n14\nabc\n");
+
+        let expected = "masses:4: can't parse \"abc\": invalid digit found in string";
+
+        assert_eq!(
+            PuzzleInput::from_string("masses", "12\n\n14\n")
+                .parse_lines::<i32>()
+                .unwrap(),
+            vec![12, 14]
+        );
+        assert_eq!(
+            input.parse_lines::<i32>().unwrap_err().to_string(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_split_commas() {
+        let input = PuzzleInput::from_string("program", "1,0, 0,3,99\n");
+
+        let expected: Vec<String> = ["1", "0", "0", "3", "99"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        assert_eq!(input.split_commas().unwrap(), expected);
+        assert_eq!(
+            input.parse_comma_separated::<i128>().unwrap(),
+            vec![1, 0, 0, 3, 99]
+        );
+        assert_eq!(
+            PuzzleInput::from_string("program", "\n")
+                .split_commas()
+                .unwrap_err()
+                .to_string(),
+            "program: input is empty"
+        );
+    }
+
+    #[test]
+    fn test_to_char_grid() {
+        let input = PuzzleInput::from_string("map", ".#\n#.\n");
+
+        let expected = vec![vec!['.', '#'], vec!['#', '.']];
+
+        assert_eq!(input.to_char_grid(), expected);
+    }
 }
@@ -0,0 +1,122 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+// Breadth first search, returning how many steps it takes to get to every
+// reachable node from `start`.
+pub fn bfs<T, F, I>(start: T, mut get_neighbours: F) -> HashMap<T, usize>
+where
+    T: Hash + Eq + Clone,
+    F: FnMut(&T) -> I,
+    I: IntoIterator<Item = T>,
+{
+    let mut distances = HashMap::new();
+    let mut queue = VecDeque::new();
+
+    distances.insert(start.clone(), 0);
+    queue.push_back(start);
+
+    while let Some(node) = queue.pop_front() {
+        let distance = distances[&node];
+
+        for neighbour in get_neighbours(&node) {
+            if !distances.contains_key(&neighbour) {
+                distances.insert(neighbour.clone(), distance + 1);
+                queue.push_back(neighbour);
+            }
+        }
+    }
+
+    distances
+}
+
+// Dijkstra's algorithm, returning the cheapest goal node and what it cost to
+// get there. `get_successors` gives each next node with the cost to move to it.
+pub fn dijkstra<T, F, I, G>(start: T, mut get_successors: F, is_goal: G) -> Option<(usize, T)>
+where
+    T: Hash + Eq + Clone,
+    F: FnMut(&T) -> I,
+    I: IntoIterator<Item = (T, usize)>,
+    G: Fn(&T) -> bool,
+{
+    // The heap holds indexes into `nodes`, so nodes don't need to be Ord
+    let mut nodes = vec![start.clone()];
+    let mut costs = HashMap::new();
+    let mut heap = BinaryHeap::new();
+
+    costs.insert(start, 0);
+    heap.push(Reverse((0, 0)));
+
+    while let Some(Reverse((cost, index))) = heap.pop() {
+        let node = nodes[index].clone();
+
+        if costs.get(&node).is_some_and(|&best_cost| cost > best_cost) {
+            continue;
+        }
+
+        if is_goal(&node) {
+            return Some((cost, node));
+        }
+
+        for (successor, step_cost) in get_successors(&node) {
+            let successor_cost = cost + step_cost;
+
+            if costs
+                .get(&successor)
+                .is_none_or(|&best_cost| successor_cost < best_cost)
+            {
+                costs.insert(successor.clone(), successor_cost);
+                nodes.push(successor);
+                heap.push(Reverse((successor_cost, nodes.len() - 1)));
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bfs() {
+        let result = bfs(1, |&node| {
+            if node < 20 {
+                vec![node * 2, node + 1]
+            } else {
+                vec![]
+            }
+        });
+
+        assert_eq!(result[&1], 0);
+        assert_eq!(result[&16], 4);
+        assert_eq!(result[&15], 6);
+        assert_eq!(result.get(&40), None);
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        // a -> b is cheap but b -> d is expensive
+        let edges: HashMap<char, Vec<(char, usize)>> = [
+            ('a', vec![('b', 1), ('c', 4)]),
+            ('b', vec![('d', 10)]),
+            ('c', vec![('d', 2)]),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+
+        let get_successors = |node: &char| edges.get(node).cloned().unwrap_or_default();
+
+        assert_eq!(
+            dijkstra('a', get_successors, |&node| node == 'd'),
+            Some((6, 'd'))
+        );
+        assert_eq!(dijkstra('a', get_successors, |&node| node == 'e'), None);
+        assert_eq!(
+            dijkstra('a', get_successors, |&node| node == 'a'),
+            Some((0, 'a'))
+        );
+    }
+}
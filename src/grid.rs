@@ -0,0 +1,171 @@
+use crate::location::point_2d::Point2d;
+
+// Up, right, down and left, with y growing down the screen
+pub const NEIGHBOUR_OFFSETS: [(i32, i32); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+pub fn get_orthogonal_neighbours(location: &Point2d<i32>) -> Vec<Point2d<i32>> {
+    NEIGHBOUR_OFFSETS
+        .iter()
+        .map(|&(x_offset, y_offset)| Point2d::new(location.x + x_offset, location.y + y_offset))
+        .collect()
+}
+
+// A rectangular grid with (0, 0) in the top left.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Grid<T>, String> {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+
+        if let Some(index) = rows.iter().position(|row| row.len() != width) {
+            return Err(format!(
+                "Row {} has {} cells, expected {}",
+                index + 1,
+                rows[index].len(),
+                width
+            ));
+        }
+
+        Ok(Grid {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+
+    pub fn get_height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, location: &Point2d<i32>) -> bool {
+        self.get_index(location).is_some()
+    }
+
+    pub fn get(&self, location: &Point2d<i32>) -> Option<&T> {
+        self.get_index(location).map(|index| &self.cells[index])
+    }
+
+    // Returns the old value, or None if the location is off the grid.
+    pub fn set(&mut self, location: &Point2d<i32>, value: T) -> Option<T> {
+        let index = self.get_index(location)?;
+
+        Some(std::mem::replace(&mut self.cells[index], value))
+    }
+
+    // Neighbours that are on the grid
+    pub fn get_neighbours(&self, location: &Point2d<i32>) -> Vec<Point2d<i32>> {
+        get_orthogonal_neighbours(location)
+            .into_iter()
+            .filter(|neighbour| self.contains(neighbour))
+            .collect()
+    }
+
+    // Row by row, from the top left
+    pub fn iter(&self) -> impl Iterator<Item = (Point2d<i32>, &T)> + '_ {
+        self.cells.iter().enumerate().map(move |(index, value)| {
+            let location = Point2d::new((index % self.width) as i32, (index / self.width) as i32);
+
+            (location, value)
+        })
+    }
+
+    pub fn find<F>(&self, predicate: F) -> Option<Point2d<i32>>
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.iter()
+            .find(|(_, value)| predicate(value))
+            .map(|(location, _)| location)
+    }
+
+    fn get_index(&self, location: &Point2d<i32>) -> Option<usize> {
+        let (x, y) = (location.x, location.y);
+
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            return None;
+        }
+
+        Some(y as usize * self.width + x as usize)
+    }
+}
+
+impl Grid<char> {
+    // Blank lines are skipped.
+    pub fn from_text(text: &str) -> Result<Grid<char>, String> {
+        Grid::from_rows(
+            text.lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| line.chars().collect())
+                .collect(),
+        )
+    }
+
+    pub fn render_to_string(&self) -> String {
+        self.cells
+            .chunks(self.width.max(1))
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_text() {
+        let grid = Grid::from_text("#.#\n.@.\n").unwrap();
+
+        assert_eq!(grid.get_width(), 3);
+        assert_eq!(grid.get_height(), 2);
+        assert_eq!(grid.get(&Point2d::new(1, 1)), Some(&'@'));
+        assert_eq!(grid.get(&Point2d::new(3, 0)), None);
+        assert_eq!(grid.find(|&c| c == '@'), Some(Point2d::new(1, 1)));
+        assert_eq!(grid.render_to_string(), "#.#\n.@.");
+        assert_eq!(
+            Grid::from_text("##\n#"),
+            Err(String::from("Row 2 has 1 cells, expected 2"))
+        );
+    }
+
+    #[test]
+    fn test_set() {
+        let mut grid = Grid::new(2, 2, '.');
+
+        assert_eq!(grid.set(&Point2d::new(1, 0), '#'), Some('.'));
+        assert_eq!(grid.set(&Point2d::new(-1, 0), '#'), None);
+        assert_eq!(grid.render_to_string(), ".#\n..");
+    }
+
+    #[test]
+    fn test_get_neighbours() {
+        let grid = Grid::new(3, 3, 0);
+
+        let expected = vec![Point2d::new(1, 0), Point2d::new(0, 1)];
+
+        assert_eq!(grid.get_neighbours(&Point2d::new(0, 0)), expected);
+        assert_eq!(grid.get_neighbours(&Point2d::new(1, 1)).len(), 4);
+    }
+}
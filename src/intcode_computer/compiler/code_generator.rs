@@ -0,0 +1,346 @@
+use std::collections::HashMap;
+
+use super::super::linker::module::{ModuleBuilder, ObjectModule, Operand};
+use super::syntax::{
+    BinaryOperator, Expression, Function, Program, Statement, UnaryOperator, BUILT_IN_FUNCTIONS,
+};
+
+// Every function becomes an exported label following the linker's calling
+// convention. Variables and temporaries live in frame slots after the
+// parameters, handed out like a stack so the frame of a call can always start
+// at the first free slot.
+pub fn generate(program: &Program) -> Result<ObjectModule, String> {
+    let mut arities: HashMap<&str, usize> = BUILT_IN_FUNCTIONS.iter().copied().collect();
+
+    for function in &program.functions {
+        if arities
+            .insert(&function.name, function.parameters.len())
+            .is_some()
+        {
+            return Err(format!(
+                "Function {} is defined more than once",
+                function.name
+            ));
+        }
+    }
+
+    match arities.get("main") {
+        Some(0) => {}
+        Some(_) => return Err(String::from("main can't take arguments")),
+        None => return Err(String::from("No main function")),
+    }
+
+    let mut builder = ModuleBuilder::new("program");
+
+    for function in &program.functions {
+        FunctionGenerator::new(&mut builder, &arities, function).generate(function)?;
+    }
+
+    builder.build()
+}
+
+struct FunctionGenerator<'a> {
+    builder: &'a mut ModuleBuilder,
+    arities: &'a HashMap<&'a str, usize>,
+    scopes: Vec<HashMap<String, i128>>,
+    next_slot: i128,
+}
+
+impl<'a> FunctionGenerator<'a> {
+    fn new(
+        builder: &'a mut ModuleBuilder,
+        arities: &'a HashMap<&'a str, usize>,
+        function: &Function,
+    ) -> FunctionGenerator<'a> {
+        // slot 0 holds the return address
+        let parameters = function
+            .parameters
+            .iter()
+            .enumerate()
+            .map(|(index, name)| (name.clone(), index as i128 + 1))
+            .collect();
+
+        FunctionGenerator {
+            builder,
+            arities,
+            scopes: vec![parameters],
+            next_slot: function.parameters.len() as i128 + 1,
+        }
+    }
+
+    fn generate(&mut self, function: &Function) -> Result<(), String> {
+        self.builder.label(&function.name);
+        self.builder.export(&function.name);
+
+        self.block(&function.body)?;
+
+        // falling off the end returns 0
+        self.builder
+            .copy(Operand::immediate(0), Operand::relative(1));
+        self.builder.ret();
+
+        Ok(())
+    }
+
+    fn block(&mut self, statements: &[Statement]) -> Result<(), String> {
+        let first_free_slot = self.next_slot;
+
+        self.scopes.push(HashMap::new());
+
+        for statement in statements {
+            self.statement(statement)?;
+        }
+
+        self.scopes.pop();
+        self.next_slot = first_free_slot;
+
+        Ok(())
+    }
+
+    fn statement(&mut self, statement: &Statement) -> Result<(), String> {
+        let first_free_slot = self.next_slot;
+
+        match statement {
+            Statement::Let(name, value) => {
+                let value = self.operand(value)?;
+
+                // the new variable takes the first slot once the value is known
+                self.next_slot = first_free_slot;
+
+                let slot = self.allocate_slot();
+
+                self.builder.copy(value, Operand::relative(slot));
+                self.scopes.last_mut().unwrap().insert(name.clone(), slot);
+
+                return Ok(());
+            }
+            Statement::Assign(name, value) => {
+                let slot = self.variable_slot(name)?;
+                let value = self.operand(value)?;
+
+                self.builder.copy(value, Operand::relative(slot));
+            }
+            Statement::If(condition, then_block, else_block) => {
+                let else_label = self.builder.generate_label("else");
+                let end_label = self.builder.generate_label("end_if");
+
+                let condition = self.operand(condition)?;
+
+                self.next_slot = first_free_slot;
+
+                self.builder
+                    .jump_if_false(condition, Operand::label_address(&else_label));
+                self.block(then_block)?;
+                self.builder.jump(Operand::label_address(&end_label));
+                self.builder.label(&else_label);
+                self.block(else_block)?;
+                self.builder.label(&end_label);
+            }
+            Statement::While(condition, body) => {
+                let start_label = self.builder.generate_label("while");
+                let end_label = self.builder.generate_label("end_while");
+
+                self.builder.label(&start_label);
+
+                let condition = self.operand(condition)?;
+
+                self.next_slot = first_free_slot;
+
+                self.builder
+                    .jump_if_false(condition, Operand::label_address(&end_label));
+                self.block(body)?;
+                self.builder.jump(Operand::label_address(&start_label));
+                self.builder.label(&end_label);
+            }
+            Statement::Return(value) => {
+                let value = match value {
+                    Some(value) => self.operand(value)?,
+                    None => Operand::immediate(0),
+                };
+
+                self.builder.copy(value, Operand::relative(1));
+                self.builder.ret();
+            }
+            Statement::Expression(expression) => {
+                self.operand(expression)?;
+            }
+        }
+
+        self.next_slot = first_free_slot;
+
+        Ok(())
+    }
+
+    // Numbers and variables are used in place, anything else is evaluated
+    // into a new temporary slot.
+    fn operand(&mut self, expression: &Expression) -> Result<Operand, String> {
+        match expression {
+            Expression::Number(number) => Ok(Operand::immediate(*number)),
+            Expression::Variable(name) => Ok(Operand::relative(self.variable_slot(name)?)),
+            _ => {
+                let slot = self.allocate_slot();
+
+                self.expression(expression, slot)?;
+
+                Ok(Operand::relative(slot))
+            }
+        }
+    }
+
+    // `target` is always a temporary, so it never aliases an operand.
+    fn expression(&mut self, expression: &Expression, target: i128) -> Result<(), String> {
+        let result = Operand::relative(target);
+
+        match expression {
+            Expression::Number(_) | Expression::Variable(_) => {
+                let value = self.operand(expression)?;
+
+                self.builder.copy(value, result);
+            }
+            Expression::Unary(UnaryOperator::Negate, value) => {
+                let value = self.operand(value)?;
+
+                self.builder.multiply(value, Operand::immediate(-1), result);
+            }
+            Expression::Unary(UnaryOperator::Not, value) => {
+                let value = self.operand(value)?;
+
+                self.builder.equals(value, Operand::immediate(0), result);
+            }
+            Expression::Binary(
+                operator @ (BinaryOperator::And | BinaryOperator::Or),
+                left,
+                right,
+            ) => {
+                let end_label = self.builder.generate_label("short_circuit");
+
+                self.expression(left, target)?;
+
+                if *operator == BinaryOperator::And {
+                    self.builder
+                        .jump_if_false(result.clone(), Operand::label_address(&end_label));
+                } else {
+                    self.builder
+                        .jump_if_true(result.clone(), Operand::label_address(&end_label));
+                }
+
+                self.expression(right, target)?;
+                self.builder.label(&end_label);
+                self.normalize(target);
+            }
+            Expression::Binary(operator, left, right) => {
+                let left = self.operand(left)?;
+                let right = self.operand(right)?;
+
+                self.binary(*operator, left, right, target);
+            }
+            Expression::Call(name, arguments) => {
+                self.call(name, arguments, target)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn binary(&mut self, operator: BinaryOperator, left: Operand, right: Operand, target: i128) {
+        let result = Operand::relative(target);
+
+        match operator {
+            BinaryOperator::Add => self.builder.add(left, right, result),
+            BinaryOperator::Subtract => {
+                self.builder
+                    .multiply(right, Operand::immediate(-1), result.clone());
+                self.builder.add(left, result.clone(), result);
+            }
+            BinaryOperator::Multiply => self.builder.multiply(left, right, result),
+            BinaryOperator::Less => self.builder.less_than(left, right, result),
+            BinaryOperator::Greater => self.builder.less_than(right, left, result),
+            BinaryOperator::LessOrEqual => {
+                self.builder.less_than(right, left, result);
+                self.negate(target);
+            }
+            BinaryOperator::GreaterOrEqual => {
+                self.builder.less_than(left, right, result);
+                self.negate(target);
+            }
+            BinaryOperator::Equal => self.builder.equals(left, right, result),
+            BinaryOperator::NotEqual => {
+                self.builder.equals(left, right, result);
+                self.negate(target);
+            }
+            BinaryOperator::And | BinaryOperator::Or => unreachable!(),
+        }
+    }
+
+    fn call(&mut self, name: &str, arguments: &[Expression], target: i128) -> Result<(), String> {
+        let arity = *self
+            .arities
+            .get(name)
+            .ok_or_else(|| format!("Unknown function {}", name))?;
+
+        if arity != arguments.len() {
+            return Err(format!(
+                "{} takes {} arguments but was given {}",
+                name,
+                arity,
+                arguments.len()
+            ));
+        }
+
+        let result = Operand::relative(target);
+
+        match name {
+            "read" => self.builder.input(result),
+            "print" => {
+                let value = self.operand(&arguments[0])?;
+
+                self.builder.output(value.clone());
+                self.builder.copy(value, result);
+            }
+            _ => {
+                // every argument is evaluated before any is copied into the
+                // callee's frame, since evaluating one may make another call
+                let arguments = arguments
+                    .iter()
+                    .map(|argument| self.operand(argument))
+                    .collect::<Result<Vec<Operand>, String>>()?;
+
+                let frame_size = self.next_slot;
+
+                self.builder.call(name, &arguments, frame_size);
+                self.builder.copy(Operand::relative(frame_size + 1), result);
+            }
+        }
+
+        Ok(())
+    }
+
+    // 0 stays 0, anything else becomes 1
+    fn normalize(&mut self, slot: i128) {
+        self.negate(slot);
+        self.negate(slot);
+    }
+
+    fn negate(&mut self, slot: i128) {
+        self.builder.equals(
+            Operand::relative(slot),
+            Operand::immediate(0),
+            Operand::relative(slot),
+        );
+    }
+
+    fn allocate_slot(&mut self) -> i128 {
+        self.next_slot += 1;
+
+        self.next_slot - 1
+    }
+
+    fn variable_slot(&self, name: &str) -> Result<i128, String> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name))
+            .copied()
+            .ok_or_else(|| format!("Unknown variable {}", name))
+    }
+}
@@ -0,0 +1,249 @@
+use std::collections::HashMap;
+
+use super::syntax::{BinaryOperator, Expression, Function, Program, Statement, UnaryOperator};
+
+const MAX_CALL_DEPTH: usize = 200;
+
+// Runs the program directly, as a reference for the generated Intcode.
+pub fn interpret(program: &Program, inputs: &[i128]) -> Result<Vec<i128>, String> {
+    let mut interpreter = Interpreter {
+        functions: program
+            .functions
+            .iter()
+            .map(|function| (function.name.as_str(), function))
+            .collect(),
+        inputs: inputs.iter().copied().rev().collect(),
+        outputs: Vec::new(),
+        call_depth: 0,
+    };
+
+    interpreter.call("main", Vec::new())?;
+
+    Ok(interpreter.outputs)
+}
+
+struct Interpreter<'a> {
+    functions: HashMap<&'a str, &'a Function>,
+    // reversed, so the next input is at the end
+    inputs: Vec<i128>,
+    outputs: Vec<i128>,
+    call_depth: usize,
+}
+
+type Scopes = Vec<HashMap<String, i128>>;
+
+impl<'a> Interpreter<'a> {
+    fn call(&mut self, name: &str, arguments: Vec<i128>) -> Result<i128, String> {
+        match (name, arguments.as_slice()) {
+            ("read", []) => {
+                return self
+                    .inputs
+                    .pop()
+                    .ok_or_else(|| String::from("Ran out of input"))
+            }
+            ("print", [value]) => {
+                self.outputs.push(*value);
+
+                return Ok(*value);
+            }
+            _ => {}
+        }
+
+        let function = *self
+            .functions
+            .get(name)
+            .ok_or_else(|| format!("Unknown function {}", name))?;
+
+        if function.parameters.len() != arguments.len() {
+            return Err(format!(
+                "{} takes {} arguments but was given {}",
+                name,
+                function.parameters.len(),
+                arguments.len()
+            ));
+        }
+
+        if self.call_depth == MAX_CALL_DEPTH {
+            return Err(format!("Calls nested deeper than {}", MAX_CALL_DEPTH));
+        }
+
+        let mut scopes = vec![function.parameters.iter().cloned().zip(arguments).collect()];
+
+        self.call_depth += 1;
+
+        let result = self.block(&function.body, &mut scopes);
+
+        self.call_depth -= 1;
+
+        Ok(result?.unwrap_or(0))
+    }
+
+    // Some(value) once a return statement ran
+    fn block(
+        &mut self,
+        statements: &[Statement],
+        scopes: &mut Scopes,
+    ) -> Result<Option<i128>, String> {
+        scopes.push(HashMap::new());
+
+        let mut result = Ok(None);
+
+        for statement in statements {
+            result = self.statement(statement, scopes);
+
+            if !matches!(result, Ok(None)) {
+                break;
+            }
+        }
+
+        scopes.pop();
+
+        result
+    }
+
+    fn statement(
+        &mut self,
+        statement: &Statement,
+        scopes: &mut Scopes,
+    ) -> Result<Option<i128>, String> {
+        match statement {
+            Statement::Let(name, value) => {
+                let value = self.expression(value, scopes)?;
+
+                scopes.last_mut().unwrap().insert(name.clone(), value);
+            }
+            Statement::Assign(name, value) => {
+                let value = self.expression(value, scopes)?;
+
+                *Self::variable(name, scopes)? = value;
+            }
+            Statement::If(condition, then_block, else_block) => {
+                return if self.expression(condition, scopes)? != 0 {
+                    self.block(then_block, scopes)
+                } else {
+                    self.block(else_block, scopes)
+                };
+            }
+            Statement::While(condition, body) => {
+                while self.expression(condition, scopes)? != 0 {
+                    if let Some(value) = self.block(body, scopes)? {
+                        return Ok(Some(value));
+                    }
+                }
+            }
+            Statement::Return(value) => {
+                let value = match value {
+                    Some(value) => self.expression(value, scopes)?,
+                    None => 0,
+                };
+
+                return Ok(Some(value));
+            }
+            Statement::Expression(expression) => {
+                self.expression(expression, scopes)?;
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn expression(&mut self, expression: &Expression, scopes: &mut Scopes) -> Result<i128, String> {
+        match expression {
+            Expression::Number(number) => Ok(*number),
+            Expression::Variable(name) => Self::variable(name, scopes).map(|value| *value),
+            Expression::Unary(UnaryOperator::Negate, value) => self
+                .expression(value, scopes)?
+                .checked_neg()
+                .ok_or_else(|| String::from("Overflow")),
+            Expression::Unary(UnaryOperator::Not, value) => {
+                Ok((self.expression(value, scopes)? == 0) as i128)
+            }
+            Expression::Binary(BinaryOperator::And, left, right) => Ok((self
+                .expression(left, scopes)?
+                != 0
+                && self.expression(right, scopes)? != 0)
+                as i128),
+            Expression::Binary(BinaryOperator::Or, left, right) => Ok((self
+                .expression(left, scopes)?
+                != 0
+                || self.expression(right, scopes)? != 0)
+                as i128),
+            Expression::Binary(operator, left, right) => {
+                let left = self.expression(left, scopes)?;
+                let right = self.expression(right, scopes)?;
+
+                Self::binary(*operator, left, right)
+            }
+            Expression::Call(name, arguments) => {
+                let arguments = arguments
+                    .iter()
+                    .map(|argument| self.expression(argument, scopes))
+                    .collect::<Result<Vec<i128>, String>>()?;
+
+                self.call(name, arguments)
+            }
+        }
+    }
+
+    fn binary(operator: BinaryOperator, left: i128, right: i128) -> Result<i128, String> {
+        let result = match operator {
+            BinaryOperator::Add => left.checked_add(right),
+            BinaryOperator::Subtract => left.checked_sub(right),
+            BinaryOperator::Multiply => left.checked_mul(right),
+            BinaryOperator::Less => Some((left < right) as i128),
+            BinaryOperator::LessOrEqual => Some((left <= right) as i128),
+            BinaryOperator::Greater => Some((left > right) as i128),
+            BinaryOperator::GreaterOrEqual => Some((left >= right) as i128),
+            BinaryOperator::Equal => Some((left == right) as i128),
+            BinaryOperator::NotEqual => Some((left != right) as i128),
+            BinaryOperator::And | BinaryOperator::Or => unreachable!(),
+        };
+
+        result.ok_or_else(|| String::from("Overflow"))
+    }
+
+    fn variable<'b>(name: &str, scopes: &'b mut Scopes) -> Result<&'b mut i128, String> {
+        scopes
+            .iter_mut()
+            .rev()
+            .find_map(|scope| scope.get_mut(name))
+            .ok_or_else(|| format!("Unknown variable {}", name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::intcode_computer::compiler::parser::parse;
+
+    #[test]
+    fn test_interpret_scopes() {
+        let source = "fn main() {\n  let x = 1;\n  if 1 { let x = 2; print(x); x = 3; }\n  \
+                      print(x);\n}";
+
+        let result = interpret(&parse(source).unwrap(), &[]);
+
+        assert_eq!(result, Ok(vec![2, 1]));
+    }
+
+    #[test]
+    fn test_interpret_errors() {
+        let undefined = parse("fn main() { print(y); }").unwrap();
+        let no_input = parse("fn main() { read(); }").unwrap();
+        let endless = parse("fn f() { return f(); } fn main() { f(); }").unwrap();
+
+        assert_eq!(
+            interpret(&undefined, &[]),
+            Err(String::from("Unknown variable y"))
+        );
+        assert_eq!(
+            interpret(&no_input, &[]),
+            Err(String::from("Ran out of input"))
+        );
+        assert_eq!(
+            interpret(&endless, &[]),
+            Err(String::from("Calls nested deeper than 200"))
+        );
+    }
+}
@@ -0,0 +1,177 @@
+#[derive(Debug, PartialEq, Clone)]
+pub enum Token {
+    Number(i128),
+    Identifier(String),
+    Function,
+    Let,
+    If,
+    Else,
+    While,
+    Return,
+    LeftParenthesis,
+    RightParenthesis,
+    LeftBrace,
+    RightBrace,
+    Comma,
+    Semicolon,
+    Assign,
+    Plus,
+    Minus,
+    Star,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    Equal,
+    NotEqual,
+    Not,
+    And,
+    Or,
+}
+
+// Each token comes with the line it started on.
+pub fn tokenize(source: &str) -> Result<Vec<(Token, usize)>, String> {
+    let characters: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut index = 0;
+
+    while index < characters.len() {
+        let character = characters[index];
+        let next_character = characters.get(index + 1).copied();
+
+        if character == '\n' {
+            line += 1;
+            index += 1;
+            continue;
+        }
+
+        if character.is_whitespace() {
+            index += 1;
+            continue;
+        }
+
+        if character == '/' && next_character == Some('/') {
+            while index < characters.len() && characters[index] != '\n' {
+                index += 1;
+            }
+
+            continue;
+        }
+
+        if character.is_ascii_digit() {
+            let start = index;
+
+            while index < characters.len() && characters[index].is_ascii_digit() {
+                index += 1;
+            }
+
+            let text: String = characters[start..index].iter().collect();
+            let number = text
+                .parse()
+                .map_err(|_| format!("Line {}: number {} is too large", line, text))?;
+
+            tokens.push((Token::Number(number), line));
+            continue;
+        }
+
+        if character.is_ascii_alphabetic() || character == '_' {
+            let start = index;
+
+            while index < characters.len()
+                && (characters[index].is_ascii_alphanumeric() || characters[index] == '_')
+            {
+                index += 1;
+            }
+
+            let word: String = characters[start..index].iter().collect();
+
+            tokens.push((keyword_or_identifier(word), line));
+            continue;
+        }
+
+        let (token, length) = match (character, next_character) {
+            ('<', Some('=')) => (Token::LessOrEqual, 2),
+            ('>', Some('=')) => (Token::GreaterOrEqual, 2),
+            ('=', Some('=')) => (Token::Equal, 2),
+            ('!', Some('=')) => (Token::NotEqual, 2),
+            ('&', Some('&')) => (Token::And, 2),
+            ('|', Some('|')) => (Token::Or, 2),
+            ('(', _) => (Token::LeftParenthesis, 1),
+            (')', _) => (Token::RightParenthesis, 1),
+            ('{', _) => (Token::LeftBrace, 1),
+            ('}', _) => (Token::RightBrace, 1),
+            (',', _) => (Token::Comma, 1),
+            (';', _) => (Token::Semicolon, 1),
+            ('=', _) => (Token::Assign, 1),
+            ('+', _) => (Token::Plus, 1),
+            ('-', _) => (Token::Minus, 1),
+            ('*', _) => (Token::Star, 1),
+            ('<', _) => (Token::Less, 1),
+            ('>', _) => (Token::Greater, 1),
+            ('!', _) => (Token::Not, 1),
+            _ => return Err(format!("Line {}: unexpected character {}", line, character)),
+        };
+
+        tokens.push((token, line));
+        index += length;
+    }
+
+    Ok(tokens)
+}
+
+fn keyword_or_identifier(word: String) -> Token {
+    match word.as_str() {
+        "fn" => Token::Function,
+        "let" => Token::Let,
+        "if" => Token::If,
+        "else" => Token::Else,
+        "while" => Token::While,
+        "return" => Token::Return,
+        _ => Token::Identifier(word),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize() {
+        let source = "let x = 10; // comment\nwhile x >= 1 && !done { x = x-1; }";
+
+        let expected = vec![
+            (Token::Let, 1),
+            (Token::Identifier(String::from("x")), 1),
+            (Token::Assign, 1),
+            (Token::Number(10), 1),
+            (Token::Semicolon, 1),
+            (Token::While, 2),
+            (Token::Identifier(String::from("x")), 2),
+            (Token::GreaterOrEqual, 2),
+            (Token::Number(1), 2),
+            (Token::And, 2),
+            (Token::Not, 2),
+            (Token::Identifier(String::from("done")), 2),
+            (Token::LeftBrace, 2),
+            (Token::Identifier(String::from("x")), 2),
+            (Token::Assign, 2),
+            (Token::Identifier(String::from("x")), 2),
+            (Token::Minus, 2),
+            (Token::Number(1), 2),
+            (Token::Semicolon, 2),
+            (Token::RightBrace, 2),
+        ];
+
+        let result = tokenize(source).unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_tokenize_unexpected_character() {
+        let result = tokenize("let x = 1;\nx = x / 2;");
+
+        assert_eq!(result, Err(String::from("Line 2: unexpected character /")));
+    }
+}
@@ -0,0 +1,171 @@
+pub mod code_generator;
+pub mod interpreter;
+pub mod lexer;
+pub mod parser;
+pub mod syntax;
+
+use super::linker::Linker;
+
+// Compiles a program in the small language below into Intcode, ready for
+// IntcodeComputer::new.
+//
+//   fn square(x) { return x * x; }
+//   fn main() {
+//       let n = read();
+//       while n > 0 { print(square(n)); n = n - 1; }
+//   }
+//
+// Values are integers, conditions are true when non-zero and `read()` and
+// `print(x)` take and give the computer's inputs and outputs.
+pub fn compile(source: &str) -> Result<Vec<i128>, String> {
+    let program = parser::parse(source)?;
+
+    let mut linker = Linker::new();
+
+    linker.add_module(code_generator::generate(&program)?);
+
+    linker.link("main")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::intcode_computer::{IntcodeComputer, IntcodeComputerStatus};
+
+    const FACTORIAL: &str = "
+        fn factorial(n) {
+            if n <= 1 { return 1; }
+            return n * factorial(n - 1);
+        }
+
+        fn main() {
+            let count = read();
+            while count > 0 {
+                print(factorial(read()));
+                count = count - 1;
+            }
+        }";
+
+    const FIBONACCI: &str = "
+        fn fibonacci(n) {
+            if n < 2 { return n; }
+            return fibonacci(n - 1) + fibonacci(n - 2);
+        }
+
+        fn main() { print(fibonacci(read())); }";
+
+    const LOGIC: &str = "
+        // prints which of a few conditions hold for each pair of inputs
+        fn check(a, b) {
+            print(a == b);
+            print(a != b && a > b);
+            print(a < 0 || b >= 10);
+            print(!a);
+            print(-a - -b);
+            if a > b { return 1; } else if a < b { return -1; }
+        }
+
+        fn main() {
+            let i = 0;
+            while i < 3 {
+                let result = check(read(), read());
+                print(result);
+                i = i + 1;
+            }
+        }";
+
+    const SCOPES: &str = "
+        fn add3(a, b, c) { return a + b + c; }
+
+        fn main() {
+            let x = 5;
+            let y = add3(x, add3(1, 2, 3), x * 2);
+            if y {
+                let x = y + 1;
+                print(x);
+            }
+            print(x);
+            print(y);
+        }";
+
+    fn run_compiled(source: &str, inputs: &[i128]) -> Vec<i128> {
+        let program = compile(source).unwrap();
+        let mut intcode_computer = IntcodeComputer::new(program.as_slice());
+        let mut inputs = inputs.iter();
+
+        intcode_computer.execute_program();
+
+        while intcode_computer.get_status() == IntcodeComputerStatus::WaitingForInput {
+            intcode_computer.set_input(*inputs.next().unwrap());
+            intcode_computer.execute_program();
+        }
+
+        intcode_computer.get_outputs()
+    }
+
+    fn assert_matches_interpreter(source: &str, inputs: &[i128]) {
+        let expected = interpreter::interpret(&parser::parse(source).unwrap(), inputs).unwrap();
+
+        let result = run_compiled(source, inputs);
+
+        assert_eq!(result, expected, "inputs {:?}", inputs);
+    }
+
+    #[test]
+    fn test_factorial() {
+        assert_eq!(
+            run_compiled(FACTORIAL, &[3, 0, 5, 10]),
+            vec![1, 120, 3_628_800]
+        );
+
+        assert_matches_interpreter(FACTORIAL, &[4, 1, 2, 3, 20]);
+    }
+
+    #[test]
+    fn test_fibonacci() {
+        for n in 0..=12 {
+            assert_matches_interpreter(FIBONACCI, &[n]);
+        }
+    }
+
+    #[test]
+    fn test_logic() {
+        let pairs = [[0, 0], [3, -2], [-4, 12], [7, 7], [1, 0], [0, 1]];
+
+        for first in &pairs {
+            for second in &pairs {
+                let inputs = [first[0], first[1], second[0], second[1], 5, 5];
+
+                assert_matches_interpreter(LOGIC, &inputs);
+            }
+        }
+    }
+
+    #[test]
+    fn test_scopes() {
+        assert_eq!(run_compiled(SCOPES, &[]), vec![22, 5, 21]);
+
+        assert_matches_interpreter(SCOPES, &[]);
+    }
+
+    #[test]
+    fn test_compile_errors() {
+        assert_eq!(
+            compile("fn main() { print(x); }"),
+            Err(String::from("Unknown variable x"))
+        );
+        assert_eq!(
+            compile("fn f(a) {} fn main() { f(); }"),
+            Err(String::from("f takes 1 arguments but was given 0"))
+        );
+        assert_eq!(
+            compile("fn f() {} fn f() {} fn main() {}"),
+            Err(String::from("Function f is defined more than once"))
+        );
+        assert_eq!(
+            compile("fn start() {}"),
+            Err(String::from("No main function"))
+        );
+    }
+}
@@ -0,0 +1,408 @@
+use super::lexer::{tokenize, Token};
+use super::syntax::{BinaryOperator, Expression, Function, Program, Statement, UnaryOperator};
+
+// Binary operators from loosest to tightest binding
+const PRECEDENCE_LEVELS: [&[(Token, BinaryOperator)]; 5] = [
+    &[(Token::Or, BinaryOperator::Or)],
+    &[(Token::And, BinaryOperator::And)],
+    &[
+        (Token::Equal, BinaryOperator::Equal),
+        (Token::NotEqual, BinaryOperator::NotEqual),
+    ],
+    &[
+        (Token::Less, BinaryOperator::Less),
+        (Token::LessOrEqual, BinaryOperator::LessOrEqual),
+        (Token::Greater, BinaryOperator::Greater),
+        (Token::GreaterOrEqual, BinaryOperator::GreaterOrEqual),
+    ],
+    &[
+        (Token::Plus, BinaryOperator::Add),
+        (Token::Minus, BinaryOperator::Subtract),
+    ],
+];
+
+pub fn parse(source: &str) -> Result<Program, String> {
+    let mut parser = Parser {
+        tokens: tokenize(source)?,
+        position: 0,
+    };
+
+    let mut functions = Vec::new();
+
+    while parser.peek().is_some() {
+        functions.push(parser.function()?);
+    }
+
+    Ok(Program { functions })
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    position: usize,
+}
+
+impl Parser {
+    fn function(&mut self) -> Result<Function, String> {
+        self.expect(Token::Function)?;
+
+        let name = self.identifier()?;
+        let mut parameters = Vec::new();
+
+        self.expect(Token::LeftParenthesis)?;
+
+        if !self.next_is(Token::RightParenthesis) {
+            parameters.push(self.identifier()?);
+
+            while self.next_is(Token::Comma) {
+                self.advance();
+                parameters.push(self.identifier()?);
+            }
+        }
+
+        self.expect(Token::RightParenthesis)?;
+
+        Ok(Function {
+            name,
+            parameters,
+            body: self.block()?,
+        })
+    }
+
+    fn block(&mut self) -> Result<Vec<Statement>, String> {
+        let mut statements = Vec::new();
+
+        self.expect(Token::LeftBrace)?;
+
+        while !self.next_is(Token::RightBrace) {
+            statements.push(self.statement()?);
+        }
+
+        self.expect(Token::RightBrace)?;
+
+        Ok(statements)
+    }
+
+    fn statement(&mut self) -> Result<Statement, String> {
+        match self.peek() {
+            Some(Token::Let) => {
+                self.advance();
+
+                let name = self.identifier()?;
+
+                self.expect(Token::Assign)?;
+
+                let value = self.expression()?;
+
+                self.expect(Token::Semicolon)?;
+
+                Ok(Statement::Let(name, value))
+            }
+            Some(Token::If) => self.if_statement(),
+            Some(Token::While) => {
+                self.advance();
+
+                let condition = self.expression()?;
+
+                Ok(Statement::While(condition, self.block()?))
+            }
+            Some(Token::Return) => {
+                self.advance();
+
+                let value = if self.next_is(Token::Semicolon) {
+                    None
+                } else {
+                    Some(self.expression()?)
+                };
+
+                self.expect(Token::Semicolon)?;
+
+                Ok(Statement::Return(value))
+            }
+            Some(Token::Identifier(name))
+                if self.tokens.get(self.position + 1).map(|(token, _)| token)
+                    == Some(&Token::Assign) =>
+            {
+                let name = name.clone();
+
+                self.position += 2;
+
+                let value = self.expression()?;
+
+                self.expect(Token::Semicolon)?;
+
+                Ok(Statement::Assign(name, value))
+            }
+            _ => {
+                let expression = self.expression()?;
+
+                self.expect(Token::Semicolon)?;
+
+                Ok(Statement::Expression(expression))
+            }
+        }
+    }
+
+    fn if_statement(&mut self) -> Result<Statement, String> {
+        self.expect(Token::If)?;
+
+        let condition = self.expression()?;
+        let then_block = self.block()?;
+
+        let else_block = if self.next_is(Token::Else) {
+            self.advance();
+
+            if self.next_is(Token::If) {
+                vec![self.if_statement()?]
+            } else {
+                self.block()?
+            }
+        } else {
+            Vec::new()
+        };
+
+        Ok(Statement::If(condition, then_block, else_block))
+    }
+
+    fn expression(&mut self) -> Result<Expression, String> {
+        self.binary(0)
+    }
+
+    fn binary(&mut self, level: usize) -> Result<Expression, String> {
+        if level == PRECEDENCE_LEVELS.len() {
+            return self.product();
+        }
+
+        let mut left = self.binary(level + 1)?;
+
+        while let Some(operator) = self.binary_operator(PRECEDENCE_LEVELS[level]) {
+            self.advance();
+
+            let right = self.binary(level + 1)?;
+
+            left = Expression::Binary(operator, Box::new(left), Box::new(right));
+        }
+
+        Ok(left)
+    }
+
+    fn product(&mut self) -> Result<Expression, String> {
+        let mut left = self.unary()?;
+
+        while self.next_is(Token::Star) {
+            self.advance();
+
+            let right = self.unary()?;
+
+            left = Expression::Binary(BinaryOperator::Multiply, Box::new(left), Box::new(right));
+        }
+
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expression, String> {
+        let operator = match self.peek() {
+            Some(Token::Minus) => UnaryOperator::Negate,
+            Some(Token::Not) => UnaryOperator::Not,
+            _ => return self.primary(),
+        };
+
+        self.advance();
+
+        Ok(Expression::Unary(operator, Box::new(self.unary()?)))
+    }
+
+    fn primary(&mut self) -> Result<Expression, String> {
+        match self.peek().cloned() {
+            Some(Token::Number(number)) => {
+                self.advance();
+
+                Ok(Expression::Number(number))
+            }
+            Some(Token::Identifier(_)) => {
+                let name = self.identifier()?;
+
+                if !self.next_is(Token::LeftParenthesis) {
+                    return Ok(Expression::Variable(name));
+                }
+
+                self.advance();
+
+                let mut arguments = Vec::new();
+
+                if !self.next_is(Token::RightParenthesis) {
+                    arguments.push(self.expression()?);
+
+                    while self.next_is(Token::Comma) {
+                        self.advance();
+                        arguments.push(self.expression()?);
+                    }
+                }
+
+                self.expect(Token::RightParenthesis)?;
+
+                Ok(Expression::Call(name, arguments))
+            }
+            Some(Token::LeftParenthesis) => {
+                self.advance();
+
+                let expression = self.expression()?;
+
+                self.expect(Token::RightParenthesis)?;
+
+                Ok(expression)
+            }
+            _ => Err(self.error("an expression")),
+        }
+    }
+
+    fn binary_operator(&self, operators: &[(Token, BinaryOperator)]) -> Option<BinaryOperator> {
+        let next_token = self.peek()?;
+
+        operators
+            .iter()
+            .find(|(token, _)| token == next_token)
+            .map(|(_, operator)| *operator)
+    }
+
+    // Names starting with __ are kept for generated labels.
+    fn identifier(&mut self) -> Result<String, String> {
+        match self.peek().cloned() {
+            Some(Token::Identifier(name)) if name.starts_with("__") => Err(format!(
+                "Line {}: names can't start with __",
+                self.current_line()
+            )),
+            Some(Token::Identifier(name)) => {
+                self.advance();
+
+                Ok(name)
+            }
+            _ => Err(self.error("a name")),
+        }
+    }
+
+    fn expect(&mut self, token: Token) -> Result<(), String> {
+        if self.next_is(token.clone()) {
+            self.advance();
+
+            Ok(())
+        } else {
+            Err(self.error(&format!("{:?}", token)))
+        }
+    }
+
+    fn next_is(&self, token: Token) -> bool {
+        self.peek() == Some(&token)
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(token, _)| token)
+    }
+
+    fn advance(&mut self) {
+        self.position += 1;
+    }
+
+    fn current_line(&self) -> usize {
+        self.tokens
+            .get(self.position)
+            .or_else(|| self.tokens.last())
+            .map(|(_, line)| *line)
+            .unwrap_or(1)
+    }
+
+    fn error(&self, expected: &str) -> String {
+        match self.peek() {
+            Some(token) => format!(
+                "Line {}: expected {} but found {:?}",
+                self.current_line(),
+                expected,
+                token
+            ),
+            None => format!(
+                "Line {}: expected {} but reached the end",
+                self.current_line(),
+                expected
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn number(value: i128) -> Box<Expression> {
+        Box::new(Expression::Number(value))
+    }
+
+    #[test]
+    fn test_parse_precedence() {
+        let program = parse("fn main() { return 1 + 2 * -3 < 4 || 0; }").unwrap();
+
+        let product = Expression::Binary(
+            BinaryOperator::Multiply,
+            number(2),
+            Box::new(Expression::Unary(UnaryOperator::Negate, number(3))),
+        );
+        let sum = Expression::Binary(BinaryOperator::Add, number(1), Box::new(product));
+        let comparison = Expression::Binary(BinaryOperator::Less, Box::new(sum), number(4));
+        let expected = vec![Statement::Return(Some(Expression::Binary(
+            BinaryOperator::Or,
+            Box::new(comparison),
+            number(0),
+        )))];
+
+        assert_eq!(program.functions[0].body, expected);
+    }
+
+    #[test]
+    fn test_parse_statements() {
+        let source = "fn f(a, b) {\n  let x = a;\n  if x { x = b; } else if b { return; }\n  \
+                      while x { print(x); }\n}";
+
+        let program = parse(source).unwrap();
+        let function = &program.functions[0];
+
+        let expected_else = vec![Statement::If(
+            Expression::Variable(String::from("b")),
+            vec![Statement::Return(None)],
+            Vec::new(),
+        )];
+
+        assert_eq!(function.name, "f");
+        assert_eq!(function.parameters, vec!["a", "b"]);
+        assert_eq!(function.body.len(), 3);
+
+        match &function.body[1] {
+            Statement::If(_, then_block, else_block) => {
+                assert_eq!(
+                    then_block,
+                    &vec![Statement::Assign(
+                        String::from("x"),
+                        Expression::Variable(String::from("b"))
+                    )]
+                );
+                assert_eq!(else_block, &expected_else);
+            }
+            statement => panic!("Expected an if statement, found {:?}", statement),
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse("fn main() {\n  let = 3;\n}"),
+            Err(String::from("Line 2: expected a name but found Assign"))
+        );
+        assert_eq!(
+            parse("fn main() { return 1"),
+            Err(String::from(
+                "Line 1: expected Semicolon but reached the end"
+            ))
+        );
+        assert_eq!(
+            parse("fn __main() {}"),
+            Err(String::from("Line 1: names can't start with __"))
+        );
+    }
+}
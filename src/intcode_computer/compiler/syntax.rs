@@ -0,0 +1,54 @@
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum UnaryOperator {
+    Negate,
+    Not,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum BinaryOperator {
+    Add,
+    Subtract,
+    Multiply,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    Equal,
+    NotEqual,
+    And,
+    Or,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Expression {
+    Number(i128),
+    Variable(String),
+    Unary(UnaryOperator, Box<Expression>),
+    Binary(BinaryOperator, Box<Expression>, Box<Expression>),
+    // read() and print(x) are calls to built in functions
+    Call(String, Vec<Expression>),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Statement {
+    Let(String, Expression),
+    Assign(String, Expression),
+    If(Expression, Vec<Statement>, Vec<Statement>),
+    While(Expression, Vec<Statement>),
+    Return(Option<Expression>),
+    Expression(Expression),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Function {
+    pub name: String,
+    pub parameters: Vec<String>,
+    pub body: Vec<Statement>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Program {
+    pub functions: Vec<Function>,
+}
+
+pub const BUILT_IN_FUNCTIONS: [(&str, usize); 2] = [("read", 0), ("print", 1)];
@@ -0,0 +1,479 @@
+use std::collections::{BTreeMap, HashMap};
+
+use super::symbolic_executor::{LinearExpression, Symbol};
+
+const MAX_PROPAGATION_ROUNDS: usize = 64;
+const MAX_SEARCH_NODES: usize = 100_000;
+
+pub type Assignment = HashMap<Symbol, i128>;
+
+type Domains = BTreeMap<Symbol, (i128, i128)>;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Satisfiability {
+    Satisfiable(Assignment),
+    Unsatisfiable,
+    // The search budget ran out before a solution or a proof was found
+    Unknown,
+}
+
+impl Satisfiability {
+    pub fn get_assignment(self) -> Option<Assignment> {
+        match self {
+            Satisfiability::Satisfiable(assignment) => Some(assignment),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Relation {
+    Equal,
+    NotEqual,
+    LessThan,
+    GreaterOrEqual,
+}
+
+// A constraint of the form `expression <relation> 0`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Constraint {
+    expression: LinearExpression,
+    relation: Relation,
+}
+
+impl Constraint {
+    pub fn new(expression: LinearExpression, relation: Relation) -> Constraint {
+        Constraint {
+            expression,
+            relation,
+        }
+    }
+
+    pub fn equals(first: &LinearExpression, second: &LinearExpression) -> Option<Constraint> {
+        Some(Constraint::new(first.sub(second)?, Relation::Equal))
+    }
+
+    pub fn not_equals(first: &LinearExpression, second: &LinearExpression) -> Option<Constraint> {
+        Some(Constraint::new(first.sub(second)?, Relation::NotEqual))
+    }
+
+    pub fn less_than(first: &LinearExpression, second: &LinearExpression) -> Option<Constraint> {
+        Some(Constraint::new(first.sub(second)?, Relation::LessThan))
+    }
+
+    pub fn greater_or_equal(
+        first: &LinearExpression,
+        second: &LinearExpression,
+    ) -> Option<Constraint> {
+        Some(Constraint::new(
+            first.sub(second)?,
+            Relation::GreaterOrEqual,
+        ))
+    }
+
+    pub fn get_expression(&self) -> &LinearExpression {
+        &self.expression
+    }
+
+    pub fn get_relation(&self) -> Relation {
+        self.relation
+    }
+
+    pub fn is_satisfied_by(&self, assignment: &Assignment) -> Option<bool> {
+        let value = self.expression.evaluate(assignment)?;
+
+        let is_satisfied = match self.relation {
+            Relation::Equal => value == 0,
+            Relation::NotEqual => value != 0,
+            Relation::LessThan => value < 0,
+            Relation::GreaterOrEqual => value >= 0,
+        };
+
+        Some(is_satisfied)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ConstraintSolver {
+    domains: Domains,
+    default_domain: (i128, i128),
+    max_search_nodes: usize,
+}
+
+impl ConstraintSolver {
+    pub fn new(default_lower_bound: i128, default_upper_bound: i128) -> ConstraintSolver {
+        ConstraintSolver {
+            domains: BTreeMap::new(),
+            default_domain: (default_lower_bound, default_upper_bound),
+            max_search_nodes: MAX_SEARCH_NODES,
+        }
+    }
+
+    pub fn set_domain(&mut self, symbol: Symbol, lower_bound: i128, upper_bound: i128) {
+        self.domains.insert(symbol, (lower_bound, upper_bound));
+    }
+
+    pub fn get_domain(&self, symbol: &Symbol) -> (i128, i128) {
+        *self.domains.get(symbol).unwrap_or(&self.default_domain)
+    }
+
+    pub fn set_max_search_nodes(&mut self, max_search_nodes: usize) {
+        self.max_search_nodes = max_search_nodes;
+    }
+
+    pub fn solve(&self, constraints: &[Constraint]) -> Satisfiability {
+        let mut domains = Domains::new();
+
+        for constraint in constraints {
+            for symbol in constraint.expression.get_coefficients().keys() {
+                domains.insert(*symbol, self.get_domain(symbol));
+            }
+        }
+
+        let mut nodes_left = self.max_search_nodes;
+
+        Self::search(domains, constraints, &mut nodes_left)
+    }
+
+    fn search(
+        mut domains: Domains,
+        constraints: &[Constraint],
+        nodes_left: &mut usize,
+    ) -> Satisfiability {
+        if *nodes_left == 0 {
+            return Satisfiability::Unknown;
+        }
+
+        *nodes_left -= 1;
+
+        if !Self::propagate(&mut domains, constraints) {
+            return Satisfiability::Unsatisfiable;
+        }
+
+        let split_symbol = domains
+            .iter()
+            .filter(|(_, (lower, upper))| lower < upper)
+            .min_by_key(|(_, (lower, upper))| upper.saturating_sub(*lower))
+            .map(|(symbol, _)| *symbol);
+
+        match split_symbol {
+            Some(symbol) => {
+                let (lower, upper) = domains[&symbol];
+                let middle = lower + (upper - lower) / 2;
+
+                let mut lower_half = domains.clone();
+                lower_half.insert(symbol, (lower, middle));
+
+                let lower_result = Self::search(lower_half, constraints, nodes_left);
+
+                if let Satisfiability::Satisfiable(_) = lower_result {
+                    return lower_result;
+                }
+
+                domains.insert(symbol, (middle + 1, upper));
+
+                match Self::search(domains, constraints, nodes_left) {
+                    Satisfiability::Unsatisfiable => lower_result,
+                    upper_result => upper_result,
+                }
+            }
+            None => {
+                let assignment: Assignment = domains
+                    .iter()
+                    .map(|(symbol, (value, _))| (*symbol, *value))
+                    .collect();
+
+                let is_solution = constraints
+                    .iter()
+                    .map(|constraint| constraint.is_satisfied_by(&assignment))
+                    .try_fold(true, |is_solution, is_satisfied| {
+                        Some(is_solution && is_satisfied?)
+                    });
+
+                match is_solution {
+                    Some(true) => Satisfiability::Satisfiable(assignment),
+                    Some(false) => Satisfiability::Unsatisfiable,
+                    // the expression overflowed, so it can't be checked
+                    None => Satisfiability::Unknown,
+                }
+            }
+        }
+    }
+
+    // Tightens the domains using interval bounds. Returns false if a domain
+    // became empty.
+    fn propagate(domains: &mut Domains, constraints: &[Constraint]) -> bool {
+        for _ in 0..MAX_PROPAGATION_ROUNDS {
+            let mut has_changed = false;
+
+            for constraint in constraints {
+                match Self::tighten(domains, constraint) {
+                    Some(changed) => has_changed |= changed,
+                    None => return false,
+                }
+            }
+
+            if !has_changed {
+                break;
+            }
+        }
+
+        true
+    }
+
+    // Returns None if the constraint cannot be satisfied, otherwise whether any
+    // domain was narrowed.
+    fn tighten(domains: &mut Domains, constraint: &Constraint) -> Option<bool> {
+        let expression = &constraint.expression;
+
+        let bounds = match Self::expression_bounds(domains, expression) {
+            Some(bounds) => bounds,
+            None => return Some(false),
+        };
+
+        let (total_min, total_max) = bounds;
+
+        // Bounds the expression must lie in, inclusive.
+        let (required_min, required_max) = match constraint.relation {
+            Relation::Equal => (0, 0),
+            Relation::LessThan => (i128::MIN, -1),
+            Relation::GreaterOrEqual => (0, i128::MAX),
+            Relation::NotEqual => {
+                return Self::tighten_not_equal(domains, expression, bounds);
+            }
+        };
+
+        if total_max < required_min || required_max < total_min {
+            return None;
+        }
+
+        let mut has_changed = false;
+
+        for (symbol, &coefficient) in expression.get_coefficients() {
+            let (lower, upper) = domains[symbol];
+            let (term_min, term_max) = Self::term_bounds(coefficient, lower, upper)?;
+
+            // the term has to fit in [required - rest]
+            let rest_min = total_min - term_min;
+            let rest_max = total_max - term_max;
+
+            let term_lower = required_min.checked_sub(rest_max);
+            let term_upper = required_max.checked_sub(rest_min);
+
+            let (mut new_lower, mut new_upper) = (lower, upper);
+
+            let (lower_term, upper_term) = if coefficient > 0 {
+                (term_lower, term_upper)
+            } else {
+                (term_upper, term_lower)
+            };
+
+            if let Some(bound) = lower_term.and_then(|term| ceil_div(term, coefficient)) {
+                new_lower = new_lower.max(bound);
+            }
+
+            if let Some(bound) = upper_term.and_then(|term| floor_div(term, coefficient)) {
+                new_upper = new_upper.min(bound);
+            }
+
+            if new_upper < new_lower {
+                return None;
+            }
+
+            if (new_lower, new_upper) != (lower, upper) {
+                domains.insert(*symbol, (new_lower, new_upper));
+                has_changed = true;
+            }
+        }
+
+        Some(has_changed)
+    }
+
+    fn tighten_not_equal(
+        domains: &mut Domains,
+        expression: &LinearExpression,
+        (total_min, total_max): (i128, i128),
+    ) -> Option<bool> {
+        if total_min == 0 && total_max == 0 {
+            return None;
+        }
+
+        let mut unfixed = expression
+            .get_coefficients()
+            .iter()
+            .filter(|(symbol, _)| domains[*symbol].0 < domains[*symbol].1);
+
+        let (symbol, coefficient) = match (unfixed.next(), unfixed.next()) {
+            (Some((&symbol, &coefficient)), None) => (symbol, coefficient),
+            _ => return Some(false),
+        };
+
+        let (lower, upper) = domains[&symbol];
+        let (term_min, _) = Self::term_bounds(coefficient, lower, upper)?;
+        let rest = total_min - term_min;
+
+        // the only value of the symbol that would make the expression zero
+        if rest % coefficient != 0 {
+            return Some(false);
+        }
+
+        let forbidden = -rest / coefficient;
+
+        if forbidden == lower {
+            domains.insert(symbol, (lower + 1, upper));
+        } else if forbidden == upper {
+            domains.insert(symbol, (lower, upper - 1));
+        } else {
+            return Some(false);
+        }
+
+        Some(true)
+    }
+
+    fn expression_bounds(domains: &Domains, expression: &LinearExpression) -> Option<(i128, i128)> {
+        let mut total_min = expression.get_constant();
+        let mut total_max = expression.get_constant();
+
+        for (symbol, &coefficient) in expression.get_coefficients() {
+            let (lower, upper) = domains[symbol];
+            let (term_min, term_max) = Self::term_bounds(coefficient, lower, upper)?;
+
+            total_min = total_min.checked_add(term_min)?;
+            total_max = total_max.checked_add(term_max)?;
+        }
+
+        Some((total_min, total_max))
+    }
+
+    fn term_bounds(coefficient: i128, lower: i128, upper: i128) -> Option<(i128, i128)> {
+        let at_lower = coefficient.checked_mul(lower)?;
+        let at_upper = coefficient.checked_mul(upper)?;
+
+        Some((at_lower.min(at_upper), at_lower.max(at_upper)))
+    }
+}
+
+fn floor_div(numerator: i128, denominator: i128) -> Option<i128> {
+    let quotient = numerator.checked_div(denominator)?;
+
+    if numerator % denominator != 0 && ((numerator < 0) != (denominator < 0)) {
+        Some(quotient - 1)
+    } else {
+        Some(quotient)
+    }
+}
+
+fn ceil_div(numerator: i128, denominator: i128) -> Option<i128> {
+    let quotient = numerator.checked_div(denominator)?;
+
+    if numerator % denominator != 0 && ((numerator < 0) == (denominator < 0)) {
+        Some(quotient + 1)
+    } else {
+        Some(quotient)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn x() -> LinearExpression {
+        LinearExpression::symbol(Symbol::Input(0))
+    }
+
+    fn y() -> LinearExpression {
+        LinearExpression::symbol(Symbol::Input(1))
+    }
+
+    // a * x + b * y
+    fn x_and_y(a: i128, b: i128) -> LinearExpression {
+        x().scale(a).unwrap().add(&y().scale(b).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_solve_linear_equation() {
+        let solver = ConstraintSolver::new(0, 10);
+        // 2x + 3y == 12, x < y
+        let constraints = vec![
+            Constraint::equals(&x_and_y(2, 3), &LinearExpression::constant(12)).unwrap(),
+            Constraint::less_than(&x(), &y()).unwrap(),
+        ];
+
+        let expected: Assignment = vec![(Symbol::Input(0), 0), (Symbol::Input(1), 4)]
+            .into_iter()
+            .collect();
+
+        let result = solver.solve(&constraints);
+
+        assert_eq!(result, Satisfiability::Satisfiable(expected));
+    }
+
+    #[test]
+    fn test_solve_respects_domains() {
+        let mut solver = ConstraintSolver::new(0, 99);
+        solver.set_domain(Symbol::Input(0), 50, 60);
+
+        let constraints =
+            vec![Constraint::greater_or_equal(&LinearExpression::constant(52), &x()).unwrap()];
+
+        let result = solver.solve(&constraints).get_assignment().unwrap();
+
+        assert_eq!(result[&Symbol::Input(0)], 50);
+    }
+
+    #[test]
+    fn test_solve_not_equal() {
+        let mut solver = ConstraintSolver::new(0, 99);
+        solver.set_domain(Symbol::Input(0), 7, 8);
+
+        let constraints =
+            vec![Constraint::not_equals(&x(), &LinearExpression::constant(7)).unwrap()];
+
+        let result = solver.solve(&constraints).get_assignment().unwrap();
+
+        assert_eq!(result[&Symbol::Input(0)], 8);
+    }
+
+    #[test]
+    fn test_solve_unsatisfiable() {
+        let solver = ConstraintSolver::new(-1000, 1000);
+        let constraints = vec![
+            Constraint::less_than(&x(), &LinearExpression::constant(0)).unwrap(),
+            Constraint::greater_or_equal(&x(), &LinearExpression::constant(0)).unwrap(),
+        ];
+
+        assert_eq!(solver.solve(&constraints), Satisfiability::Unsatisfiable);
+    }
+
+    #[test]
+    fn test_solve_parity() {
+        let solver = ConstraintSolver::new(0, 99);
+        // 2x + 2y == 7 has no integer solutions
+        let constraints =
+            vec![Constraint::equals(&x_and_y(2, 2), &LinearExpression::constant(7)).unwrap()];
+
+        assert_eq!(solver.solve(&constraints), Satisfiability::Unsatisfiable);
+    }
+
+    #[test]
+    fn test_solve_runs_out_of_search_nodes() {
+        let mut solver = ConstraintSolver::new(i64::MIN as i128, i64::MAX as i128);
+        solver.set_max_search_nodes(1_000);
+        // the same parity constraint can't be refuted over such large domains
+        let constraints =
+            vec![Constraint::equals(&x_and_y(2, 2), &LinearExpression::constant(7)).unwrap()];
+
+        assert_eq!(solver.solve(&constraints), Satisfiability::Unknown);
+    }
+
+    #[test]
+    fn test_floor_and_ceil_div() {
+        assert_eq!(floor_div(7, 2), Some(3));
+        assert_eq!(floor_div(-7, 2), Some(-4));
+        assert_eq!(floor_div(7, -2), Some(-4));
+        assert_eq!(ceil_div(7, 2), Some(4));
+        assert_eq!(ceil_div(-7, 2), Some(-3));
+        assert_eq!(ceil_div(-7, -2), Some(4));
+        assert_eq!(floor_div(i128::MIN, -1), None);
+    }
+}
@@ -0,0 +1,289 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::io;
+
+use super::intcode_instruction::Opcode;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct BranchCoverage {
+    pub taken: u64,
+    pub not_taken: u64,
+}
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Coverage {
+    // Addresses that decode as instructions in a linear sweep of the program.
+    // Code and data are mixed in Intcode, so these are only candidates.
+    candidate_instructions: BTreeSet<u128>,
+    instruction_hits: BTreeMap<u128, u64>,
+    branches: BTreeMap<u128, BranchCoverage>,
+}
+
+impl Coverage {
+    pub fn new(program: &HashMap<u128, i128>) -> Coverage {
+        Coverage {
+            candidate_instructions: Self::sweep_instructions(program),
+            instruction_hits: BTreeMap::new(),
+            branches: BTreeMap::new(),
+        }
+    }
+
+    pub fn record_instruction(&mut self, index: u128) {
+        *self.instruction_hits.entry(index).or_insert(0) += 1;
+    }
+
+    pub fn record_branch(&mut self, index: u128, was_taken: bool) {
+        let branch = self.branches.entry(index).or_default();
+
+        if was_taken {
+            branch.taken += 1;
+        } else {
+            branch.not_taken += 1;
+        }
+    }
+
+    // Combines coverage from another run, e.g. a clone of the same computer.
+    pub fn merge(&mut self, other: &Coverage) {
+        self.candidate_instructions
+            .extend(other.candidate_instructions.iter().copied());
+
+        for (&index, &hits) in &other.instruction_hits {
+            *self.instruction_hits.entry(index).or_insert(0) += hits;
+        }
+
+        for (&index, branch) in &other.branches {
+            let own_branch = self.branches.entry(index).or_default();
+
+            own_branch.taken += branch.taken;
+            own_branch.not_taken += branch.not_taken;
+        }
+    }
+
+    pub fn get_instruction_hits(&self) -> &BTreeMap<u128, u64> {
+        &self.instruction_hits
+    }
+
+    pub fn get_branches(&self) -> &BTreeMap<u128, BranchCoverage> {
+        &self.branches
+    }
+
+    pub fn get_unexecuted_instructions(&self) -> Vec<u128> {
+        self.candidate_instructions
+            .iter()
+            .filter(|index| !self.instruction_hits.contains_key(index))
+            .copied()
+            .collect()
+    }
+
+    // Branches where only one direction was exercised, with the missing
+    // direction (true for taken).
+    pub fn get_partial_branches(&self) -> Vec<(u128, bool)> {
+        self.branches
+            .iter()
+            .filter_map(|(&index, branch)| match (branch.taken, branch.not_taken) {
+                (0, _) => Some((index, true)),
+                (_, 0) => Some((index, false)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn summary(&self) -> String {
+        let instructions = self.all_instructions();
+        let number_of_directions = self.branches.len() * 2;
+        let number_of_covered_directions: usize = self
+            .branches
+            .values()
+            .map(|branch| (branch.taken != 0) as usize + (branch.not_taken != 0) as usize)
+            .sum();
+
+        let mut lines = vec![
+            format!(
+                "Instructions: {}/{} executed",
+                self.instruction_hits.len(),
+                instructions.len()
+            ),
+            format!(
+                "Branches: {}/{} directions taken",
+                number_of_covered_directions, number_of_directions
+            ),
+        ];
+
+        for index in self.get_unexecuted_instructions() {
+            lines.push(format!("  not executed: {}", index));
+        }
+
+        for (index, missing_taken) in self.get_partial_branches() {
+            let direction = if missing_taken { "taken" } else { "not taken" };
+
+            lines.push(format!("  branch at {} never {}", index, direction));
+        }
+
+        lines.join("\n")
+    }
+
+    // lcov style tracefile, with instruction addresses in place of line numbers
+    pub fn to_lcov(&self, source_name: &str) -> String {
+        let mut lines = vec![String::from("TN:"), format!("SF:{}", source_name)];
+
+        let instructions = self.all_instructions();
+
+        for index in &instructions {
+            let hits = self.instruction_hits.get(index).unwrap_or(&0);
+
+            lines.push(format!("DA:{},{}", index, hits));
+        }
+
+        for (index, branch) in &self.branches {
+            lines.push(format!("BRDA:{},0,0,{}", index, branch.taken));
+            lines.push(format!("BRDA:{},0,1,{}", index, branch.not_taken));
+        }
+
+        let branches_hit = self
+            .branches
+            .values()
+            .map(|branch| (branch.taken != 0) as usize + (branch.not_taken != 0) as usize)
+            .sum::<usize>();
+
+        lines.push(format!("BRF:{}", self.branches.len() * 2));
+        lines.push(format!("BRH:{}", branches_hit));
+        lines.push(format!("LF:{}", instructions.len()));
+        lines.push(format!("LH:{}", self.instruction_hits.len()));
+        lines.push(String::from("end_of_record"));
+
+        lines.join("\n") + "\n"
+    }
+
+    pub fn write_lcov(&self, source_name: &str, file_name: &str) -> io::Result<()> {
+        fs::write(file_name, self.to_lcov(source_name))
+    }
+
+    fn all_instructions(&self) -> BTreeSet<u128> {
+        self.candidate_instructions
+            .iter()
+            .chain(self.instruction_hits.keys())
+            .copied()
+            .collect()
+    }
+
+    fn sweep_instructions(program: &HashMap<u128, i128>) -> BTreeSet<u128> {
+        let mut instructions = BTreeSet::new();
+        let mut index = 0;
+
+        while let Some(&value) = program.get(&index) {
+            let instruction_length = Opcode::instruction_length(value)
+                .filter(|&length| (index..(index + length)).all(|i| program.contains_key(&i)));
+
+            match instruction_length {
+                Some(length) => {
+                    instructions.insert(index);
+                    index += length;
+                }
+                None => index += 1,
+            }
+        }
+
+        instructions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::intcode_computer::{slice_to_hashmap, IntcodeComputer};
+
+    // outputs 0 if the input is 0, otherwise 1
+    const JUMP_PROGRAM: [i128; 13] = [3, 3, 1105, -1, 9, 1101, 0, 0, 12, 4, 12, 99, 1];
+
+    #[test]
+    fn test_sweep_instructions() {
+        let program = slice_to_hashmap(&[3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8]);
+
+        let expected: BTreeSet<u128> = vec![0, 2, 6, 8].into_iter().collect();
+
+        let result = Coverage::sweep_instructions(&program);
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_coverage_across_runs() {
+        let mut intcode_computer = IntcodeComputer::new(JUMP_PROGRAM.as_ref());
+
+        intcode_computer.enable_coverage();
+
+        intcode_computer.set_input(0);
+        intcode_computer.execute_program();
+
+        let coverage = intcode_computer.get_coverage().unwrap();
+
+        assert_eq!(coverage.get_partial_branches(), vec![(2, true)]);
+        assert!(coverage.get_unexecuted_instructions().is_empty());
+
+        intcode_computer.reset();
+        intcode_computer.set_input(5);
+        intcode_computer.execute_program();
+
+        let coverage = intcode_computer.get_coverage().unwrap();
+
+        let expected_branch = BranchCoverage {
+            taken: 1,
+            not_taken: 1,
+        };
+
+        assert!(coverage.get_partial_branches().is_empty());
+        assert_eq!(coverage.get_branches()[&2], expected_branch);
+        assert_eq!(coverage.get_instruction_hits()[&5], 1);
+        assert_eq!(coverage.get_instruction_hits()[&9], 2);
+    }
+
+    #[test]
+    fn test_to_lcov() {
+        let mut intcode_computer = IntcodeComputer::new(JUMP_PROGRAM.as_ref());
+
+        intcode_computer.enable_coverage();
+        intcode_computer.set_input(1);
+        intcode_computer.execute_program();
+
+        let expected = "TN:\nSF:jump\nDA:0,1\nDA:2,1\nDA:5,0\nDA:9,1\nDA:11,1\n\
+                        BRDA:2,0,0,1\nBRDA:2,0,1,0\nBRF:2\nBRH:1\nLF:5\nLH:4\nend_of_record\n";
+
+        let result = intcode_computer.get_coverage().unwrap().to_lcov("jump");
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_summary() {
+        let mut intcode_computer = IntcodeComputer::new(JUMP_PROGRAM.as_ref());
+
+        intcode_computer.enable_coverage();
+        intcode_computer.set_input(1);
+        intcode_computer.execute_program();
+
+        let expected = "Instructions: 4/5 executed\nBranches: 1/2 directions taken\n  \
+                        not executed: 5\n  branch at 2 never not taken";
+
+        let result = intcode_computer.get_coverage().unwrap().summary();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_merge() {
+        let program = slice_to_hashmap(&JUMP_PROGRAM);
+        let mut first = Coverage::new(&program);
+        let mut second = Coverage::new(&program);
+
+        first.record_instruction(0);
+        first.record_branch(2, true);
+        second.record_instruction(0);
+        second.record_branch(2, false);
+
+        first.merge(&second);
+
+        assert_eq!(first.get_instruction_hits()[&0], 2);
+        assert!(first.get_partial_branches().is_empty());
+    }
+}
@@ -0,0 +1,313 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use super::intcode_instruction::Opcode;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum HookAction {
+    Continue,
+    Pause,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct MemoryMutation {
+    pub address: u128,
+    pub old_value: i128,
+    pub new_value: i128,
+}
+
+// `result` and `mutation` are only set after the instruction executed.
+#[derive(Debug)]
+pub struct InstructionEvent<'a> {
+    pub index: u128,
+    pub base_index: u128,
+    pub opcode: &'a Opcode,
+    pub result: Option<i128>,
+    pub mutation: Option<MemoryMutation>,
+    pub memory: &'a HashMap<u128, i128>,
+}
+
+pub trait ExecutionHook {
+    // Pausing here stops the computer before the instruction executes.
+    fn before_instruction(&mut self, _event: &InstructionEvent) -> HookAction {
+        HookAction::Continue
+    }
+
+    // Pausing here stops the computer after the instruction executed.
+    fn after_instruction(&mut self, _event: &InstructionEvent) -> HookAction {
+        HookAction::Continue
+    }
+}
+
+pub type SharedHook = Arc<Mutex<dyn ExecutionHook + Send>>;
+
+// Clones of a computer share its hooks.
+#[derive(Clone, Default)]
+pub struct ExecutionHooks {
+    hooks: Vec<SharedHook>,
+}
+
+impl ExecutionHooks {
+    pub fn add(&mut self, hook: SharedHook) {
+        self.hooks.push(hook);
+    }
+
+    pub fn clear(&mut self) {
+        self.hooks.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hooks.is_empty()
+    }
+
+    // Every hook sees the event, even if an earlier one asked to pause.
+    pub fn before_instruction(&self, event: &InstructionEvent) -> HookAction {
+        self.run(|hook| hook.before_instruction(event))
+    }
+
+    pub fn after_instruction(&self, event: &InstructionEvent) -> HookAction {
+        self.run(|hook| hook.after_instruction(event))
+    }
+
+    fn run<F>(&self, f: F) -> HookAction
+    where
+        F: Fn(&mut (dyn ExecutionHook + Send)) -> HookAction,
+    {
+        self.hooks
+            .iter()
+            .map(|hook| f(&mut *hook.lock().unwrap()))
+            .fold(HookAction::Continue, |action, hook_action| {
+                if hook_action == HookAction::Pause {
+                    HookAction::Pause
+                } else {
+                    action
+                }
+            })
+    }
+}
+
+impl fmt::Debug for ExecutionHooks {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ExecutionHooks({})", self.hooks.len())
+    }
+}
+
+impl PartialEq for ExecutionHooks {
+    fn eq(&self, other: &ExecutionHooks) -> bool {
+        self.hooks.len() == other.hooks.len()
+            && self
+                .hooks
+                .iter()
+                .zip(other.hooks.iter())
+                .all(|(first, second)| Arc::ptr_eq(first, second))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::intcode_computer::{IntcodeComputer, IntcodeComputerStatus};
+
+    const PROGRAM: [i128; 17] = [
+        3, 15, 3, 16, 1002, 16, 10, 16, 1, 16, 15, 15, 4, 15, 99, 566, 10,
+    ];
+
+    #[derive(Default)]
+    struct Tracer {
+        before: Vec<u128>,
+        after: Vec<(u128, Option<MemoryMutation>)>,
+    }
+
+    impl ExecutionHook for Tracer {
+        fn before_instruction(&mut self, event: &InstructionEvent) -> HookAction {
+            self.before.push(event.index);
+
+            HookAction::Continue
+        }
+
+        fn after_instruction(&mut self, event: &InstructionEvent) -> HookAction {
+            self.after.push((event.index, event.mutation));
+
+            HookAction::Continue
+        }
+    }
+
+    struct Watchpoint {
+        address: u128,
+    }
+
+    impl ExecutionHook for Watchpoint {
+        fn after_instruction(&mut self, event: &InstructionEvent) -> HookAction {
+            match event.mutation {
+                Some(mutation) if mutation.address == self.address => HookAction::Pause,
+                _ => HookAction::Continue,
+            }
+        }
+    }
+
+    struct Breakpoint {
+        index: u128,
+    }
+
+    impl ExecutionHook for Breakpoint {
+        fn before_instruction(&mut self, event: &InstructionEvent) -> HookAction {
+            if event.index == self.index {
+                HookAction::Pause
+            } else {
+                HookAction::Continue
+            }
+        }
+    }
+
+    #[test]
+    fn test_tracer_hook() {
+        let mut intcode_computer = IntcodeComputer::new(PROGRAM.as_ref());
+        let tracer = intcode_computer.add_hook(Tracer::default());
+
+        intcode_computer.execute_program();
+        intcode_computer.set_input(656);
+        intcode_computer.execute_program();
+        intcode_computer.set_input(10);
+        intcode_computer.execute_program();
+
+        let tracer = tracer.lock().unwrap();
+
+        let expected_before = vec![0, 2, 4, 8, 12, 14];
+        let expected_mutation = Some(MemoryMutation {
+            address: 16,
+            old_value: 10,
+            new_value: 100,
+        });
+
+        assert_eq!(tracer.before, expected_before);
+        assert_eq!(tracer.after.len(), 6);
+        assert_eq!(tracer.after[2], (4, expected_mutation));
+        assert_eq!(tracer.after[4], (12, None));
+    }
+
+    #[test]
+    fn test_watchpoint_pauses_after_write() {
+        let mut intcode_computer = IntcodeComputer::new(PROGRAM.as_ref());
+        intcode_computer.add_hook(Watchpoint { address: 15 });
+
+        intcode_computer.set_input(656);
+        intcode_computer.execute_program();
+
+        assert_eq!(intcode_computer.get_status(), IntcodeComputerStatus::Paused);
+        assert_eq!(intcode_computer.get_memory_range(15..16), vec![656]);
+
+        intcode_computer.execute_program();
+        intcode_computer.set_input(10);
+        intcode_computer.execute_program();
+
+        // the add at index 8 writes to 15 again
+        assert_eq!(intcode_computer.get_status(), IntcodeComputerStatus::Paused);
+
+        let output = intcode_computer.execute_program();
+
+        assert_eq!(
+            intcode_computer.get_status(),
+            IntcodeComputerStatus::Finished
+        );
+        assert_eq!(output, Some(756));
+    }
+
+    #[test]
+    fn test_breakpoint_pauses_before_instruction() {
+        let mut intcode_computer = IntcodeComputer::new(PROGRAM.as_ref());
+        intcode_computer.add_hook(Breakpoint { index: 12 });
+
+        intcode_computer.set_input(656);
+        intcode_computer.execute_program();
+        intcode_computer.set_input(10);
+        intcode_computer.execute_program();
+
+        assert_eq!(intcode_computer.get_status(), IntcodeComputerStatus::Paused);
+        assert!(intcode_computer.get_outputs().is_empty());
+
+        intcode_computer.execute_program();
+
+        assert_eq!(
+            intcode_computer.get_status(),
+            IntcodeComputerStatus::Finished
+        );
+        assert_eq!(intcode_computer.get_outputs(), vec![756]);
+    }
+
+    #[test]
+    fn test_breakpoint_on_input_instruction_pauses_once() {
+        let mut intcode_computer = IntcodeComputer::new(PROGRAM.as_ref());
+        intcode_computer.add_hook(Breakpoint { index: 0 });
+        let tracer = intcode_computer.add_hook(Tracer::default());
+
+        // waits for input before the hooks see the instruction
+        intcode_computer.execute_program();
+
+        assert_eq!(
+            intcode_computer.get_status(),
+            IntcodeComputerStatus::WaitingForInput
+        );
+
+        intcode_computer.set_input(656);
+        intcode_computer.execute_program();
+
+        assert_eq!(intcode_computer.get_status(), IntcodeComputerStatus::Paused);
+        assert_eq!(intcode_computer.get_memory_range(15..16), vec![566]);
+
+        intcode_computer.execute_program();
+
+        assert_eq!(
+            intcode_computer.get_status(),
+            IntcodeComputerStatus::WaitingForInput
+        );
+        assert_eq!(intcode_computer.get_memory_range(15..16), vec![656]);
+
+        intcode_computer.set_input(10);
+        intcode_computer.execute_program();
+
+        assert_eq!(
+            intcode_computer.get_status(),
+            IntcodeComputerStatus::Finished
+        );
+        assert_eq!(intcode_computer.get_outputs(), vec![756]);
+        assert_eq!(tracer.lock().unwrap().before, vec![0, 2, 4, 8, 12, 14]);
+    }
+
+    #[test]
+    fn test_breakpoint_after_watchpoint_pause() {
+        let mut intcode_computer = IntcodeComputer::new(PROGRAM.as_ref());
+        intcode_computer.add_hook(Watchpoint { address: 15 });
+        intcode_computer.add_hook(Breakpoint { index: 2 });
+
+        intcode_computer.set_input(656);
+        intcode_computer.execute_program();
+        intcode_computer.set_input(10);
+        intcode_computer.execute_program();
+
+        // the next instruction still goes through the before hooks
+        assert_eq!(intcode_computer.get_status(), IntcodeComputerStatus::Paused);
+        assert_eq!(intcode_computer.get_memory_range(16..17), vec![10]);
+
+        intcode_computer.execute_program();
+
+        // and pauses again when the sum is written back to 15
+        assert_eq!(intcode_computer.get_status(), IntcodeComputerStatus::Paused);
+        assert_eq!(intcode_computer.get_memory_range(15..17), vec![756, 100]);
+    }
+
+    #[test]
+    fn test_clear_hooks() {
+        let mut intcode_computer = IntcodeComputer::new(PROGRAM.as_ref());
+        intcode_computer.add_hook(Breakpoint { index: 0 });
+        intcode_computer.clear_hooks();
+
+        intcode_computer.execute_program();
+
+        assert_eq!(
+            intcode_computer.get_status(),
+            IntcodeComputerStatus::WaitingForInput
+        );
+    }
+}
@@ -79,7 +79,7 @@ impl Opcode {
                 Opcode::Multiply(first_parameter, second_parameter, third_parameter)
             }
             3 => {
-                let input_parameter = Parameter::new(1, user_input as i128);
+                let input_parameter = Parameter::new(1, user_input);
 
                 let first_parameter =
                     Parameter::new(first_parameter_mode, program_memory[&plus_one_index]);
@@ -345,6 +345,55 @@ impl Opcode {
         }
     }
 
+    // Returns the length of a well formed instruction, including its parameters.
+    // None if the value can't be decoded as an instruction.
+    pub fn instruction_length(instruction: i128) -> Option<u128> {
+        if instruction.is_negative() {
+            return None;
+        }
+
+        let (number_of_parameters, write_parameter) = match instruction % 100 {
+            1 | 2 | 7 | 8 => (3, Some(3)),
+            3 => (1, Some(1)),
+            4 | 9 => (1, None),
+            5 | 6 => (2, None),
+            99 => (0, None),
+            _ => return None,
+        };
+
+        let mut modes = instruction / 100;
+
+        for parameter in 1..=number_of_parameters {
+            let mode = modes % 10;
+
+            if 2 < mode || (write_parameter == Some(parameter) && mode == 1) {
+                return None;
+            }
+
+            modes /= 10;
+        }
+
+        if modes != 0 {
+            return None;
+        }
+
+        Some(number_of_parameters + 1)
+    }
+
+    // Returns the index this opcode writes to. None if it doesn't write.
+    pub fn get_write_index(&self, base_index: u128) -> Option<u128> {
+        match self {
+            Opcode::Add(_, _, parameter)
+            | Opcode::Multiply(_, _, parameter)
+            | Opcode::SaveInput(_, parameter)
+            | Opcode::StoreIfLessThan(_, _, parameter)
+            | Opcode::StoreIfEquals(_, _, parameter) => {
+                Some(Self::get_save_index(parameter, base_index))
+            }
+            _ => None,
+        }
+    }
+
     fn get_parameter_value_from_memory(
         parameter: &Parameter,
         program_memory: &mut HashMap<u128, i128>,
@@ -772,6 +821,33 @@ mod tests {
         assert_eq!(program_memory, expected_program_memory);
     }
 
+    #[test]
+    fn test_instruction_length() {
+        assert_eq!(Opcode::instruction_length(1002), Some(4));
+        assert_eq!(Opcode::instruction_length(21101), Some(4));
+        assert_eq!(Opcode::instruction_length(1105), Some(3));
+        assert_eq!(Opcode::instruction_length(204), Some(2));
+        assert_eq!(Opcode::instruction_length(99), Some(1));
+        assert_eq!(Opcode::instruction_length(11101), None);
+        assert_eq!(Opcode::instruction_length(103), None);
+        assert_eq!(Opcode::instruction_length(1099), None);
+        assert_eq!(Opcode::instruction_length(-1), None);
+        assert_eq!(Opcode::instruction_length(42), None);
+    }
+
+    #[test]
+    fn test_get_write_index() {
+        let program_memory = slice_to_hashmap(&[22201, 1, 2, 3, 4, 0, 99]);
+        let user_input = 0;
+        let base_index = 10;
+
+        let add_opcode = Opcode::new(user_input, &program_memory, 0);
+        let output_opcode = Opcode::new(user_input, &program_memory, 4);
+
+        assert_eq!(add_opcode.get_write_index(base_index), Some(13));
+        assert_eq!(output_opcode.get_write_index(base_index), None);
+    }
+
     #[test]
     fn test_get_parameter_value_from_memory() {
         let mut program_memory = slice_to_hashmap(&[1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50]);
@@ -0,0 +1,252 @@
+pub mod module;
+pub mod standard_library;
+
+use module::ObjectModule;
+use std::collections::HashMap;
+
+// 109,<stack> 21101,<halt>,0,0 1105,1,<entry> 99
+const PROLOGUE_LENGTH: usize = 10;
+const HALT_ADDRESS: usize = 9;
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Linker {
+    modules: Vec<ObjectModule>,
+}
+
+impl Linker {
+    pub fn new() -> Linker {
+        Linker::default()
+    }
+
+    pub fn add_module(&mut self, module: ObjectModule) {
+        self.modules.push(module);
+    }
+
+    // Lays the modules out after a short prologue that points the relative
+    // base at the stack (right after the program) and calls `entry`, halting
+    // once it returns.
+    pub fn link(&self, entry: &str) -> Result<Vec<i128>, String> {
+        let mut base_addresses = Vec::new();
+        let mut next_address = PROLOGUE_LENGTH;
+
+        for module in &self.modules {
+            base_addresses.push(next_address);
+            next_address += module.get_code().len();
+        }
+
+        let stack_address = next_address;
+        let symbols = self.exported_symbols(&base_addresses)?;
+
+        let entry_address = *symbols
+            .get(entry)
+            .ok_or_else(|| format!("Entry point {} is not exported", entry))?;
+
+        let mut program = vec![
+            109,
+            stack_address as i128,
+            21101,
+            HALT_ADDRESS as i128,
+            0,
+            0,
+            1105,
+            1,
+            entry_address as i128,
+            99,
+        ];
+
+        for (module, &base_address) in self.modules.iter().zip(base_addresses.iter()) {
+            let mut code = module.get_code().to_vec();
+
+            for relocation in module.get_relocations() {
+                let symbol_address = match module.get_label(&relocation.symbol) {
+                    Some(local_address) => base_address + local_address,
+                    None => *symbols.get(&relocation.symbol).ok_or_else(|| {
+                        format!(
+                            "Undefined symbol {} in {}",
+                            relocation.symbol,
+                            module.get_name()
+                        )
+                    })?,
+                };
+
+                code[relocation.index] = symbol_address as i128 + relocation.addend;
+            }
+
+            program.extend(code);
+        }
+
+        Ok(program)
+    }
+
+    fn exported_symbols(&self, base_addresses: &[usize]) -> Result<HashMap<String, usize>, String> {
+        let mut symbols = HashMap::new();
+
+        for (module, &base_address) in self.modules.iter().zip(base_addresses.iter()) {
+            for export in module.get_exports() {
+                let address = base_address + module.get_label(export).unwrap();
+
+                if symbols.insert(export.clone(), address).is_some() {
+                    return Err(format!("Symbol {} is exported more than once", export));
+                }
+            }
+        }
+
+        Ok(symbols)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::module::{ModuleBuilder, Operand};
+    use super::*;
+
+    use crate::intcode_computer::IntcodeComputer;
+
+    fn run(program: &[i128], inputs: &[i128]) -> Vec<i128> {
+        let mut intcode_computer = IntcodeComputer::new(program);
+
+        intcode_computer.execute_program();
+
+        for &input in inputs {
+            intcode_computer.set_input(input);
+            intcode_computer.execute_program();
+        }
+
+        intcode_computer.get_outputs()
+    }
+
+    // reads a number and prints it times three
+    fn main_module() -> ObjectModule {
+        let mut builder = ModuleBuilder::new("main");
+
+        builder.label("main");
+        builder.export("main");
+        builder.input(Operand::relative(1));
+        builder.call("times_three", &[Operand::relative(1)], 2);
+        builder.call("print_number", &[Operand::relative(3)], 2);
+        builder.ret();
+
+        builder.build().unwrap()
+    }
+
+    fn factorial_module() -> ObjectModule {
+        let mut builder = ModuleBuilder::new("factorial");
+
+        builder.label("factorial");
+        builder.export("factorial");
+        builder.jump_if_true(Operand::relative(1), Operand::label_address("recurse"));
+        builder.copy(Operand::immediate(1), Operand::relative(1));
+        builder.ret();
+        builder.label("recurse");
+        builder.add(
+            Operand::relative(1),
+            Operand::immediate(-1),
+            Operand::relative(2),
+        );
+        builder.call("factorial", &[Operand::relative(2)], 3);
+        builder.multiply(
+            Operand::relative(1),
+            Operand::relative(4),
+            Operand::relative(1),
+        );
+        builder.ret();
+
+        builder.build().unwrap()
+    }
+
+    fn ascii(text: &str) -> Vec<i128> {
+        text.bytes().map(i128::from).collect()
+    }
+
+    #[test]
+    fn test_link_with_standard_library() {
+        let mut linker = Linker::new();
+
+        linker.add_module(standard_library::print_number());
+        linker.add_module(main_module());
+        linker.add_module(standard_library::multiply_by_constant("times_three", 3));
+
+        let program = linker.link("main").unwrap();
+
+        assert_eq!(run(&program, &[14]), ascii("42"));
+        assert_eq!(run(&program, &[-7]), ascii("-21"));
+        assert_eq!(run(&program, &[0]), ascii("0"));
+        assert_eq!(run(&program, &[333_333_334]), ascii("1000000002"));
+    }
+
+    #[test]
+    fn test_recursive_calls() {
+        let mut main = ModuleBuilder::new("main");
+
+        main.label("main");
+        main.export("main");
+        main.input(Operand::relative(1));
+        main.call("factorial", &[Operand::relative(1)], 2);
+        main.output(Operand::relative(3));
+        main.ret();
+
+        let mut linker = Linker::new();
+
+        linker.add_module(main.build().unwrap());
+        linker.add_module(factorial_module());
+
+        let program = linker.link("main").unwrap();
+
+        assert_eq!(run(&program, &[5]), vec![120]);
+        assert_eq!(run(&program, &[0]), vec![1]);
+    }
+
+    #[test]
+    fn test_position_mode_relocation() {
+        let mut data = ModuleBuilder::new("data");
+
+        data.label("counter");
+        data.export("counter");
+        data.data(&[41]);
+
+        let mut main = ModuleBuilder::new("main");
+
+        main.label("main");
+        main.export("main");
+        main.add(
+            Operand::at_label("counter"),
+            Operand::immediate(1),
+            Operand::at_label("counter"),
+        );
+        main.output(Operand::at_label("counter"));
+        main.ret();
+
+        let mut linker = Linker::new();
+
+        linker.add_module(data.build().unwrap());
+        linker.add_module(main.build().unwrap());
+
+        let program = linker.link("main").unwrap();
+
+        assert_eq!(program[PROLOGUE_LENGTH], 41);
+        assert_eq!(run(&program, &[]), vec![42]);
+    }
+
+    #[test]
+    fn test_link_errors() {
+        let mut linker = Linker::new();
+
+        linker.add_module(main_module());
+
+        assert_eq!(
+            linker.link("main"),
+            Err(String::from("Undefined symbol times_three in main"))
+        );
+        assert_eq!(
+            linker.link("start"),
+            Err(String::from("Entry point start is not exported"))
+        );
+
+        linker.add_module(main_module());
+
+        assert_eq!(
+            linker.link("main"),
+            Err(String::from("Symbol main is exported more than once"))
+        );
+    }
+}
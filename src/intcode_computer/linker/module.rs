@@ -0,0 +1,379 @@
+use std::collections::HashMap;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Value {
+    Number(i128),
+    // Address of the label plus an offset, filled in by the linker
+    Label(String, i128),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Operand {
+    Immediate(Value),
+    Position(Value),
+    Relative(i128),
+}
+
+impl Operand {
+    pub fn immediate(value: i128) -> Operand {
+        Operand::Immediate(Value::Number(value))
+    }
+
+    pub fn position(address: i128) -> Operand {
+        Operand::Position(Value::Number(address))
+    }
+
+    pub fn relative(offset: i128) -> Operand {
+        Operand::Relative(offset)
+    }
+
+    // The address of the label itself, e.g. for jump targets
+    pub fn label_address(label: &str) -> Operand {
+        Operand::Immediate(Value::Label(String::from(label), 0))
+    }
+
+    // The value stored at the label
+    pub fn at_label(label: &str) -> Operand {
+        Operand::Position(Value::Label(String::from(label), 0))
+    }
+
+    fn mode(&self) -> i128 {
+        match self {
+            Operand::Position(_) => 0,
+            Operand::Immediate(_) => 1,
+            Operand::Relative(_) => 2,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Relocation {
+    pub index: usize,
+    pub symbol: String,
+    pub addend: i128,
+}
+
+// An assembled module. Code addresses start at 0 and are moved by the linker.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ObjectModule {
+    name: String,
+    code: Vec<i128>,
+    labels: HashMap<String, usize>,
+    exports: Vec<String>,
+    relocations: Vec<Relocation>,
+}
+
+impl ObjectModule {
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_code(&self) -> &[i128] {
+        &self.code
+    }
+
+    pub fn get_label(&self, label: &str) -> Option<usize> {
+        self.labels.get(label).copied()
+    }
+
+    pub fn get_exports(&self) -> &[String] {
+        &self.exports
+    }
+
+    pub fn get_relocations(&self) -> &[Relocation] {
+        &self.relocations
+    }
+
+    // Labels used by relocations that this module doesn't define
+    pub fn get_imports(&self) -> Vec<String> {
+        let mut imports: Vec<String> = self
+            .relocations
+            .iter()
+            .filter(|relocation| !self.labels.contains_key(&relocation.symbol))
+            .map(|relocation| relocation.symbol.clone())
+            .collect();
+
+        imports.sort();
+        imports.dedup();
+
+        imports
+    }
+}
+
+// Calling convention: the callee's relative base points at its frame, where
+// [0] holds the return address, [1..] the arguments and the result is left
+// in [1] on return.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ModuleBuilder {
+    name: String,
+    code: Vec<i128>,
+    labels: HashMap<String, usize>,
+    exports: Vec<String>,
+    relocations: Vec<Relocation>,
+    errors: Vec<String>,
+    number_of_generated_labels: usize,
+}
+
+impl ModuleBuilder {
+    pub fn new(name: &str) -> ModuleBuilder {
+        ModuleBuilder {
+            name: String::from(name),
+            code: Vec::new(),
+            labels: HashMap::new(),
+            exports: Vec::new(),
+            relocations: Vec::new(),
+            errors: Vec::new(),
+            number_of_generated_labels: 0,
+        }
+    }
+
+    pub fn label(&mut self, label: &str) {
+        if self.labels.contains_key(label) {
+            self.errors
+                .push(format!("Label {} is defined twice in {}", label, self.name));
+        }
+
+        self.labels.insert(String::from(label), self.code.len());
+    }
+
+    // A label name that is unique within this module
+    pub fn generate_label(&mut self, prefix: &str) -> String {
+        self.number_of_generated_labels += 1;
+
+        format!("__{}_{}", prefix, self.number_of_generated_labels)
+    }
+
+    pub fn export(&mut self, label: &str) {
+        self.exports.push(String::from(label));
+    }
+
+    pub fn data(&mut self, values: &[i128]) {
+        self.code.extend_from_slice(values);
+    }
+
+    pub fn get_current_address(&self) -> usize {
+        self.code.len()
+    }
+
+    pub fn add(&mut self, first: Operand, second: Operand, destination: Operand) {
+        self.instruction(1, &[first, second, destination], Some(2));
+    }
+
+    pub fn multiply(&mut self, first: Operand, second: Operand, destination: Operand) {
+        self.instruction(2, &[first, second, destination], Some(2));
+    }
+
+    pub fn input(&mut self, destination: Operand) {
+        self.instruction(3, &[destination], Some(0));
+    }
+
+    pub fn output(&mut self, value: Operand) {
+        self.instruction(4, &[value], None);
+    }
+
+    pub fn jump_if_true(&mut self, condition: Operand, target: Operand) {
+        self.instruction(5, &[condition, target], None);
+    }
+
+    pub fn jump_if_false(&mut self, condition: Operand, target: Operand) {
+        self.instruction(6, &[condition, target], None);
+    }
+
+    pub fn less_than(&mut self, first: Operand, second: Operand, destination: Operand) {
+        self.instruction(7, &[first, second, destination], Some(2));
+    }
+
+    pub fn equals(&mut self, first: Operand, second: Operand, destination: Operand) {
+        self.instruction(8, &[first, second, destination], Some(2));
+    }
+
+    pub fn adjust_relative_base(&mut self, value: Operand) {
+        self.instruction(9, &[value], None);
+    }
+
+    pub fn halt(&mut self) {
+        self.instruction(99, &[], None);
+    }
+
+    pub fn copy(&mut self, source: Operand, destination: Operand) {
+        self.add(source, Operand::immediate(0), destination);
+    }
+
+    pub fn jump(&mut self, target: Operand) {
+        self.jump_if_true(Operand::immediate(1), target);
+    }
+
+    // `frame_size` is the size of the caller's frame. The callee's frame
+    // starts right after it, so the result ends up in Relative(frame_size + 1).
+    pub fn call(&mut self, function: &str, arguments: &[Operand], frame_size: i128) {
+        let return_label = self.generate_label("return");
+
+        self.copy(
+            Operand::label_address(&return_label),
+            Operand::relative(frame_size),
+        );
+
+        for (offset, argument) in arguments.iter().enumerate() {
+            self.copy(
+                argument.clone(),
+                Operand::relative(frame_size + 1 + offset as i128),
+            );
+        }
+
+        self.adjust_relative_base(Operand::immediate(frame_size));
+        self.jump(Operand::label_address(function));
+        self.label(&return_label);
+        self.adjust_relative_base(Operand::immediate(-frame_size));
+    }
+
+    pub fn ret(&mut self) {
+        self.jump_if_false(Operand::immediate(0), Operand::relative(0));
+    }
+
+    pub fn build(self) -> Result<ObjectModule, String> {
+        let mut errors = self.errors;
+
+        for export in &self.exports {
+            if !self.labels.contains_key(export) {
+                errors.push(format!(
+                    "Exported label {} is not defined in {}",
+                    export, self.name
+                ));
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(errors.join("\n"));
+        }
+
+        Ok(ObjectModule {
+            name: self.name,
+            code: self.code,
+            labels: self.labels,
+            exports: self.exports,
+            relocations: self.relocations,
+        })
+    }
+
+    fn instruction(&mut self, opcode: i128, operands: &[Operand], write_operand: Option<usize>) {
+        if let Some(Operand::Immediate(_)) = write_operand.and_then(|index| operands.get(index)) {
+            self.errors.push(format!(
+                "Cannot save a value with an immediate parameter at {} in {}",
+                self.code.len(),
+                self.name
+            ));
+        }
+
+        let modes = operands
+            .iter()
+            .rev()
+            .fold(0, |modes, operand| modes * 10 + operand.mode());
+
+        self.code.push(modes * 100 + opcode);
+
+        for operand in operands {
+            let value = match operand {
+                Operand::Immediate(value) | Operand::Position(value) => value.clone(),
+                Operand::Relative(offset) => Value::Number(*offset),
+            };
+
+            match value {
+                Value::Number(number) => self.code.push(number),
+                Value::Label(symbol, addend) => {
+                    self.relocations.push(Relocation {
+                        index: self.code.len(),
+                        symbol,
+                        addend,
+                    });
+                    self.code.push(0);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_instruction_modes() {
+        let mut builder = ModuleBuilder::new("test");
+
+        builder.add(
+            Operand::relative(1),
+            Operand::immediate(-3),
+            Operand::relative(2),
+        );
+        builder.multiply(
+            Operand::position(4),
+            Operand::immediate(3),
+            Operand::position(4),
+        );
+        builder.halt();
+
+        let expected = vec![21201, 1, -3, 2, 1002, 4, 3, 4, 99];
+
+        let result = builder.build().unwrap();
+
+        assert_eq!(result.get_code(), expected.as_slice());
+    }
+
+    #[test]
+    fn test_labels_become_relocations() {
+        let mut builder = ModuleBuilder::new("test");
+
+        builder.label("start");
+        builder.output(Operand::at_label("value"));
+        builder.jump(Operand::label_address("start"));
+        builder.label("value");
+        builder.data(&[42]);
+        builder.export("start");
+
+        let result = builder.build().unwrap();
+
+        let expected_relocations = vec![
+            Relocation {
+                index: 1,
+                symbol: String::from("value"),
+                addend: 0,
+            },
+            Relocation {
+                index: 4,
+                symbol: String::from("start"),
+                addend: 0,
+            },
+        ];
+
+        assert_eq!(result.get_code(), &[4, 0, 1105, 1, 0, 42]);
+        assert_eq!(result.get_relocations(), expected_relocations.as_slice());
+        assert_eq!(result.get_label("value"), Some(5));
+        assert!(result.get_imports().is_empty());
+    }
+
+    #[test]
+    fn test_imports() {
+        let mut builder = ModuleBuilder::new("test");
+
+        builder.call("print_number", &[Operand::immediate(7)], 1);
+        builder.halt();
+
+        let result = builder.build().unwrap();
+
+        assert_eq!(result.get_imports(), vec![String::from("print_number")]);
+    }
+
+    #[test]
+    fn test_build_errors() {
+        let mut builder = ModuleBuilder::new("test");
+
+        builder.label("start");
+        builder.label("start");
+        builder.input(Operand::immediate(3));
+        builder.export("missing");
+
+        let result = builder.build();
+
+        assert_eq!(result.unwrap_err().lines().count(), 3);
+    }
+}
@@ -0,0 +1,82 @@
+use super::module::{ModuleBuilder, ObjectModule, Operand};
+
+// Largest power of ten print_number handles, so values up to 10^19 - 1
+const LARGEST_POWER_OF_TEN: u32 = 18;
+
+// A function that returns its first argument times `constant`.
+pub fn multiply_by_constant(name: &str, constant: i128) -> ObjectModule {
+    let mut builder = ModuleBuilder::new(name);
+
+    builder.label(name);
+    builder.export(name);
+    builder.multiply(
+        Operand::relative(1),
+        Operand::immediate(constant),
+        Operand::relative(1),
+    );
+    builder.ret();
+
+    builder.build().unwrap()
+}
+
+// Exports print_number, which outputs its first argument as ASCII decimal
+// digits. There is no division, so each digit is found by subtracting powers
+// of ten.
+pub fn print_number() -> ObjectModule {
+    let mut builder = ModuleBuilder::new("print_number");
+
+    let number = Operand::relative(1);
+    let has_started = Operand::relative(2);
+    let digit = Operand::relative(3);
+    let scratch = Operand::relative(4);
+
+    builder.label("print_number");
+    builder.export("print_number");
+
+    let positive = builder.generate_label("positive");
+
+    builder.less_than(number.clone(), Operand::immediate(0), scratch.clone());
+    builder.jump_if_false(scratch.clone(), Operand::label_address(&positive));
+    builder.output(Operand::immediate(i128::from(b'-')));
+    builder.multiply(number.clone(), Operand::immediate(-1), number.clone());
+    builder.label(&positive);
+    builder.copy(Operand::immediate(0), has_started.clone());
+
+    for exponent in (1..=LARGEST_POWER_OF_TEN).rev() {
+        let power = 10_i128.pow(exponent);
+        let subtract = builder.generate_label("subtract");
+        let digit_found = builder.generate_label("digit_found");
+        let skip = builder.generate_label("skip");
+
+        builder.copy(Operand::immediate(0), digit.clone());
+        builder.label(&subtract);
+        builder.less_than(number.clone(), Operand::immediate(power), scratch.clone());
+        builder.jump_if_true(scratch.clone(), Operand::label_address(&digit_found));
+        builder.add(number.clone(), Operand::immediate(-power), number.clone());
+        builder.add(digit.clone(), Operand::immediate(1), digit.clone());
+        builder.jump(Operand::label_address(&subtract));
+        builder.label(&digit_found);
+
+        // leading zeros are skipped
+        builder.add(has_started.clone(), digit.clone(), scratch.clone());
+        builder.jump_if_false(scratch.clone(), Operand::label_address(&skip));
+        builder.add(
+            digit.clone(),
+            Operand::immediate(i128::from(b'0')),
+            scratch.clone(),
+        );
+        builder.output(scratch.clone());
+        builder.copy(Operand::immediate(1), has_started.clone());
+        builder.label(&skip);
+    }
+
+    builder.add(
+        number,
+        Operand::immediate(i128::from(b'0')),
+        scratch.clone(),
+    );
+    builder.output(scratch);
+    builder.ret();
+
+    builder.build().unwrap()
+}
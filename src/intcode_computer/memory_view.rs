@@ -0,0 +1,162 @@
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum DumpFormat {
+    Decimal,
+    Hexadecimal,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct MemoryDifference {
+    pub address: u128,
+    pub before: i128,
+    pub after: i128,
+}
+
+pub fn sorted_memory(memory: &HashMap<u128, i128>) -> Vec<(u128, i128)> {
+    let mut sorted: Vec<(u128, i128)> = memory
+        .iter()
+        .map(|(&address, &value)| (address, value))
+        .collect();
+
+    sorted.sort_unstable();
+
+    sorted
+}
+
+// Addresses missing from either memory count as 0, same as when the program
+// reads them.
+pub fn memory_differences(
+    before: &HashMap<u128, i128>,
+    after: &HashMap<u128, i128>,
+) -> Vec<MemoryDifference> {
+    let mut addresses: Vec<u128> = before.keys().chain(after.keys()).copied().collect();
+
+    addresses.sort_unstable();
+    addresses.dedup();
+
+    addresses
+        .into_iter()
+        .map(|address| MemoryDifference {
+            address,
+            before: *before.get(&address).unwrap_or(&0),
+            after: *after.get(&address).unwrap_or(&0),
+        })
+        .filter(|difference| difference.before != difference.after)
+        .collect()
+}
+
+// Lays the memory out in rows of `values_per_row`, each prefixed with the
+// address of its first column. Rows with nothing stored are skipped.
+pub fn format_memory_dump(
+    memory: &HashMap<u128, i128>,
+    format: DumpFormat,
+    values_per_row: usize,
+) -> String {
+    let values_per_row = values_per_row.max(1) as u128;
+
+    let mut rows: BTreeMap<u128, Vec<Option<i128>>> = BTreeMap::new();
+
+    for (&address, &value) in memory {
+        let row = rows
+            .entry(address / values_per_row)
+            .or_insert_with(|| vec![None; values_per_row as usize]);
+
+        row[(address % values_per_row) as usize] = Some(value);
+    }
+
+    let last_address = memory.keys().max().copied().unwrap_or(0);
+    let address_width = format_value(last_address as i128, format).len();
+    let value_width = memory
+        .values()
+        .map(|&value| format_value(value, format).len())
+        .max()
+        .unwrap_or(1);
+
+    rows.iter()
+        .map(|(row, values)| {
+            let cells: Vec<String> = values
+                .iter()
+                .map(|value| match value {
+                    Some(value) => format!(
+                        "{:>width$}",
+                        format_value(*value, format),
+                        width = value_width
+                    ),
+                    None => format!("{:>width$}", ".", width = value_width),
+                })
+                .collect();
+
+            format!(
+                "{:>width$}: {}",
+                format_value((row * values_per_row) as i128, format),
+                cells.join(" "),
+                width = address_width
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn format_value(value: i128, format: DumpFormat) -> String {
+    match format {
+        DumpFormat::Decimal => value.to_string(),
+        DumpFormat::Hexadecimal if value.is_negative() => format!("-{:x}", value.unsigned_abs()),
+        DumpFormat::Hexadecimal => format!("{:x}", value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::intcode_computer::slice_to_hashmap;
+
+    #[test]
+    fn test_memory_differences() {
+        let before = slice_to_hashmap(&[1, 2, 3]);
+        let mut after = slice_to_hashmap(&[1, 5, 3]);
+        after.insert(10, 0);
+        after.insert(11, 7);
+
+        let expected = vec![
+            MemoryDifference {
+                address: 1,
+                before: 2,
+                after: 5,
+            },
+            MemoryDifference {
+                address: 11,
+                before: 0,
+                after: 7,
+            },
+        ];
+
+        let result = memory_differences(&before, &after);
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_format_memory_dump_decimal() {
+        let memory = slice_to_hashmap(&[1, 9, 10, 3, 2, 3, 11, 0, 99, 30]);
+
+        let expected = String::from("0:  1  9 10  3\n4:  2  3 11  0\n8: 99 30  .  .");
+
+        let result = format_memory_dump(&memory, DumpFormat::Decimal, 4);
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_format_memory_dump_hexadecimal_sparse() {
+        let mut memory = slice_to_hashmap(&[255, -1]);
+        memory.insert(32, 16);
+
+        let expected = String::from(" 0: ff -1  .  .\n20: 10  .  .  .");
+
+        let result = format_memory_dump(&memory, DumpFormat::Hexadecimal, 4);
+
+        assert_eq!(result, expected);
+    }
+}
@@ -1,12 +1,31 @@
+pub mod compiler;
+pub mod constraint_solver;
+pub mod coverage;
+pub mod hooks;
 pub mod intcode_instruction;
-
+pub mod linker;
+pub mod memory_view;
+pub mod network;
+pub mod optimizer;
+pub mod parameter_search;
+pub mod self_modification;
+pub mod session;
+pub mod symbolic_executor;
+
+use coverage::Coverage;
+use hooks::{ExecutionHook, ExecutionHooks, HookAction, InstructionEvent, MemoryMutation};
 use intcode_instruction::Opcode;
-use std::collections::HashMap;
+use memory_view::{DumpFormat, MemoryDifference};
+use self_modification::SelfModificationDetector;
+use std::collections::{BTreeSet, HashMap};
+use std::ops::Range;
+use std::sync::{Arc, Mutex};
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum IntcodeComputerStatus {
     NotStarted,
     WaitingForInput,
+    Paused,
     Finished,
 }
 
@@ -20,6 +39,11 @@ pub struct IntcodeComputer {
     output_cache: Vec<i128>,
     outputs: Vec<i128>,
     original_program: HashMap<u128, i128>,
+    written_addresses: BTreeSet<u128>,
+    coverage: Option<Coverage>,
+    hooks: ExecutionHooks,
+    // The before hooks already paused on the current instruction
+    paused_before_instruction: bool,
 }
 
 impl IntcodeComputer {
@@ -35,28 +59,66 @@ impl IntcodeComputer {
         self.current_status = IntcodeComputerStatus::WaitingForInput;
         self.output_cache = Vec::new();
 
-        let mut opcode = Opcode::new(
-            self.current_input.unwrap_or(0),
-            &self.current_program,
-            self.current_index,
-        );
+        loop {
+            let opcode = Opcode::new(
+                self.current_input.unwrap_or(0),
+                &self.current_program,
+                self.current_index,
+            );
+
+            if let Opcode::SaveInput(_, _) = opcode {
+                if self.current_input.is_none() {
+                    return output;
+                }
+            }
+
+            // a paused instruction already went through the before hooks, even
+            // if it then had to wait for input
+            if !self.paused_before_instruction
+                && self.run_hooks(&opcode, None, None, true) == HookAction::Pause
+            {
+                self.current_status = IntcodeComputerStatus::Paused;
+                self.paused_before_instruction = true;
 
-        if let Opcode::SaveInput(_, _) = opcode {
-            if self.current_input.is_none() {
                 return output;
             }
-        }
 
-        while let Some((opcode_execution_result, next_index)) = opcode.execute(
-            &mut self.current_program,
-            self.current_index,
-            self.current_base_index,
-        ) {
-            let next_opcode = Opcode::new(
-                self.current_input.unwrap_or(0),
-                &self.current_program,
-                next_index,
-            );
+            let write_index = opcode.get_write_index(self.current_base_index);
+            let old_value =
+                write_index.map(|index| *self.current_program.get(&index).unwrap_or(&0));
+
+            let (opcode_execution_result, next_index) = match opcode.execute(
+                &mut self.current_program,
+                self.current_index,
+                self.current_base_index,
+            ) {
+                Some(execution) => execution,
+                None => break,
+            };
+
+            self.paused_before_instruction = false;
+
+            if let Some(write_index) = write_index {
+                self.written_addresses.insert(write_index);
+            }
+
+            if let Some(coverage) = self.coverage.as_mut() {
+                coverage.record_instruction(self.current_index);
+
+                if let Opcode::JumpIfTrue(_, _) | Opcode::JumpIfFalse(_, _) = opcode {
+                    coverage.record_branch(self.current_index, opcode_execution_result == 1);
+                }
+            }
+
+            let mutation = write_index.map(|address| MemoryMutation {
+                address,
+                old_value: old_value.unwrap_or(0),
+                new_value: self.current_program[&address],
+            });
+
+            let hook_action =
+                self.run_hooks(&opcode, Some(opcode_execution_result), mutation, false);
+
             self.current_index = next_index;
 
             match opcode {
@@ -75,20 +137,51 @@ impl IntcodeComputer {
                 _ => {}
             }
 
-            if let Opcode::SaveInput(_, _) = next_opcode {
-                if self.current_input.is_none() {
-                    return output;
-                }
+            if hook_action == HookAction::Pause {
+                self.current_status = IntcodeComputerStatus::Paused;
+
+                return output;
             }
+        }
+
+        if let Some(coverage) = self.coverage.as_mut() {
+            coverage.record_instruction(self.current_index);
+        }
 
-            opcode = next_opcode;
+        if !self.hooks.is_empty() {
+            let opcode = Opcode::Terminate;
+
+            self.run_hooks(&opcode, None, None, false);
         }
 
         self.current_status = IntcodeComputerStatus::Finished;
+        self.paused_before_instruction = false;
 
         output
     }
 
+    // Hooks are kept through resets and shared with clones. The returned
+    // handle gives access to the hook's state.
+    pub fn add_hook<H>(&mut self, hook: H) -> Arc<Mutex<H>>
+    where
+        H: ExecutionHook + Send + 'static,
+    {
+        let shared_hook = Arc::new(Mutex::new(hook));
+
+        self.hooks.add(shared_hook.clone());
+
+        shared_hook
+    }
+
+    // Runs as a hook, so it's shared with clones and removed by clear_hooks.
+    pub fn detect_self_modification(&mut self) -> Arc<Mutex<SelfModificationDetector>> {
+        self.add_hook(SelfModificationDetector::new())
+    }
+
+    pub fn clear_hooks(&mut self) {
+        self.hooks.clear();
+    }
+
     pub fn set_input(&mut self, input: i128) {
         self.current_input = Some(input);
     }
@@ -111,28 +204,62 @@ impl IntcodeComputer {
         self.current_base_index = 0;
         self.output_cache = Vec::new();
         self.outputs = Vec::new();
+        self.written_addresses.clear();
+        self.paused_before_instruction = false;
     }
 
     pub fn get_current_memory(&self) -> HashMap<u128, i128> {
         self.current_program.clone()
     }
 
+    // Coverage is kept through resets, so it accumulates over several runs.
+    pub fn enable_coverage(&mut self) {
+        if self.coverage.is_none() {
+            self.coverage = Some(Coverage::new(&self.original_program));
+        }
+    }
+
+    pub fn get_coverage(&self) -> Option<&Coverage> {
+        self.coverage.as_ref()
+    }
+
+    pub fn get_memory_range(&self, range: Range<u128>) -> Vec<i128> {
+        range
+            .map(|index| *self.current_program.get(&index).unwrap_or(&0))
+            .collect()
+    }
+
+    pub fn get_sorted_memory(&self) -> Vec<(u128, i128)> {
+        memory_view::sorted_memory(&self.current_program)
+    }
+
+    pub fn get_memory_differences(&self, other: &IntcodeComputer) -> Vec<MemoryDifference> {
+        memory_view::memory_differences(&self.current_program, &other.current_program)
+    }
+
+    pub fn get_memory_differences_from_original(&self) -> Vec<MemoryDifference> {
+        memory_view::memory_differences(&self.original_program, &self.current_program)
+    }
+
+    // Addresses written by the program since it was created or last reset
+    pub fn get_written_addresses(&self) -> Vec<u128> {
+        self.written_addresses.iter().copied().collect()
+    }
+
+    pub fn format_memory(&self, format: DumpFormat, values_per_row: usize) -> String {
+        memory_view::format_memory_dump(&self.current_program, format, values_per_row)
+    }
+
     pub fn get_status(&self) -> IntcodeComputerStatus {
         self.current_status.clone()
     }
 
     pub fn get_latest_output(&self) -> Option<i128> {
-        self.outputs.last().map(|&x| x)
+        self.outputs.last().copied()
     }
 
     pub fn get_last_n_outputs(&self, n: usize) -> Vec<i128> {
-        self.outputs
-            .iter()
-            .rev()
-            .take(n)
-            .map(|&x| x)
-            .rev()
-            .collect()
+        self.outputs.iter().rev().take(n).copied().rev().collect()
     }
 
     pub fn get_outputs(&self) -> Vec<i128> {
@@ -146,6 +273,33 @@ impl IntcodeComputer {
     pub fn increment_index(&mut self, step_size: u128) {
         self.current_index += step_size;
     }
+
+    fn run_hooks(
+        &self,
+        opcode: &Opcode,
+        result: Option<i128>,
+        mutation: Option<MemoryMutation>,
+        is_before: bool,
+    ) -> HookAction {
+        if self.hooks.is_empty() {
+            return HookAction::Continue;
+        }
+
+        let event = InstructionEvent {
+            index: self.current_index,
+            base_index: self.current_base_index,
+            opcode,
+            result,
+            mutation,
+            memory: &self.current_program,
+        };
+
+        if is_before {
+            self.hooks.before_instruction(&event)
+        } else {
+            self.hooks.after_instruction(&event)
+        }
+    }
 }
 
 impl From<&[i128]> for IntcodeComputer {
@@ -159,6 +313,10 @@ impl From<&[i128]> for IntcodeComputer {
             output_cache: Vec::new(),
             outputs: Vec::new(),
             original_program: slice_to_hashmap(a),
+            written_addresses: BTreeSet::new(),
+            coverage: None,
+            hooks: ExecutionHooks::default(),
+            paused_before_instruction: false,
         }
     }
 }
@@ -217,6 +375,10 @@ mod tests {
             output_cache: Vec::new(),
             outputs: Vec::new(),
             original_program: slice_to_hashmap(&PROGRAM),
+            written_addresses: BTreeSet::new(),
+            coverage: None,
+            hooks: ExecutionHooks::default(),
+            paused_before_instruction: false,
         };
 
         let result = IntcodeComputer::new(PROGRAM.to_vec().as_slice());
@@ -330,6 +492,10 @@ mod tests {
             output_cache: Vec::new(),
             outputs: Vec::new(),
             original_program: slice_to_hashmap(&PROGRAM.to_vec()),
+            written_addresses: vec![15].into_iter().collect(),
+            coverage: None,
+            hooks: ExecutionHooks::default(),
+            paused_before_instruction: false,
         };
 
         // waits at first input
@@ -365,6 +531,10 @@ mod tests {
             output_cache: vec![756],
             outputs: vec![756],
             original_program: slice_to_hashmap(&PROGRAM.to_vec()),
+            written_addresses: vec![15, 16].into_iter().collect(),
+            coverage: None,
+            hooks: ExecutionHooks::default(),
+            paused_before_instruction: false,
         };
         let expected_result = Some(756);
 
@@ -430,4 +600,61 @@ mod tests {
 
         assert_eq!(intcode_computer, expected);
     }
+
+    #[test]
+    fn test_get_memory_range() {
+        let intcode_computer = IntcodeComputer::new(PROGRAM.to_vec().as_slice());
+
+        let expected = vec![566, 10, 0, 0];
+
+        let result = intcode_computer.get_memory_range(15..19);
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_get_memory_differences_from_original() {
+        let mut intcode_computer = IntcodeComputer::new(PROGRAM.to_vec().as_slice());
+
+        intcode_computer.execute_program();
+        intcode_computer.set_input(656);
+        intcode_computer.execute_program();
+        intcode_computer.set_input(10);
+        intcode_computer.execute_program();
+
+        let expected = vec![
+            MemoryDifference {
+                address: 15,
+                before: 566,
+                after: 756,
+            },
+            MemoryDifference {
+                address: 16,
+                before: 10,
+                after: 100,
+            },
+        ];
+
+        let result = intcode_computer.get_memory_differences_from_original();
+
+        assert_eq!(result, expected);
+        assert_eq!(intcode_computer.get_written_addresses(), vec![15, 16]);
+    }
+
+    #[test]
+    fn test_written_addresses_include_unchanged_writes() {
+        // writes 0 over the 0 at index 5
+        let mut intcode_computer = IntcodeComputer::new(vec![1101, 0, 0, 5, 99, 0].as_slice());
+
+        intcode_computer.execute_program();
+
+        assert!(intcode_computer
+            .get_memory_differences_from_original()
+            .is_empty());
+        assert_eq!(intcode_computer.get_written_addresses(), vec![5]);
+
+        intcode_computer.reset();
+
+        assert!(intcode_computer.get_written_addresses().is_empty());
+    }
 }
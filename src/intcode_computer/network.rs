@@ -0,0 +1,475 @@
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use super::{IntcodeComputer, IntcodeComputerStatus};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Packet {
+    pub destination: i128,
+    pub x: i128,
+    pub y: i128,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct LoggedPacket {
+    pub round: usize,
+    pub source: i128,
+    pub packet: Packet,
+}
+
+pub trait NetworkMonitor {
+    fn receive(&mut self, packet: &Packet);
+
+    // Called once the network has been idle for long enough. The returned
+    // packets are sent from the monitor's address.
+    fn on_idle(&mut self) -> Vec<Packet> {
+        Vec::new()
+    }
+
+    // The network stops running once this is true.
+    fn is_finished(&self) -> bool {
+        false
+    }
+}
+
+type SharedMonitor = Arc<Mutex<dyn NetworkMonitor + Send>>;
+
+// Remembers the last packet it received and sends it to address 0 whenever
+// the network is idle. Finished once it sends the same Y twice in a row.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Nat {
+    received: Vec<Packet>,
+    sent: Vec<Packet>,
+}
+
+impl Nat {
+    pub fn new() -> Nat {
+        Nat::default()
+    }
+
+    pub fn get_received(&self) -> &[Packet] {
+        &self.received
+    }
+
+    pub fn get_sent(&self) -> &[Packet] {
+        &self.sent
+    }
+
+    pub fn get_repeated_y(&self) -> Option<i128> {
+        match self.sent.as_slice() {
+            [.., first, second] if first.y == second.y => Some(second.y),
+            _ => None,
+        }
+    }
+}
+
+impl NetworkMonitor for Nat {
+    fn receive(&mut self, packet: &Packet) {
+        self.received.push(*packet);
+    }
+
+    fn on_idle(&mut self) -> Vec<Packet> {
+        match self.received.last() {
+            Some(packet) => {
+                let packet = Packet {
+                    destination: 0,
+                    x: packet.x,
+                    y: packet.y,
+                };
+
+                self.sent.push(packet);
+
+                vec![packet]
+            }
+            None => Vec::new(),
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.get_repeated_y().is_some()
+    }
+}
+
+// Computers take turns in address order. On its turn a computer reads
+// everything queued for it, or a single -1 if nothing is, and runs until it
+// asks for more. Packets are queued as soon as they're sent, so the same
+// program always produces the same traffic.
+pub struct Network {
+    computers: Vec<IntcodeComputer>,
+    queues: Vec<VecDeque<i128>>,
+    // outputs that don't make a whole packet yet
+    partial_packets: Vec<Vec<i128>>,
+    monitor: Option<(i128, SharedMonitor)>,
+    packet_log: Option<Vec<LoggedPacket>>,
+    round: usize,
+    idle_rounds: usize,
+    rounds_until_idle: usize,
+}
+
+impl Network {
+    pub fn new<A>(args: A, number_of_computers: usize) -> Network
+    where
+        A: Into<IntcodeComputer>,
+    {
+        let computer = args.into();
+
+        Network {
+            computers: vec![computer; number_of_computers],
+            queues: (0..number_of_computers)
+                .map(|address| vec![address as i128].into_iter().collect())
+                .collect(),
+            partial_packets: vec![Vec::new(); number_of_computers],
+            monitor: None,
+            packet_log: None,
+            round: 0,
+            idle_rounds: 0,
+            rounds_until_idle: 2,
+        }
+    }
+
+    // Packets sent to `address` go to the monitor instead of a computer.
+    pub fn set_monitor<M>(&mut self, address: i128, monitor: M) -> Arc<Mutex<M>>
+    where
+        M: NetworkMonitor + Send + 'static,
+    {
+        let shared_monitor = Arc::new(Mutex::new(monitor));
+
+        self.monitor = Some((address, shared_monitor.clone()));
+
+        shared_monitor
+    }
+
+    // Number of rounds in a row without any traffic before the network counts
+    // as idle. Computers may do some work on -1 before sending again.
+    pub fn set_rounds_until_idle(&mut self, rounds: usize) {
+        self.rounds_until_idle = rounds.max(1);
+    }
+
+    pub fn enable_packet_log(&mut self) {
+        if self.packet_log.is_none() {
+            self.packet_log = Some(Vec::new());
+        }
+    }
+
+    pub fn get_packet_log(&self) -> &[LoggedPacket] {
+        self.packet_log.as_deref().unwrap_or(&[])
+    }
+
+    pub fn get_round(&self) -> usize {
+        self.round
+    }
+
+    pub fn get_computer(&self, address: usize) -> Option<&IntcodeComputer> {
+        self.computers.get(address)
+    }
+
+    pub fn is_idle(&self) -> bool {
+        self.idle_rounds >= self.rounds_until_idle
+    }
+
+    // Sends a packet from outside the network, logged with source -1.
+    pub fn send(&mut self, packet: Packet) {
+        self.route(-1, packet);
+    }
+
+    // Runs one round and returns whether it had no traffic at all.
+    pub fn run_round(&mut self) -> bool {
+        let mut had_traffic = self.queues.iter().any(|queue| !queue.is_empty());
+
+        for address in 0..self.computers.len() {
+            if self.computers[address].get_status() == IntcodeComputerStatus::Finished {
+                continue;
+            }
+
+            let inputs: Vec<i128> = if self.queues[address].is_empty() {
+                vec![-1]
+            } else {
+                self.queues[address].drain(..).collect()
+            };
+
+            let mut outputs = Vec::new();
+
+            if self.computers[address].get_status() == IntcodeComputerStatus::NotStarted {
+                self.computers[address].execute_program();
+                outputs.extend(self.computers[address].get_output_cache());
+            }
+
+            for input in inputs {
+                self.computers[address].set_input(input);
+                self.computers[address].execute_program();
+                outputs.extend(self.computers[address].get_output_cache());
+            }
+
+            had_traffic |= !outputs.is_empty();
+
+            self.partial_packets[address].extend(outputs);
+
+            while self.partial_packets[address].len() >= 3 {
+                let values: Vec<i128> = self.partial_packets[address].drain(..3).collect();
+
+                self.route(
+                    address as i128,
+                    Packet {
+                        destination: values[0],
+                        x: values[1],
+                        y: values[2],
+                    },
+                );
+            }
+        }
+
+        self.round += 1;
+
+        if had_traffic {
+            self.idle_rounds = 0;
+        } else {
+            self.idle_rounds += 1;
+        }
+
+        !had_traffic
+    }
+
+    // Runs until the monitor is finished, every computer halted or, without
+    // a monitor to wake it up, the network goes idle. Returns the number of
+    // rounds run in total.
+    pub fn run(&mut self, max_rounds: usize) -> Result<usize, String> {
+        while self.round < max_rounds {
+            self.run_round();
+
+            if self.is_monitor_finished() || self.are_all_finished() {
+                return Ok(self.round);
+            }
+
+            if self.is_idle() {
+                let (address, monitor) = match &self.monitor {
+                    Some((address, monitor)) => (*address, monitor.clone()),
+                    None => return Ok(self.round),
+                };
+
+                let packets = monitor.lock().unwrap().on_idle();
+
+                for packet in packets {
+                    self.route(address, packet);
+                }
+
+                if self.is_monitor_finished() {
+                    return Ok(self.round);
+                }
+
+                self.idle_rounds = 0;
+            }
+        }
+
+        Err(format!("Network still running after {} rounds", max_rounds))
+    }
+
+    // Packets for addresses without a computer or monitor are dropped.
+    fn route(&mut self, source: i128, packet: Packet) {
+        if let Some(packet_log) = self.packet_log.as_mut() {
+            packet_log.push(LoggedPacket {
+                round: self.round,
+                source,
+                packet,
+            });
+        }
+
+        match &self.monitor {
+            Some((address, monitor)) if *address == packet.destination => {
+                monitor.lock().unwrap().receive(&packet);
+            }
+            _ => {
+                let queue = usize::try_from(packet.destination)
+                    .ok()
+                    .and_then(|destination| self.queues.get_mut(destination));
+
+                if let Some(queue) = queue {
+                    queue.push_back(packet.x);
+                    queue.push_back(packet.y);
+                }
+            }
+        }
+    }
+
+    fn is_monitor_finished(&self) -> bool {
+        match &self.monitor {
+            Some((_, monitor)) => monitor.lock().unwrap().is_finished(),
+            None => false,
+        }
+    }
+
+    fn are_all_finished(&self) -> bool {
+        self.computers
+            .iter()
+            .all(|computer| computer.get_status() == IntcodeComputerStatus::Finished)
+    }
+}
+
+impl fmt::Debug for Network {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Network")
+            .field("computers", &self.computers.len())
+            .field("queues", &self.queues)
+            .field("round", &self.round)
+            .field("idle_rounds", &self.idle_rounds)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::intcode_computer::compiler::compile;
+
+    // Computer 0 starts a packet along the chain 0 -> 1 -> 2 -> 3 -> 255,
+    // each computer adding one to X and its address to Y.
+    const CHAIN: &str = "
+        fn main() {
+            let address = read();
+            if address == 0 { print(1); print(0); print(0); }
+            while 1 {
+                let x = read();
+                if x != -1 {
+                    let y = read();
+                    if address == 3 { print(255); } else { print(address + 1); }
+                    print(x + 1);
+                    print(y + address);
+                }
+            }
+        }";
+
+    // Computer 0 sends (1, 5) to 255 and answers every packet it gets with
+    // (X + 1, 7). Every other computer only listens.
+    const ECHO: &str = "
+        fn main() {
+            let address = read();
+            if address == 0 { print(255); print(1); print(5); }
+            while 1 {
+                let x = read();
+                if x != -1 {
+                    read();
+                    print(255); print(x + 1); print(7);
+                }
+            }
+        }";
+
+    #[derive(Default)]
+    struct FirstPacket {
+        packet: Option<Packet>,
+    }
+
+    impl NetworkMonitor for FirstPacket {
+        fn receive(&mut self, packet: &Packet) {
+            self.packet.get_or_insert(*packet);
+        }
+
+        fn is_finished(&self) -> bool {
+            self.packet.is_some()
+        }
+    }
+
+    fn network(source: &str, number_of_computers: usize) -> Network {
+        let program = compile(source).unwrap();
+
+        Network::new(program.as_slice(), number_of_computers)
+    }
+
+    #[test]
+    fn test_packets_are_routed() {
+        let mut network = network(CHAIN, 4);
+        let monitor = network.set_monitor(255, FirstPacket::default());
+
+        network.enable_packet_log();
+
+        let expected_packet = Packet {
+            destination: 255,
+            x: 3,
+            y: 6,
+        };
+        let expected_sources = vec![0, 1, 2, 3];
+
+        let result = network.run(100);
+
+        let sources: Vec<i128> = network
+            .get_packet_log()
+            .iter()
+            .map(|logged| logged.source)
+            .collect();
+
+        assert_eq!(result, Ok(1));
+        assert_eq!(monitor.lock().unwrap().packet, Some(expected_packet));
+        assert_eq!(sources, expected_sources);
+    }
+
+    #[test]
+    fn test_nat_wakes_idle_network() {
+        let mut network = network(ECHO, 3);
+        let nat = network.set_monitor(255, Nat::new());
+
+        network.run(100).unwrap();
+
+        let nat = nat.lock().unwrap();
+
+        let sent_y: Vec<i128> = nat.get_sent().iter().map(|packet| packet.y).collect();
+
+        assert_eq!(nat.get_received()[0].y, 5);
+        assert_eq!(sent_y, vec![5, 7, 7]);
+        assert_eq!(nat.get_repeated_y(), Some(7));
+    }
+
+    #[test]
+    fn test_idle_without_monitor() {
+        let mut network = network(CHAIN, 2);
+
+        network.enable_packet_log();
+
+        let result = network.run(100);
+
+        // the packet for 2 is dropped
+        assert_eq!(result, Ok(3));
+        assert!(network.is_idle());
+        assert_eq!(network.get_packet_log()[1].packet.destination, 2);
+    }
+
+    #[test]
+    fn test_send_from_outside() {
+        let mut network = network(ECHO, 2);
+        let monitor = network.set_monitor(255, FirstPacket::default());
+
+        network.run_round();
+        monitor.lock().unwrap().packet = None;
+
+        network.send(Packet {
+            destination: 0,
+            x: 41,
+            y: 0,
+        });
+
+        network.run(100).unwrap();
+
+        assert_eq!(
+            monitor.lock().unwrap().packet.map(|packet| packet.x),
+            Some(42)
+        );
+    }
+
+    #[test]
+    fn test_deterministic_scheduling() {
+        let run = || {
+            let mut network = network(ECHO, 3);
+
+            network.set_monitor(255, Nat::new());
+            network.enable_packet_log();
+            network.run(100).unwrap();
+
+            network.get_packet_log().to_vec()
+        };
+
+        let first = run();
+
+        assert_eq!(first.len(), 6);
+        assert_eq!(first, run());
+    }
+}
@@ -0,0 +1,621 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fmt;
+
+use super::intcode_instruction::{Opcode, Parameter};
+use super::slice_to_hashmap;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Transformation {
+    ConstantFolded {
+        address: u128,
+        value: i128,
+    },
+    ReplacedWithMove {
+        address: u128,
+    },
+    JumpThreaded {
+        address: u128,
+        old_target: u128,
+        new_target: u128,
+    },
+    UnreachableRemoved {
+        start: u128,
+        length: u128,
+    },
+}
+
+impl fmt::Display for Transformation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Transformation::ConstantFolded { address, value } => {
+                write!(f, "{}: folded to the constant {}", address, value)
+            }
+            Transformation::ReplacedWithMove { address } => {
+                write!(f, "{}: replaced with a move", address)
+            }
+            Transformation::JumpThreaded {
+                address,
+                old_target,
+                new_target,
+            } => write!(
+                f,
+                "{}: jump to {} threaded to {}",
+                address, old_target, new_target
+            ),
+            Transformation::UnreachableRemoved { start, length } => write!(
+                f,
+                "{}..{}: removed {} unreachable values",
+                start,
+                start + length,
+                length
+            ),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct OptimizedProgram {
+    program: Vec<i128>,
+    transformations: Vec<Transformation>,
+    // Why some optimizations were skipped
+    notes: Vec<String>,
+}
+
+impl OptimizedProgram {
+    pub fn get_program(&self) -> &[i128] {
+        &self.program
+    }
+
+    pub fn get_transformations(&self) -> &[Transformation] {
+        &self.transformations
+    }
+
+    pub fn get_notes(&self) -> &[String] {
+        &self.notes
+    }
+
+    pub fn report(&self) -> String {
+        self.transformations
+            .iter()
+            .map(|transformation| transformation.to_string())
+            .chain(self.notes.iter().map(|note| format!("note: {}", note)))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+// Static optimizations that keep every address where it was, so values read
+// as data and jump targets stay valid. Only instructions reachable from 0 are
+// touched, and never ones the program writes to or reads as data.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Optimizer {
+    program: Vec<i128>,
+    trust_relative_addressing: bool,
+}
+
+impl Optimizer {
+    pub fn new(program: &[i128]) -> Optimizer {
+        Optimizer {
+            program: program.to_vec(),
+            trust_relative_addressing: false,
+        }
+    }
+
+    // Relative mode addresses aren't known statically, so by default a
+    // program using them isn't optimized at all. Trusting them assumes they
+    // only ever touch memory past the program, e.g. a stack.
+    pub fn set_trust_relative_addressing(&mut self, trust_relative_addressing: bool) {
+        self.trust_relative_addressing = trust_relative_addressing;
+    }
+
+    pub fn optimize(&self) -> OptimizedProgram {
+        let mut optimized = OptimizedProgram {
+            program: self.program.clone(),
+            transformations: Vec::new(),
+            notes: Vec::new(),
+        };
+
+        let analysis = match self.analyze(&self.program) {
+            Ok(analysis) => analysis,
+            Err(note) => {
+                optimized.notes.push(note);
+
+                return optimized;
+            }
+        };
+
+        for (&address, &length) in &analysis.instructions {
+            if analysis.is_fixed(address, length) {
+                continue;
+            }
+
+            let transformation = Self::simplify(&mut optimized.program, address)
+                .or_else(|| Self::thread_jump(&mut optimized.program, address, &analysis));
+
+            optimized.transformations.extend(transformation);
+        }
+
+        // threading can leave jumps behind that nothing reaches anymore
+        match self.analyze(&optimized.program) {
+            Ok(analysis) => self.remove_unreachable(&mut optimized, &analysis),
+            Err(note) => optimized.notes.push(note),
+        }
+
+        optimized
+    }
+
+    // Rewrites arithmetic on immediates into a constant move and multiplying
+    // by 1 or adding 0 into the canonical move `add x, 0`.
+    fn simplify(program: &mut [i128], address: u128) -> Option<Transformation> {
+        let index = address as usize;
+        let instruction = program[index];
+
+        if !matches!(instruction % 100, 1 | 2 | 7 | 8) {
+            return None;
+        }
+
+        let destination_mode = instruction / 10000;
+        let (first, second) = (program[index + 1], program[index + 2]);
+        let first_mode = (instruction / 100) % 10;
+        let second_mode = (instruction / 1000) % 10;
+
+        let folded = match (instruction % 100, first_mode, second_mode) {
+            (1, 1, 1) => first.checked_add(second),
+            (2, 1, 1) => first.checked_mul(second),
+            (2, 1, _) if first == 0 => Some(0),
+            (2, _, 1) if second == 0 => Some(0),
+            (7, 1, 1) => Some((first < second) as i128),
+            (8, 1, 1) => Some((first == second) as i128),
+            _ => None,
+        };
+
+        if let Some(value) = folded {
+            let replacement = [destination_mode * 10000 + 1101, value, 0];
+
+            if program[index..(index + 3)] == replacement {
+                return None;
+            }
+
+            program[index..(index + 3)].copy_from_slice(&replacement);
+
+            return Some(Transformation::ConstantFolded { address, value });
+        }
+
+        let moved = match (instruction % 100, first_mode, second_mode) {
+            (2, _, 1) if second == 1 => Some((first_mode, first)),
+            (2, 1, _) if first == 1 => Some((second_mode, second)),
+            (1, 1, _) if first == 0 => Some((second_mode, second)),
+            _ => None,
+        };
+
+        moved.map(|(mode, value)| {
+            program[index..(index + 3)].copy_from_slice(&[
+                destination_mode * 10000 + 1000 + mode * 100 + 1,
+                value,
+                0,
+            ]);
+
+            Transformation::ReplacedWithMove { address }
+        })
+    }
+
+    fn thread_jump(
+        program: &mut [i128],
+        address: u128,
+        analysis: &Analysis,
+    ) -> Option<Transformation> {
+        let index = address as usize;
+
+        let instruction = program[index];
+
+        // only immediate targets can be threaded
+        if !matches!(instruction % 100, 5 | 6) || instruction / 1000 != 1 || program[index + 2] < 0
+        {
+            return None;
+        }
+
+        let old_target = program[index + 2] as u128;
+        let mut new_target = old_target;
+        let mut seen = HashSet::new();
+
+        while seen.insert(new_target) {
+            match analysis.unconditional_jump_target(program, new_target) {
+                Some(next_target) => new_target = next_target,
+                None => break,
+            }
+        }
+
+        if new_target == old_target {
+            return None;
+        }
+
+        program[index + 2] = new_target as i128;
+
+        Some(Transformation::JumpThreaded {
+            address,
+            old_target,
+            new_target,
+        })
+    }
+
+    // Unreachable values are zeroed, or dropped when they end the program.
+    // Values read or written as data are kept.
+    fn remove_unreachable(&self, optimized: &mut OptimizedProgram, analysis: &Analysis) {
+        if analysis.has_indirect_jumps {
+            optimized.notes.push(String::from(
+                "unreachable code kept, some jump targets aren't immediate",
+            ));
+
+            return;
+        }
+
+        if analysis.is_self_modifying() {
+            optimized.notes.push(String::from(
+                "unreachable code kept, the program writes to its own instructions",
+            ));
+
+            return;
+        }
+
+        let mut kept: BTreeSet<u128> = analysis
+            .instructions
+            .iter()
+            .flat_map(|(&address, &length)| address..(address + length))
+            .collect();
+
+        kept.extend(analysis.data_reads.iter().copied());
+        kept.extend(analysis.data_writes.iter().copied());
+
+        let program = &mut optimized.program;
+        let mut address = 0;
+
+        while address < program.len() as u128 {
+            if kept.contains(&address) {
+                address += 1;
+                continue;
+            }
+
+            let start = address;
+
+            while address < program.len() as u128 && !kept.contains(&address) {
+                address += 1;
+            }
+
+            let is_at_end = address == program.len() as u128;
+            let removed = &mut program[(start as usize)..(address as usize)];
+
+            if removed.iter().all(|&value| value == 0) && !is_at_end {
+                continue;
+            }
+
+            removed.iter_mut().for_each(|value| *value = 0);
+
+            optimized
+                .transformations
+                .push(Transformation::UnreachableRemoved {
+                    start,
+                    length: address - start,
+                });
+        }
+
+        let trailing_zeros = program
+            .iter()
+            .enumerate()
+            .rev()
+            .take_while(|(address, &value)| value == 0 && !kept.contains(&(*address as u128)))
+            .count();
+
+        program.truncate(program.len() - trailing_zeros);
+    }
+
+    fn analyze(&self, program: &[i128]) -> Result<Analysis, String> {
+        let memory = slice_to_hashmap(program);
+        let mut analysis = Analysis::default();
+        let mut addresses_to_visit = vec![0];
+
+        while let Some(address) = addresses_to_visit.pop() {
+            if analysis.instructions.contains_key(&address) {
+                continue;
+            }
+
+            let length = memory
+                .get(&address)
+                .and_then(|&instruction| Opcode::instruction_length(instruction))
+                .filter(|&length| (address..(address + length)).all(|i| memory.contains_key(&i)))
+                .ok_or_else(|| format!("no valid instruction at reachable address {}", address))?;
+
+            analysis.instructions.insert(address, length);
+
+            let opcode = Opcode::new(0, &memory, address);
+            let next_address = address + length;
+
+            let (reads, write, jump) = match &opcode {
+                Opcode::Add(first, second, third)
+                | Opcode::Multiply(first, second, third)
+                | Opcode::StoreIfLessThan(first, second, third)
+                | Opcode::StoreIfEquals(first, second, third) => {
+                    (vec![first, second], Some(third), None)
+                }
+                Opcode::SaveInput(_, destination) => (Vec::new(), Some(destination), None),
+                Opcode::Output(value) | Opcode::AdjustRelativeBase(value) => {
+                    (vec![value], None, None)
+                }
+                Opcode::JumpIfTrue(condition, target) => (
+                    vec![condition, target],
+                    None,
+                    Some((condition, target, true)),
+                ),
+                Opcode::JumpIfFalse(condition, target) => (
+                    vec![condition, target],
+                    None,
+                    Some((condition, target, false)),
+                ),
+                Opcode::Terminate => (Vec::new(), None, None),
+            };
+
+            for parameter in reads.iter().chain(write.iter()) {
+                if matches!(parameter, Parameter::Relative(_)) && !self.trust_relative_addressing {
+                    return Err(format!(
+                        "not optimized, relative mode access at {} may touch the program",
+                        address
+                    ));
+                }
+            }
+
+            for parameter in &reads {
+                if let Parameter::Position(source) = parameter {
+                    analysis.data_reads.insert(*source as u128);
+                }
+            }
+
+            if let Some(Parameter::Position(destination)) = write {
+                analysis.data_writes.insert(*destination as u128);
+            }
+
+            match jump {
+                None if opcode == Opcode::Terminate => {}
+                None => addresses_to_visit.push(next_address),
+                Some((condition, target, jumps_if_true)) => {
+                    let condition = match condition {
+                        Parameter::Immediate(value) => Some((*value != 0) == jumps_if_true),
+                        _ => None,
+                    };
+
+                    match target {
+                        Parameter::Immediate(target) if *target >= 0 => {
+                            if condition != Some(false) {
+                                addresses_to_visit.push(*target as u128);
+                            }
+                        }
+                        _ => analysis.has_indirect_jumps |= condition != Some(false),
+                    }
+
+                    if condition != Some(true) {
+                        addresses_to_visit.push(next_address);
+                    }
+                }
+            }
+        }
+
+        Ok(analysis)
+    }
+}
+
+#[derive(Debug, Default)]
+struct Analysis {
+    // reachable instruction addresses and their lengths
+    instructions: BTreeMap<u128, u128>,
+    data_reads: BTreeSet<u128>,
+    data_writes: BTreeSet<u128>,
+    has_indirect_jumps: bool,
+}
+
+impl Analysis {
+    // Fixed instructions are read or written as data, or overlap another
+    // reachable instruction, so they have to stay exactly as they are.
+    fn is_fixed(&self, address: u128, length: u128) -> bool {
+        (address..(address + length)).any(|i| {
+            self.data_reads.contains(&i)
+                || self.data_writes.contains(&i)
+                || self.instructions_covering(i) != [address]
+        })
+    }
+
+    fn is_self_modifying(&self) -> bool {
+        self.data_writes
+            .iter()
+            .any(|&address| !self.instructions_covering(address).is_empty())
+    }
+
+    fn instructions_covering(&self, address: u128) -> Vec<u128> {
+        self.instructions
+            .range(..=address)
+            .rev()
+            .take_while(|(&start, _)| address < start + 4)
+            .filter(|(&start, &length)| address < start + length)
+            .map(|(&start, _)| start)
+            .collect()
+    }
+
+    // Where an unconditional, unmodified jump at `address` goes
+    fn unconditional_jump_target(&self, program: &[i128], address: u128) -> Option<u128> {
+        let length = *self.instructions.get(&address)?;
+
+        if self.is_fixed(address, length) {
+            return None;
+        }
+
+        let index = address as usize;
+
+        match (program[index], program[index + 1], program[index + 2]) {
+            (1105, condition, target) if condition != 0 && target >= 0 => Some(target as u128),
+            (1106, 0, target) if target >= 0 => Some(target as u128),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::file_reader::to_string_vector;
+    use crate::intcode_computer::compiler::compile;
+    use crate::intcode_computer::{IntcodeComputer, IntcodeComputerStatus};
+
+    // outputs 2 + 3 and the value at 32, with a jump chain and dead code
+    const PROGRAM: [i128; 36] = [
+        1101, 2, 3, 30, 1002, 30, 1, 31, 4, 31, 1105, 1, 20, 99, 77, 77, 77, 77, 77, 77, 1105, 1,
+        24, 0, 4, 32, 99, 0, 0, 0, 0, 0, 42, 5, 5, 5,
+    ];
+
+    fn run(program: &[i128], inputs: &[i128]) -> Vec<i128> {
+        let mut intcode_computer = IntcodeComputer::new(program);
+        let mut inputs = inputs.iter();
+
+        intcode_computer.execute_program();
+
+        while intcode_computer.get_status() == IntcodeComputerStatus::WaitingForInput {
+            intcode_computer.set_input(*inputs.next().unwrap());
+            intcode_computer.execute_program();
+        }
+
+        intcode_computer.get_outputs()
+    }
+
+    fn assert_same_outputs(original: &[i128], optimized: &[i128], inputs: &[i128]) {
+        assert_eq!(
+            run(optimized, inputs),
+            run(original, inputs),
+            "inputs {:?}",
+            inputs
+        );
+    }
+
+    #[test]
+    fn test_optimize() {
+        let result = Optimizer::new(&PROGRAM).optimize();
+
+        let expected_transformations = vec![
+            Transformation::ConstantFolded {
+                address: 0,
+                value: 5,
+            },
+            Transformation::ReplacedWithMove { address: 4 },
+            Transformation::JumpThreaded {
+                address: 10,
+                old_target: 20,
+                new_target: 24,
+            },
+            Transformation::UnreachableRemoved {
+                start: 13,
+                length: 11,
+            },
+            Transformation::UnreachableRemoved {
+                start: 33,
+                length: 3,
+            },
+        ];
+        let expected_program = vec![
+            1101, 5, 0, 30, 1001, 30, 0, 31, 4, 31, 1105, 1, 24, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            4, 32, 99, 0, 0, 0, 0, 0, 42,
+        ];
+
+        assert_eq!(
+            result.get_transformations(),
+            expected_transformations.as_slice()
+        );
+        assert_eq!(result.get_program(), expected_program.as_slice());
+        assert!(result.get_notes().is_empty());
+
+        assert_same_outputs(&PROGRAM, result.get_program(), &[]);
+    }
+
+    #[test]
+    fn test_report() {
+        let result = Optimizer::new(&PROGRAM).optimize();
+
+        let expected = "0: folded to the constant 5\n4: replaced with a move\n\
+                        10: jump to 20 threaded to 24\n13..24: removed 11 unreachable values\n\
+                        33..36: removed 3 unreachable values";
+
+        assert_eq!(result.report(), expected);
+    }
+
+    #[test]
+    fn test_self_modifying_code_is_kept() {
+        // the first instruction patches the multiply by 0 into a multiply by 2
+        let program = [1101, 1, 1, 5, 1102, 0, 1, 11, 4, 11, 99, 0];
+
+        let result = Optimizer::new(&program).optimize();
+
+        let expected_transformations = vec![Transformation::ConstantFolded {
+            address: 0,
+            value: 2,
+        }];
+
+        assert_eq!(
+            result.get_transformations(),
+            expected_transformations.as_slice()
+        );
+        assert_eq!(result.get_program()[4..8], program[4..8]);
+        assert_eq!(
+            result.get_notes(),
+            &[String::from(
+                "unreachable code kept, the program writes to its own instructions"
+            )]
+        );
+
+        assert_same_outputs(&program, result.get_program(), &[]);
+    }
+
+    #[test]
+    fn test_relative_addressing() {
+        let source = "
+            fn sign(n) {
+                if n < 0 { return -1; } else { if n == 0 { return 0; } }
+                return 1;
+            }
+
+            fn main() {
+                let n = read();
+                while n != 0 { print(sign(n) * 1); n = read(); }
+            }";
+        let program = compile(source).unwrap();
+        let inputs = [5, -3, 8, 0];
+
+        let untrusted = Optimizer::new(&program).optimize();
+
+        assert_eq!(untrusted.get_program(), program.as_slice());
+        assert!(untrusted.get_notes()[0].starts_with("not optimized"));
+
+        let mut optimizer = Optimizer::new(&program);
+
+        optimizer.set_trust_relative_addressing(true);
+
+        let trusted = optimizer.optimize();
+
+        assert!(!trusted.get_transformations().is_empty());
+
+        assert_same_outputs(&program, trusted.get_program(), &inputs);
+    }
+
+    #[test]
+    fn test_day_5_program() {
+        let program: Vec<i128> = to_string_vector("inputs/day_5.txt").unwrap()[0]
+            .split(',')
+            .map(|value| value.parse().unwrap())
+            .collect();
+
+        let result = Optimizer::new(&program).optimize();
+
+        // the program patches the opcode at 6 before running it
+        assert_eq!(
+            result.get_notes(),
+            &[String::from("no valid instruction at reachable address 6")]
+        );
+
+        assert_same_outputs(&program, result.get_program(), &[1]);
+        assert_same_outputs(&program, result.get_program(), &[5]);
+    }
+}
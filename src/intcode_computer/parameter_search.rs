@@ -0,0 +1,435 @@
+use std::convert::TryFrom;
+use std::ops::RangeInclusive;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+use super::hooks::{ExecutionHook, HookAction, InstructionEvent};
+use super::{IntcodeComputer, IntcodeComputerStatus};
+
+const DEFAULT_MAX_INSTRUCTIONS: u64 = 1_000_000;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ParameterSearch {
+    intcode_computer: IntcodeComputer,
+    patches: Vec<(usize, RangeInclusive<i32>)>,
+    inputs: Vec<i128>,
+    number_of_threads: usize,
+    max_instructions: u64,
+}
+
+// Pauses the computer once it has run out of instructions.
+#[derive(Debug)]
+struct InstructionBudget {
+    remaining: u64,
+}
+
+impl ExecutionHook for InstructionBudget {
+    fn before_instruction(&mut self, _event: &InstructionEvent) -> HookAction {
+        if self.remaining == 0 {
+            return HookAction::Pause;
+        }
+
+        self.remaining -= 1;
+
+        HookAction::Continue
+    }
+}
+
+impl ParameterSearch {
+    pub fn new<A>(args: A) -> ParameterSearch
+    where
+        A: Into<IntcodeComputer>,
+    {
+        let number_of_threads = thread::available_parallelism()
+            .map(|threads| threads.get())
+            .unwrap_or(1);
+
+        ParameterSearch {
+            intcode_computer: args.into(),
+            patches: Vec::new(),
+            inputs: Vec::new(),
+            number_of_threads,
+            max_instructions: DEFAULT_MAX_INSTRUCTIONS,
+        }
+    }
+
+    // Each combination of patch values is written with replace_code_in_program
+    // before the program runs.
+    pub fn add_patch(
+        &mut self,
+        code_index: usize,
+        values: RangeInclusive<i32>,
+    ) -> Result<(), String> {
+        if !self
+            .intcode_computer
+            .original_program
+            .contains_key(&(code_index as u128))
+        {
+            return Err(format!(
+                "Cannot patch index {}, it's outside the program",
+                code_index
+            ));
+        }
+
+        self.patches.push((code_index, values));
+
+        if let Err(error) = self.number_of_combinations() {
+            self.patches.pop();
+
+            return Err(error);
+        }
+
+        Ok(())
+    }
+
+    // Inputs fed, in order, whenever the program waits for input.
+    pub fn set_inputs(&mut self, inputs: &[i128]) {
+        self.inputs = inputs.to_vec();
+    }
+
+    pub fn set_number_of_threads(&mut self, number_of_threads: usize) {
+        self.number_of_threads = number_of_threads.max(1);
+    }
+
+    // Combinations that run longer than this are treated as never halting.
+    pub fn set_max_instructions(&mut self, max_instructions: u64) {
+        self.max_instructions = max_instructions;
+    }
+
+    pub fn number_of_combinations(&self) -> Result<usize, String> {
+        self.patches
+            .iter()
+            .try_fold(1_usize, |product, (_, values)| {
+                Self::range_length(values)?.checked_mul(product)
+            })
+            .ok_or_else(|| String::from("There are too many patch combinations to search"))
+    }
+
+    // Returns the patch values, in the order the patches were added, of every
+    // combination where the predicate holds after running the program.
+    // Combinations that run out of instructions or crash never match.
+    pub fn find_all<P>(&self, predicate: P) -> Result<Vec<Vec<i32>>, String>
+    where
+        P: Fn(&IntcodeComputer) -> bool + Sync,
+    {
+        let matches = Mutex::new(Vec::new());
+
+        self.search(|combination_index, computer| {
+            if predicate(computer) {
+                matches.lock().unwrap().push(combination_index);
+            }
+
+            true
+        })?;
+
+        let mut matches = matches.into_inner().unwrap();
+
+        matches.sort_unstable();
+
+        Ok(matches
+            .into_iter()
+            .map(|combination_index| self.combination_values(combination_index))
+            .collect())
+    }
+
+    // Same as find_all, but stops early and returns the match that would come
+    // first in a sequential search.
+    pub fn find_first<P>(&self, predicate: P) -> Result<Option<Vec<i32>>, String>
+    where
+        P: Fn(&IntcodeComputer) -> bool + Sync,
+    {
+        let first_match = AtomicUsize::new(usize::MAX);
+
+        self.search(|combination_index, computer| {
+            if first_match.load(Ordering::Relaxed) < combination_index {
+                return false;
+            }
+
+            if predicate(computer) {
+                first_match.fetch_min(combination_index, Ordering::Relaxed);
+
+                return false;
+            }
+
+            true
+        })?;
+
+        match first_match.into_inner() {
+            usize::MAX => Ok(None),
+            combination_index => Ok(Some(self.combination_values(combination_index))),
+        }
+    }
+
+    // Runs every combination across the threads, each thread taking every
+    // n-th combination. A thread stops once `on_finished` returns false.
+    fn search<F>(&self, on_finished: F) -> Result<(), String>
+    where
+        F: Fn(usize, &IntcodeComputer) -> bool + Sync,
+    {
+        let number_of_combinations = self.number_of_combinations()?;
+        let number_of_threads = self.number_of_threads.min(number_of_combinations).max(1);
+
+        thread::scope(|scope| {
+            for thread_index in 0..number_of_threads {
+                let on_finished = &on_finished;
+
+                scope.spawn(move || {
+                    for combination_index in
+                        (thread_index..number_of_combinations).step_by(number_of_threads)
+                    {
+                        let computer = match self.run_combination(combination_index) {
+                            Some(computer) => computer,
+                            None => continue,
+                        };
+
+                        if !on_finished(combination_index, &computer) {
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+
+        Ok(())
+    }
+
+    // None if the combination ran out of instructions, or patched in something
+    // the computer panics on, like an unknown opcode.
+    fn run_combination(&self, combination_index: usize) -> Option<IntcodeComputer> {
+        let mut computer = self.intcode_computer.clone();
+
+        let values = self.combination_values(combination_index);
+
+        for ((code_index, _), value) in self.patches.iter().zip(values) {
+            computer.replace_code_in_program(*code_index, value);
+        }
+
+        let budget = computer.add_hook(InstructionBudget {
+            remaining: self.max_instructions,
+        });
+
+        let mut inputs = self.inputs.iter();
+
+        let run = panic::catch_unwind(AssertUnwindSafe(|| {
+            computer.execute_program();
+
+            while computer.get_status() == IntcodeComputerStatus::WaitingForInput {
+                match inputs.next() {
+                    Some(&input) => computer.set_input(input),
+                    None => break,
+                }
+
+                computer.execute_program();
+            }
+        }));
+
+        let ran_out_of_instructions = budget.lock().unwrap().remaining == 0
+            && computer.get_status() == IntcodeComputerStatus::Paused;
+
+        if run.is_err() || ran_out_of_instructions {
+            return None;
+        }
+
+        Some(computer)
+    }
+
+    // The last patch varies fastest.
+    fn combination_values(&self, combination_index: usize) -> Vec<i32> {
+        let mut remaining = combination_index;
+
+        let mut values: Vec<i32> = self
+            .patches
+            .iter()
+            .rev()
+            .map(|(_, values)| {
+                // the search only runs when every length fits
+                let length = Self::range_length(values).unwrap_or(1);
+                let offset = remaining % length;
+
+                remaining /= length;
+
+                (*values.start() as i64 + offset as i64) as i32
+            })
+            .collect();
+
+        values.reverse();
+
+        values
+    }
+
+    // None if the length doesn't fit in a usize
+    fn range_length(values: &RangeInclusive<i32>) -> Option<usize> {
+        if values.is_empty() {
+            return Some(0);
+        }
+
+        let length = *values.end() as i64 - *values.start() as i64 + 1;
+
+        usize::try_from(length).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // memory[0] = noun * 5 + verb + 7, with noun and verb at 1 and 2
+    const NOUN_VERB_PROGRAM: [i128; 21] = [
+        1, 0, 0, 3, 1, 1, 2, 3, 1002, 1, 4, 0, 1, 0, 3, 0, 1001, 0, 7, 0, 99,
+    ];
+    // outputs input * 3 + 4
+    const LINEAR_PROGRAM: [i128; 15] = [3, 13, 1002, 13, 3, 14, 1001, 14, 4, 14, 4, 14, 99, 0, 0];
+
+    fn noun_verb_search() -> ParameterSearch {
+        let mut search = ParameterSearch::new(NOUN_VERB_PROGRAM.as_ref());
+
+        search.add_patch(1, 0..=20).unwrap();
+        search.add_patch(2, 0..=20).unwrap();
+
+        search
+    }
+
+    #[test]
+    fn test_combination_values() {
+        let search = noun_verb_search();
+
+        assert_eq!(search.number_of_combinations(), Ok(441));
+        assert_eq!(search.combination_values(0), vec![0, 0]);
+        assert_eq!(search.combination_values(22), vec![1, 1]);
+        assert_eq!(search.combination_values(440), vec![20, 20]);
+    }
+
+    #[test]
+    fn test_find_all() {
+        let mut search = noun_verb_search();
+
+        search.set_number_of_threads(3);
+
+        let expected = vec![
+            vec![0, 20],
+            vec![1, 15],
+            vec![2, 10],
+            vec![3, 5],
+            vec![4, 0],
+        ];
+
+        let result = search
+            .find_all(|computer| computer.get_current_memory()[&0] == 27)
+            .unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_find_first() {
+        let mut search = noun_verb_search();
+
+        search.set_number_of_threads(4);
+
+        let expected = Some(vec![0, 20]);
+
+        let result = search
+            .find_first(|computer| computer.get_current_memory()[&0] == 27)
+            .unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_find_first_no_match() {
+        let search = noun_verb_search();
+
+        let result = search
+            .find_first(|computer| computer.get_current_memory()[&0] == -1)
+            .unwrap();
+
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_find_all_with_inputs() {
+        let mut search = ParameterSearch::new(LINEAR_PROGRAM.as_ref());
+
+        // patches the multiplier
+        search.add_patch(4, 1..=5).unwrap();
+        search.set_inputs(&[6]);
+
+        let expected = vec![vec![3]];
+
+        let result = search
+            .find_all(|computer| computer.get_outputs() == vec![22])
+            .unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_wide_ranges() {
+        let mut search = ParameterSearch::new(NOUN_VERB_PROGRAM.as_ref());
+
+        search.add_patch(1, i32::MIN..=i32::MAX).unwrap();
+
+        assert_eq!(search.number_of_combinations(), Ok(1 << 32));
+        assert_eq!(search.combination_values(0), vec![i32::MIN]);
+        assert_eq!(search.combination_values((1 << 32) - 1), vec![i32::MAX]);
+
+        assert_eq!(
+            search.add_patch(2, i32::MIN..=i32::MAX),
+            Err(String::from(
+                "There are too many patch combinations to search"
+            ))
+        );
+        assert_eq!(search.patches.len(), 1);
+    }
+
+    #[test]
+    fn test_endless_combinations_never_match() {
+        // jumps back to itself forever unless the condition is patched to 0
+        let program = [1105, 1, 0, 99];
+        let mut search = ParameterSearch::new(program.as_ref());
+
+        search.add_patch(1, 0..=1).unwrap();
+        search.set_max_instructions(1_000);
+
+        let expected = vec![vec![0]];
+
+        let result = search.find_all(|_| true).unwrap();
+
+        assert_eq!(result, expected);
+        assert_eq!(
+            search.find_first(|computer| computer.get_outputs().is_empty()),
+            Ok(Some(vec![0]))
+        );
+    }
+
+    #[test]
+    fn test_crashing_combinations_never_match() {
+        // 98 isn't an opcode
+        let program = [1101, 1, 1, 5, 99, 0];
+        let mut search = ParameterSearch::new(program.as_ref());
+
+        search.add_patch(4, 98..=99).unwrap();
+        search.set_number_of_threads(2);
+
+        let expected = vec![vec![99]];
+
+        let result = search.find_all(|_| true).unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_add_patch_outside_program() {
+        let mut search = ParameterSearch::new(NOUN_VERB_PROGRAM.as_ref());
+
+        assert_eq!(
+            search.add_patch(21, 0..=1),
+            Err(String::from(
+                "Cannot patch index 21, it's outside the program"
+            ))
+        );
+        assert!(search.patches.is_empty());
+    }
+}
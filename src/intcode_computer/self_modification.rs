@@ -0,0 +1,226 @@
+use std::collections::HashSet;
+use std::ops::Range;
+
+use super::hooks::{ExecutionHook, HookAction, InstructionEvent, MemoryMutation};
+use super::intcode_instruction::Opcode;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct SelfModification {
+    // Address of the instruction that did the write
+    pub writer_index: u128,
+    pub mutation: MemoryMutation,
+    // The address was part of an instruction that already ran
+    pub hits_executed_code: bool,
+    // The address is part of the very next instruction to run
+    pub hits_pending_instruction: bool,
+}
+
+// Flags writes that land on code. Writes that only touch data are ignored.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct SelfModificationDetector {
+    executed_addresses: HashSet<u128>,
+    // last write, checked against the next instruction once it's decoded
+    pending_write: Option<(u128, MemoryMutation)>,
+    modifications: Vec<SelfModification>,
+}
+
+impl SelfModificationDetector {
+    pub fn new() -> SelfModificationDetector {
+        SelfModificationDetector::default()
+    }
+
+    pub fn get_modifications(&self) -> &[SelfModification] {
+        &self.modifications
+    }
+
+    pub fn is_self_modifying(&self) -> bool {
+        !self.modifications.is_empty()
+    }
+
+    pub fn summary(&self) -> String {
+        self.modifications
+            .iter()
+            .map(|modification| {
+                let mut targets = Vec::new();
+
+                if modification.hits_executed_code {
+                    targets.push("executed code");
+                }
+
+                if modification.hits_pending_instruction {
+                    targets.push("the next instruction");
+                }
+
+                format!(
+                    "{}: wrote {} to {} ({}), was {}",
+                    modification.writer_index,
+                    modification.mutation.new_value,
+                    modification.mutation.address,
+                    targets.join(", "),
+                    modification.mutation.old_value
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    fn check_pending_write(&mut self, next_instruction: &InstructionEvent) {
+        if let Some((writer_index, mutation)) = self.pending_write.take() {
+            let next_span = get_instruction_span(next_instruction);
+
+            let hits_executed_code = self.executed_addresses.contains(&mutation.address);
+            let hits_pending_instruction = next_span.contains(&mutation.address);
+
+            if hits_executed_code || hits_pending_instruction {
+                self.modifications.push(SelfModification {
+                    writer_index,
+                    mutation,
+                    hits_executed_code,
+                    hits_pending_instruction,
+                });
+            }
+        }
+    }
+}
+
+impl ExecutionHook for SelfModificationDetector {
+    fn before_instruction(&mut self, event: &InstructionEvent) -> HookAction {
+        self.check_pending_write(event);
+
+        HookAction::Continue
+    }
+
+    fn after_instruction(&mut self, event: &InstructionEvent) -> HookAction {
+        // before hooks are skipped when resuming from a pause
+        self.check_pending_write(event);
+
+        if *event.opcode == Opcode::Terminate {
+            return HookAction::Continue;
+        }
+
+        self.executed_addresses.extend(get_instruction_span(event));
+
+        if let Some(mutation) = event.mutation {
+            self.pending_write = Some((event.index, mutation));
+        }
+
+        HookAction::Continue
+    }
+}
+
+// Addresses the instruction was decoded from. An instruction that overwrote
+// itself is measured by what it was before the write.
+fn get_instruction_span(event: &InstructionEvent) -> Range<u128> {
+    let instruction = match event.mutation {
+        Some(mutation) if mutation.address == event.index => mutation.old_value,
+        _ => event.memory.get(&event.index).copied().unwrap_or(0),
+    };
+
+    // the length only depends on the opcode, not on the parameter modes
+    let length = Opcode::instruction_length(instruction % 100).unwrap_or(1);
+
+    event.index..(event.index + length)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::file_reader::to_string_vector;
+    use crate::intcode_computer::IntcodeComputer;
+
+    fn read_program(file_name: &str) -> Vec<String> {
+        to_string_vector(file_name).unwrap()[0]
+            .split(',')
+            .map(String::from)
+            .collect()
+    }
+
+    #[test]
+    fn test_day_2_modifies_executed_code() {
+        let program = read_program("test_inputs/day_2_part_1.txt");
+        let mut intcode_computer = IntcodeComputer::new(program.as_slice());
+        let detector = intcode_computer.detect_self_modification();
+
+        intcode_computer.execute_program();
+
+        let detector = detector.lock().unwrap();
+
+        let expected_summary = "0: wrote 70 to 3 (executed code), was 3\n\
+                                4: wrote 3500 to 0 (executed code), was 1";
+
+        assert!(detector.is_self_modifying());
+        assert_eq!(detector.summary(), expected_summary);
+    }
+
+    #[test]
+    fn test_write_to_next_instruction() {
+        let program = [1101, 99, 0, 4, 0];
+        let mut intcode_computer = IntcodeComputer::new(program.as_ref());
+        let detector = intcode_computer.detect_self_modification();
+
+        intcode_computer.execute_program();
+
+        let expected = vec![SelfModification {
+            writer_index: 0,
+            mutation: MemoryMutation {
+                address: 4,
+                old_value: 0,
+                new_value: 99,
+            },
+            hits_executed_code: false,
+            hits_pending_instruction: true,
+        }];
+
+        assert_eq!(
+            detector.lock().unwrap().get_modifications(),
+            expected.as_slice()
+        );
+    }
+
+    #[test]
+    fn test_data_writes_are_ignored() {
+        let program = [3, 9, 1001, 9, 5, 10, 4, 10, 99, 0, 0];
+        let mut intcode_computer = IntcodeComputer::new(program.as_ref());
+        let detector = intcode_computer.detect_self_modification();
+
+        intcode_computer.set_input(3);
+        intcode_computer.execute_program();
+
+        assert_eq!(intcode_computer.get_outputs(), vec![8]);
+        assert!(!detector.lock().unwrap().is_self_modifying());
+    }
+
+    #[test]
+    fn test_instruction_overwriting_its_opcode() {
+        // the first add turns itself into a 99, then the second add writes to
+        // one of its parameters
+        let program = [1101, 0, 99, 0, 1101, 7, 7, 2, 99];
+        let mut intcode_computer = IntcodeComputer::new(program.as_ref());
+        let detector = intcode_computer.detect_self_modification();
+
+        intcode_computer.execute_program();
+
+        let expected_summary = "0: wrote 99 to 0 (executed code), was 1101\n\
+                                4: wrote 14 to 2 (executed code), was 99";
+
+        assert_eq!(detector.lock().unwrap().summary(), expected_summary);
+    }
+
+    #[test]
+    fn test_day_5_patches_its_next_instruction() {
+        let program = read_program("inputs/day_5.txt");
+        let mut intcode_computer = IntcodeComputer::new(program.as_slice());
+        let detector = intcode_computer.detect_self_modification();
+
+        intcode_computer.set_input(1);
+        intcode_computer.execute_program();
+
+        let detector = detector.lock().unwrap();
+        let first_modification = detector.get_modifications()[0];
+
+        assert_eq!(first_modification.writer_index, 2);
+        assert_eq!(first_modification.mutation.address, 6);
+        assert!(first_modification.hits_pending_instruction);
+    }
+}
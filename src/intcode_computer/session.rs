@@ -0,0 +1,343 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::sync::{Arc, Mutex};
+
+use super::hooks::{ExecutionHook, HookAction, InstructionEvent};
+use super::intcode_instruction::{Opcode, Parameter};
+use super::{IntcodeComputer, IntcodeComputerStatus};
+
+const SESSION_HEADER: &str = "intcode-session";
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct RecordedInput {
+    // Number of instructions executed before the input was read
+    pub instruction_count: u64,
+    pub value: i128,
+}
+
+// Saved as a header line followed by one "<instruction count> <value>" line
+// per input.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Session {
+    inputs: Vec<RecordedInput>,
+}
+
+impl Session {
+    pub fn new() -> Session {
+        Session::default()
+    }
+
+    pub fn get_inputs(&self) -> &[RecordedInput] {
+        &self.inputs
+    }
+
+    pub fn parse(contents: &str) -> Result<Session, String> {
+        let mut lines = contents.lines().enumerate();
+
+        match lines.next() {
+            Some((_, SESSION_HEADER)) => {}
+            _ => return Err(format!("Missing {} header", SESSION_HEADER)),
+        }
+
+        let mut inputs = Vec::new();
+
+        for (index, line) in lines.filter(|(_, line)| !line.trim().is_empty()) {
+            let values: Vec<&str> = line.split_whitespace().collect();
+
+            let input = match values.as_slice() {
+                [instruction_count, value] => {
+                    instruction_count.parse().ok().zip(value.parse().ok()).map(
+                        |(instruction_count, value)| RecordedInput {
+                            instruction_count,
+                            value,
+                        },
+                    )
+                }
+                _ => None,
+            };
+
+            inputs.push(input.ok_or_else(|| format!("Line {}: can't parse {}", index + 1, line))?);
+        }
+
+        Ok(Session { inputs })
+    }
+
+    pub fn save(&self, file_name: &str) -> io::Result<()> {
+        fs::write(file_name, self.to_string())
+    }
+
+    pub fn load(file_name: &str) -> Result<Session, String> {
+        let contents = fs::read_to_string(file_name).map_err(|error| error.to_string())?;
+
+        Session::parse(&contents)
+    }
+}
+
+impl fmt::Display for Session {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}", SESSION_HEADER)?;
+
+        for input in &self.inputs {
+            writeln!(f, "{} {}", input.instruction_count, input.value)?;
+        }
+
+        Ok(())
+    }
+}
+
+// Add with IntcodeComputer::add_hook to record every input the computer reads.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct SessionRecorder {
+    instruction_count: u64,
+    session: Session,
+}
+
+impl SessionRecorder {
+    pub fn new() -> SessionRecorder {
+        SessionRecorder::default()
+    }
+
+    pub fn get_session(&self) -> &Session {
+        &self.session
+    }
+}
+
+impl ExecutionHook for SessionRecorder {
+    fn after_instruction(&mut self, event: &InstructionEvent) -> HookAction {
+        if let Opcode::SaveInput(Parameter::Immediate(value), _) = event.opcode {
+            self.session.inputs.push(RecordedInput {
+                instruction_count: self.instruction_count,
+                value: *value,
+            });
+        }
+
+        if *event.opcode != Opcode::Terminate {
+            self.instruction_count += 1;
+        }
+
+        HookAction::Continue
+    }
+}
+
+#[derive(Debug, Default)]
+struct ReplayCounter {
+    instruction_count: u64,
+    stop_at: Option<u64>,
+}
+
+impl ExecutionHook for ReplayCounter {
+    fn before_instruction(&mut self, _event: &InstructionEvent) -> HookAction {
+        if self.stop_at == Some(self.instruction_count) {
+            HookAction::Pause
+        } else {
+            HookAction::Continue
+        }
+    }
+
+    fn after_instruction(&mut self, event: &InstructionEvent) -> HookAction {
+        if *event.opcode != Opcode::Terminate {
+            self.instruction_count += 1;
+        }
+
+        HookAction::Continue
+    }
+}
+
+// Feeds a recorded session back into a fresh computer, checking every input
+// is read at the same point it was recorded at.
+#[derive(Debug)]
+pub struct Replay {
+    intcode_computer: IntcodeComputer,
+    session: Session,
+    number_of_inputs_used: usize,
+    counter: Arc<Mutex<ReplayCounter>>,
+}
+
+impl Replay {
+    pub fn new<A>(args: A, session: Session) -> Replay
+    where
+        A: Into<IntcodeComputer>,
+    {
+        let mut intcode_computer = args.into();
+        let counter = intcode_computer.add_hook(ReplayCounter::default());
+
+        Replay {
+            intcode_computer,
+            session,
+            number_of_inputs_used: 0,
+            counter,
+        }
+    }
+
+    pub fn get_computer(&self) -> &IntcodeComputer {
+        &self.intcode_computer
+    }
+
+    pub fn get_instruction_count(&self) -> u64 {
+        self.counter.lock().unwrap().instruction_count
+    }
+
+    pub fn get_number_of_inputs_used(&self) -> usize {
+        self.number_of_inputs_used
+    }
+
+    // Pauses the computer once `instruction_count` instructions have run.
+    // Calling it again with a later count continues from there.
+    pub fn run_until(&mut self, instruction_count: u64) -> Result<IntcodeComputerStatus, String> {
+        self.run(Some(instruction_count))
+    }
+
+    // Runs until the program finishes or waits for more input than was
+    // recorded.
+    pub fn run_to_end(&mut self) -> Result<IntcodeComputerStatus, String> {
+        self.run(None)
+    }
+
+    fn run(&mut self, stop_at: Option<u64>) -> Result<IntcodeComputerStatus, String> {
+        if stop_at.is_some() && stop_at <= Some(self.get_instruction_count()) {
+            return Ok(self.intcode_computer.get_status());
+        }
+
+        self.counter.lock().unwrap().stop_at = stop_at;
+
+        if self.intcode_computer.get_status() != IntcodeComputerStatus::WaitingForInput {
+            self.intcode_computer.execute_program();
+        }
+
+        while self.intcode_computer.get_status() == IntcodeComputerStatus::WaitingForInput {
+            let input = match self.session.inputs.get(self.number_of_inputs_used) {
+                Some(input) => *input,
+                None => break,
+            };
+
+            let instruction_count = self.get_instruction_count();
+
+            if input.instruction_count != instruction_count {
+                return Err(format!(
+                    "Input {} was recorded at instruction {} but read at {}",
+                    self.number_of_inputs_used, input.instruction_count, instruction_count
+                ));
+            }
+
+            self.intcode_computer.set_input(input.value);
+            self.number_of_inputs_used += 1;
+            self.intcode_computer.execute_program();
+        }
+
+        Ok(self.intcode_computer.get_status())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // reads numbers and prints their running total until it reads 0
+    const SUM_PROGRAM: [i128; 17] = [
+        3, 15, 1006, 15, 14, 1, 16, 15, 16, 4, 16, 1105, 1, 0, 99, 0, 0,
+    ];
+
+    fn record(inputs: &[i128]) -> (Session, Vec<i128>) {
+        let mut intcode_computer = IntcodeComputer::new(SUM_PROGRAM.as_ref());
+        let recorder = intcode_computer.add_hook(SessionRecorder::new());
+
+        intcode_computer.execute_program();
+
+        for &input in inputs {
+            intcode_computer.set_input(input);
+            intcode_computer.execute_program();
+        }
+
+        let session = recorder.lock().unwrap().get_session().clone();
+
+        (session, intcode_computer.get_outputs())
+    }
+
+    #[test]
+    fn test_record() {
+        let (session, _) = record(&[4, 5, 0]);
+
+        let expected = vec![
+            RecordedInput {
+                instruction_count: 0,
+                value: 4,
+            },
+            RecordedInput {
+                instruction_count: 5,
+                value: 5,
+            },
+            RecordedInput {
+                instruction_count: 10,
+                value: 0,
+            },
+        ];
+
+        assert_eq!(session.get_inputs(), expected.as_slice());
+    }
+
+    #[test]
+    fn test_session_round_trip() {
+        let (session, _) = record(&[4, -5, 0]);
+
+        let expected_contents = "intcode-session\n0 4\n5 -5\n10 0\n";
+
+        assert_eq!(session.to_string(), expected_contents);
+        assert_eq!(Session::parse(expected_contents), Ok(session));
+        assert_eq!(
+            Session::parse("intcode-session\n0 4\n5"),
+            Err(String::from("Line 3: can't parse 5"))
+        );
+        assert!(Session::parse("0 4").is_err());
+    }
+
+    #[test]
+    fn test_replay_to_end() {
+        let (session, outputs) = record(&[4, 5, 7]);
+
+        let mut replay = Replay::new(SUM_PROGRAM.as_ref(), session);
+
+        let result = replay.run_to_end();
+
+        assert_eq!(result, Ok(IntcodeComputerStatus::WaitingForInput));
+        assert_eq!(replay.get_computer().get_outputs(), outputs);
+        assert_eq!(replay.get_number_of_inputs_used(), 3);
+    }
+
+    #[test]
+    fn test_replay_stops_at_instruction() {
+        let (session, _) = record(&[4, 5, 7, 0]);
+
+        let mut replay = Replay::new(SUM_PROGRAM.as_ref(), session);
+
+        let result = replay.run_until(7);
+
+        assert_eq!(result, Ok(IntcodeComputerStatus::Paused));
+        assert_eq!(replay.get_instruction_count(), 7);
+        assert_eq!(replay.get_computer().get_outputs(), vec![4]);
+
+        let result = replay.run_to_end();
+
+        assert_eq!(result, Ok(IntcodeComputerStatus::Finished));
+        assert_eq!(replay.get_computer().get_outputs(), vec![4, 9, 16]);
+    }
+
+    #[test]
+    fn test_replay_detects_divergence() {
+        let (session, _) = record(&[4, 5, 0]);
+
+        // the patched jump skips the addition, so the second input comes early
+        let mut intcode_computer = IntcodeComputer::new(SUM_PROGRAM.as_ref());
+        intcode_computer.replace_code_in_program(2, 1005);
+        intcode_computer.replace_code_in_program(4, 9);
+
+        let mut replay = Replay::new(intcode_computer, session);
+
+        assert_eq!(
+            replay.run_to_end(),
+            Err(String::from(
+                "Input 1 was recorded at instruction 5 but read at 4"
+            ))
+        );
+    }
+}
@@ -0,0 +1,996 @@
+use std::collections::{BTreeMap, HashMap};
+
+use super::constraint_solver::{
+    Assignment, Constraint, ConstraintSolver, Relation, Satisfiability,
+};
+use super::intcode_instruction::{Opcode, Parameter};
+use super::IntcodeComputer;
+
+const DEFAULT_MAX_STEPS: usize = 100_000;
+const DEFAULT_MAX_PATHS: usize = 1_000;
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum Symbol {
+    Input(usize),
+    Memory(u128),
+}
+
+// constant + sum(coefficient * symbol)
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LinearExpression {
+    constant: i128,
+    coefficients: BTreeMap<Symbol, i128>,
+}
+
+impl LinearExpression {
+    pub fn constant(value: i128) -> LinearExpression {
+        LinearExpression {
+            constant: value,
+            coefficients: BTreeMap::new(),
+        }
+    }
+
+    pub fn symbol(symbol: Symbol) -> LinearExpression {
+        let mut coefficients = BTreeMap::new();
+        coefficients.insert(symbol, 1);
+
+        LinearExpression {
+            constant: 0,
+            coefficients,
+        }
+    }
+
+    pub fn get_constant(&self) -> i128 {
+        self.constant
+    }
+
+    pub fn get_coefficients(&self) -> &BTreeMap<Symbol, i128> {
+        &self.coefficients
+    }
+
+    pub fn as_constant(&self) -> Option<i128> {
+        if self.coefficients.is_empty() {
+            Some(self.constant)
+        } else {
+            None
+        }
+    }
+
+    // Arithmetic returns None if the constant or a coefficient overflows.
+    pub fn add(&self, other: &LinearExpression) -> Option<LinearExpression> {
+        let mut result = self.clone();
+
+        result.constant = result.constant.checked_add(other.constant)?;
+
+        for (symbol, coefficient) in &other.coefficients {
+            let result_coefficient = result.coefficients.entry(*symbol).or_insert(0);
+
+            *result_coefficient = result_coefficient.checked_add(*coefficient)?;
+        }
+
+        result
+            .coefficients
+            .retain(|_, coefficient| *coefficient != 0);
+
+        Some(result)
+    }
+
+    pub fn sub(&self, other: &LinearExpression) -> Option<LinearExpression> {
+        self.add(&other.scale(-1)?)
+    }
+
+    pub fn scale(&self, factor: i128) -> Option<LinearExpression> {
+        if factor == 0 {
+            return Some(LinearExpression::constant(0));
+        }
+
+        let coefficients = self
+            .coefficients
+            .iter()
+            .map(|(symbol, coefficient)| Some((*symbol, coefficient.checked_mul(factor)?)))
+            .collect::<Option<BTreeMap<Symbol, i128>>>()?;
+
+        Some(LinearExpression {
+            constant: self.constant.checked_mul(factor)?,
+            coefficients,
+        })
+    }
+
+    // None if a symbol isn't assigned or the value overflows
+    pub fn evaluate(&self, assignment: &Assignment) -> Option<i128> {
+        self.coefficients
+            .iter()
+            .try_fold(self.constant, |acc, (symbol, coefficient)| {
+                acc.checked_add(coefficient.checked_mul(*assignment.get(symbol)?)?)
+            })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SymbolicValue {
+    Linear(LinearExpression),
+    // Non-linear or read through a symbolic address
+    Unknown,
+}
+
+impl SymbolicValue {
+    pub fn constant(value: i128) -> SymbolicValue {
+        SymbolicValue::Linear(LinearExpression::constant(value))
+    }
+
+    // An expression that overflowed is no longer known
+    fn from_expression(expression: Option<LinearExpression>) -> SymbolicValue {
+        expression.map_or(SymbolicValue::Unknown, SymbolicValue::Linear)
+    }
+
+    pub fn as_constant(&self) -> Option<i128> {
+        match self {
+            SymbolicValue::Linear(expression) => expression.as_constant(),
+            SymbolicValue::Unknown => None,
+        }
+    }
+
+    fn add(&self, other: &SymbolicValue) -> SymbolicValue {
+        match (self, other) {
+            (SymbolicValue::Linear(first), SymbolicValue::Linear(second)) => {
+                SymbolicValue::from_expression(first.add(second))
+            }
+            _ => SymbolicValue::Unknown,
+        }
+    }
+
+    fn multiply(&self, other: &SymbolicValue) -> SymbolicValue {
+        match (self, other) {
+            (SymbolicValue::Linear(first), SymbolicValue::Linear(second)) => {
+                match (first.as_constant(), second.as_constant()) {
+                    (Some(factor), _) => SymbolicValue::from_expression(second.scale(factor)),
+                    (_, Some(factor)) => SymbolicValue::from_expression(first.scale(factor)),
+                    _ => SymbolicValue::Unknown,
+                }
+            }
+            _ => SymbolicValue::Unknown,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum PathEnd {
+    Terminated,
+    StepLimitReached,
+    Unsupported(String),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct SymbolicPath {
+    end: PathEnd,
+    constraints: Vec<Constraint>,
+    outputs: Vec<SymbolicValue>,
+    memory: HashMap<u128, SymbolicValue>,
+    number_of_inputs: usize,
+    is_proven_feasible: bool,
+}
+
+impl SymbolicPath {
+    pub fn get_end(&self) -> &PathEnd {
+        &self.end
+    }
+
+    pub fn get_constraints(&self) -> &[Constraint] {
+        &self.constraints
+    }
+
+    pub fn get_outputs(&self) -> &[SymbolicValue] {
+        &self.outputs
+    }
+
+    pub fn get_memory_value(&self, address: u128) -> SymbolicValue {
+        self.memory
+            .get(&address)
+            .cloned()
+            .unwrap_or_else(|| SymbolicValue::constant(0))
+    }
+
+    pub fn get_number_of_inputs(&self) -> usize {
+        self.number_of_inputs
+    }
+
+    // False if the solver ran out of search nodes before it could show some
+    // input reaches this path.
+    pub fn is_proven_feasible(&self) -> bool {
+        self.is_proven_feasible
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SymbolicState {
+    memory: HashMap<u128, SymbolicValue>,
+    current_index: u128,
+    current_base_index: u128,
+    number_of_inputs: usize,
+    outputs: Vec<SymbolicValue>,
+    constraints: Vec<Constraint>,
+    steps: usize,
+    is_proven_feasible: bool,
+}
+
+impl SymbolicState {
+    fn into_path(self, end: PathEnd) -> SymbolicPath {
+        SymbolicPath {
+            end,
+            constraints: self.constraints,
+            outputs: self.outputs,
+            memory: self.memory,
+            number_of_inputs: self.number_of_inputs,
+            is_proven_feasible: self.is_proven_feasible,
+        }
+    }
+
+    fn read(&self, parameter: &Parameter, word: &SymbolicValue) -> Result<SymbolicValue, String> {
+        let address = match parameter {
+            Parameter::Immediate(_) => return Ok(word.clone()),
+            Parameter::Position(_) => word.as_constant(),
+            Parameter::Relative(_) => word
+                .as_constant()
+                .map(|offset| self.relative_address(offset))
+                .transpose()?,
+        };
+
+        match address {
+            Some(address) if address.is_negative() => {
+                Err(format!("Intcode index {} cannot be negative!", address))
+            }
+            Some(address) => Ok(self
+                .memory
+                .get(&(address as u128))
+                .cloned()
+                .unwrap_or_else(|| SymbolicValue::constant(0))),
+            None => Ok(SymbolicValue::Unknown),
+        }
+    }
+
+    fn write_address(&self, parameter: &Parameter, word: &SymbolicValue) -> Result<u128, String> {
+        let address = match parameter {
+            Parameter::Immediate(_) => {
+                return Err(String::from(
+                    "Cannot save a value with an immediate parameter!",
+                ))
+            }
+            Parameter::Position(_) => word.as_constant(),
+            Parameter::Relative(_) => word
+                .as_constant()
+                .map(|offset| self.relative_address(offset))
+                .transpose()?,
+        };
+
+        match address {
+            Some(address) if address.is_negative() => {
+                Err(format!("Intcode index {} cannot be negative!", address))
+            }
+            Some(address) => Ok(address as u128),
+            None => Err(format!(
+                "Write through a symbolic address at index {}",
+                self.current_index
+            )),
+        }
+    }
+
+    fn relative_address(&self, offset: i128) -> Result<i128, String> {
+        offset
+            .checked_add(self.current_base_index as i128)
+            .ok_or_else(|| format!("Relative address overflows at index {}", self.current_index))
+    }
+}
+
+enum Step {
+    Next(Vec<SymbolicState>),
+    Finished(SymbolicState, PathEnd),
+}
+
+#[derive(Debug, Clone)]
+pub struct SymbolicExecutor {
+    memory: HashMap<u128, SymbolicValue>,
+    current_index: u128,
+    current_base_index: u128,
+    solver: ConstraintSolver,
+    max_steps: usize,
+    max_paths: usize,
+}
+
+impl SymbolicExecutor {
+    pub fn new<A>(args: A) -> SymbolicExecutor
+    where
+        A: Into<IntcodeComputer>,
+    {
+        let computer = args.into();
+
+        let memory = computer
+            .current_program
+            .iter()
+            .map(|(&address, &value)| (address, SymbolicValue::constant(value)))
+            .collect();
+
+        SymbolicExecutor {
+            memory,
+            current_index: computer.current_index,
+            current_base_index: computer.current_base_index,
+            solver: ConstraintSolver::new(i64::MIN as i128, i64::MAX as i128),
+            max_steps: DEFAULT_MAX_STEPS,
+            max_paths: DEFAULT_MAX_PATHS,
+        }
+    }
+
+    // Replaces the value at the address with a Symbol::Memory(address)
+    pub fn set_symbolic_memory(&mut self, address: u128) {
+        self.memory.insert(
+            address,
+            SymbolicValue::Linear(LinearExpression::symbol(Symbol::Memory(address))),
+        );
+    }
+
+    pub fn set_domain(&mut self, symbol: Symbol, lower_bound: i128, upper_bound: i128) {
+        self.solver.set_domain(symbol, lower_bound, upper_bound);
+    }
+
+    pub fn set_max_steps(&mut self, max_steps: usize) {
+        self.max_steps = max_steps;
+    }
+
+    pub fn set_max_paths(&mut self, max_paths: usize) {
+        self.max_paths = max_paths;
+    }
+
+    pub fn set_max_search_nodes(&mut self, max_search_nodes: usize) {
+        self.solver.set_max_search_nodes(max_search_nodes);
+    }
+
+    pub fn get_solver(&self) -> &ConstraintSolver {
+        &self.solver
+    }
+
+    // Explores every feasible path depth first, up to max_paths finished paths.
+    // Forks the solver can't decide are kept, but marked as unproven.
+    pub fn explore(&self) -> Vec<SymbolicPath> {
+        let initial_state = SymbolicState {
+            memory: self.memory.clone(),
+            current_index: self.current_index,
+            current_base_index: self.current_base_index,
+            number_of_inputs: 0,
+            outputs: Vec::new(),
+            constraints: Vec::new(),
+            steps: 0,
+            is_proven_feasible: true,
+        };
+
+        let mut paths = Vec::new();
+        let mut pending_states = vec![initial_state];
+
+        while let Some(mut state) = pending_states.pop() {
+            if self.max_paths <= paths.len() {
+                break;
+            }
+
+            loop {
+                if self.max_steps <= state.steps {
+                    paths.push(state.into_path(PathEnd::StepLimitReached));
+                    break;
+                }
+
+                state.steps += 1;
+
+                match Self::step(state) {
+                    Step::Finished(finished_state, end) => {
+                        paths.push(finished_state.into_path(end));
+                        break;
+                    }
+                    Step::Next(mut next_states) => {
+                        // only one successor means no fork, so stay on this path
+                        if next_states.len() == 1 {
+                            state = next_states.pop().unwrap();
+                            continue;
+                        }
+
+                        for mut next_state in next_states.into_iter().rev() {
+                            match self.solver.solve(&next_state.constraints) {
+                                Satisfiability::Satisfiable(_) => {
+                                    next_state.is_proven_feasible = true
+                                }
+                                Satisfiability::Unsatisfiable => continue,
+                                Satisfiability::Unknown => next_state.is_proven_feasible = false,
+                            }
+
+                            pending_states.push(next_state);
+                        }
+                        break;
+                    }
+                }
+            }
+        }
+
+        paths
+    }
+
+    // Solves for the first path where `constraints_for` yields satisfiable
+    // constraints, in addition to the path's own constraints.
+    pub fn solve_for<F>(&self, constraints_for: F) -> Option<Assignment>
+    where
+        F: Fn(&SymbolicPath) -> Option<Vec<Constraint>>,
+    {
+        self.explore().iter().find_map(|path| {
+            let mut constraints = path.get_constraints().to_vec();
+
+            constraints.extend(constraints_for(path)?);
+
+            self.solver.solve(&constraints).get_assignment()
+        })
+    }
+
+    pub fn find_inputs_for_outputs(&self, target_outputs: &[i128]) -> Option<Assignment> {
+        self.solve_for(|path| {
+            if *path.get_end() != PathEnd::Terminated
+                || path.get_outputs().len() != target_outputs.len()
+            {
+                return None;
+            }
+
+            path.get_outputs()
+                .iter()
+                .zip(target_outputs)
+                .map(|(output, &target)| match output {
+                    SymbolicValue::Linear(expression) => {
+                        Constraint::equals(expression, &LinearExpression::constant(target))
+                    }
+                    SymbolicValue::Unknown => None,
+                })
+                .collect()
+        })
+    }
+
+    pub fn find_memory_value(&self, address: u128, target: i128) -> Option<Assignment> {
+        self.solve_for(|path| {
+            if *path.get_end() != PathEnd::Terminated {
+                return None;
+            }
+
+            match path.get_memory_value(address) {
+                SymbolicValue::Linear(expression) => Some(vec![Constraint::equals(
+                    &expression,
+                    &LinearExpression::constant(target),
+                )?]),
+                SymbolicValue::Unknown => None,
+            }
+        })
+    }
+
+    // Executes one instruction. A second state is returned when the path forks
+    // on a symbolic comparison or jump.
+    fn step(mut state: SymbolicState) -> Step {
+        let (opcode, words) = match Self::decode(&state) {
+            Ok(decoded) => decoded,
+            Err(error) => return Step::Finished(state, PathEnd::Unsupported(error)),
+        };
+
+        let result = match &opcode {
+            Opcode::Add(first_parameter, second_parameter, third_parameter)
+            | Opcode::Multiply(first_parameter, second_parameter, third_parameter) => {
+                Self::execute_arithmetic(
+                    &mut state,
+                    &opcode,
+                    [first_parameter, second_parameter, third_parameter],
+                    &words,
+                )
+            }
+            Opcode::SaveInput(_, first_parameter) => {
+                Self::execute_save_input(&mut state, first_parameter, &words)
+            }
+            Opcode::Output(first_parameter) => {
+                Self::execute_output(&mut state, first_parameter, &words)
+            }
+            Opcode::JumpIfTrue(first_parameter, second_parameter) => Self::execute_jump(
+                &mut state,
+                true,
+                [first_parameter, second_parameter],
+                &words,
+            ),
+            Opcode::JumpIfFalse(first_parameter, second_parameter) => Self::execute_jump(
+                &mut state,
+                false,
+                [first_parameter, second_parameter],
+                &words,
+            ),
+            Opcode::StoreIfLessThan(first_parameter, second_parameter, third_parameter)
+            | Opcode::StoreIfEquals(first_parameter, second_parameter, third_parameter) => {
+                Self::execute_comparison(
+                    &mut state,
+                    &opcode,
+                    [first_parameter, second_parameter, third_parameter],
+                    &words,
+                )
+            }
+            Opcode::AdjustRelativeBase(first_parameter) => {
+                Self::execute_adjust_relative_base(&mut state, first_parameter, &words)
+            }
+            Opcode::Terminate => return Step::Finished(state, PathEnd::Terminated),
+        };
+
+        match result {
+            Ok(Some(forked_state)) => Step::Next(vec![state, forked_state]),
+            Ok(None) => Step::Next(vec![state]),
+            Err(error) => Step::Finished(state, PathEnd::Unsupported(error)),
+        }
+    }
+
+    // Decodes the instruction at the current index with Opcode::new, keeping the
+    // raw (possibly symbolic) parameter words alongside it. Instructions that
+    // Opcode::new would panic on are reported as errors instead.
+    fn decode(state: &SymbolicState) -> Result<(Opcode, Vec<SymbolicValue>), String> {
+        let index = state.current_index;
+
+        let instruction = match state.memory.get(&index).map(SymbolicValue::as_constant) {
+            Some(Some(instruction)) => instruction,
+            Some(None) => return Err(format!("Symbolic instruction at index {}", index)),
+            None => return Err(format!("Index {} is outside of program memory", index)),
+        };
+
+        let number_of_parameters = match Opcode::instruction_length(instruction) {
+            Some(length) => length - 1,
+            None => {
+                return Err(format!(
+                    "Unexpected instruction given (instruction, index): {:?}",
+                    (instruction, index)
+                ))
+            }
+        };
+
+        let mut window = HashMap::new();
+        let mut words = Vec::new();
+
+        window.insert(index, instruction);
+
+        for offset in 1..=number_of_parameters {
+            let word = match state.memory.get(&(index + offset)) {
+                Some(word) => word.clone(),
+                None => {
+                    return Err(format!(
+                        "Index {} is outside of program memory",
+                        index + offset
+                    ))
+                }
+            };
+
+            window.insert(index + offset, word.as_constant().unwrap_or(0));
+            words.push(word);
+        }
+
+        Ok((Opcode::new(0, &window, index), words))
+    }
+
+    fn execute_arithmetic(
+        state: &mut SymbolicState,
+        opcode: &Opcode,
+        parameters: [&Parameter; 3],
+        words: &[SymbolicValue],
+    ) -> Result<Option<SymbolicState>, String> {
+        let first_value = state.read(parameters[0], &words[0])?;
+        let second_value = state.read(parameters[1], &words[1])?;
+        let save_index = state.write_address(parameters[2], &words[2])?;
+
+        let value = match opcode {
+            Opcode::Add(_, _, _) => first_value.add(&second_value),
+            _ => first_value.multiply(&second_value),
+        };
+
+        state.memory.insert(save_index, value);
+        state.current_index += 4;
+
+        Ok(None)
+    }
+
+    fn execute_save_input(
+        state: &mut SymbolicState,
+        parameter: &Parameter,
+        words: &[SymbolicValue],
+    ) -> Result<Option<SymbolicState>, String> {
+        let save_index = state.write_address(parameter, &words[0])?;
+        let input = LinearExpression::symbol(Symbol::Input(state.number_of_inputs));
+
+        state
+            .memory
+            .insert(save_index, SymbolicValue::Linear(input));
+        state.number_of_inputs += 1;
+        state.current_index += 2;
+
+        Ok(None)
+    }
+
+    fn execute_output(
+        state: &mut SymbolicState,
+        parameter: &Parameter,
+        words: &[SymbolicValue],
+    ) -> Result<Option<SymbolicState>, String> {
+        let output = state.read(parameter, &words[0])?;
+
+        state.outputs.push(output);
+        state.current_index += 2;
+
+        Ok(None)
+    }
+
+    fn execute_jump(
+        state: &mut SymbolicState,
+        jump_if_true: bool,
+        parameters: [&Parameter; 2],
+        words: &[SymbolicValue],
+    ) -> Result<Option<SymbolicState>, String> {
+        let condition = match state.read(parameters[0], &words[0])? {
+            SymbolicValue::Linear(expression) => expression,
+            SymbolicValue::Unknown => {
+                return Err(format!(
+                    "Jump on an unknown value at index {}",
+                    state.current_index
+                ))
+            }
+        };
+
+        let target = state.read(parameters[1], &words[1])?;
+
+        if let Some(value) = condition.as_constant() {
+            if (value != 0) != jump_if_true {
+                state.current_index += 3;
+
+                return Ok(None);
+            }
+        }
+
+        let jump_index = match target.as_constant() {
+            Some(index) if !index.is_negative() => index as u128,
+            _ => {
+                return Err(format!(
+                    "Jump to a symbolic or negative index at {}",
+                    state.current_index
+                ))
+            }
+        };
+
+        if condition.as_constant().is_some() {
+            state.current_index = jump_index;
+
+            return Ok(None);
+        }
+
+        let (jump_relation, no_jump_relation) = if jump_if_true {
+            (Relation::NotEqual, Relation::Equal)
+        } else {
+            (Relation::Equal, Relation::NotEqual)
+        };
+
+        let jump_constraint = Constraint::new(condition.clone(), jump_relation);
+        let no_jump_constraint = Constraint::new(condition, no_jump_relation);
+
+        let mut no_jump_state = state.clone();
+
+        no_jump_state.constraints.push(no_jump_constraint);
+        no_jump_state.current_index += 3;
+
+        state.constraints.push(jump_constraint);
+        state.current_index = jump_index;
+
+        Ok(Some(no_jump_state))
+    }
+
+    fn execute_comparison(
+        state: &mut SymbolicState,
+        opcode: &Opcode,
+        parameters: [&Parameter; 3],
+        words: &[SymbolicValue],
+    ) -> Result<Option<SymbolicState>, String> {
+        let first_value = state.read(parameters[0], &words[0])?;
+        let second_value = state.read(parameters[1], &words[1])?;
+        let save_index = state.write_address(parameters[2], &words[2])?;
+
+        state.current_index += 4;
+
+        let difference = match (first_value, second_value) {
+            (SymbolicValue::Linear(first), SymbolicValue::Linear(second)) => first.sub(&second),
+            _ => None,
+        };
+
+        let difference = match difference {
+            Some(difference) => difference,
+            None => {
+                state.memory.insert(save_index, SymbolicValue::Unknown);
+
+                return Ok(None);
+            }
+        };
+
+        let is_less_than = matches!(opcode, Opcode::StoreIfLessThan(_, _, _));
+
+        if let Some(value) = difference.as_constant() {
+            let is_success = if is_less_than { value < 0 } else { value == 0 };
+
+            state
+                .memory
+                .insert(save_index, SymbolicValue::constant(is_success as i128));
+
+            return Ok(None);
+        }
+
+        let (success_relation, failure_relation) = if is_less_than {
+            (Relation::LessThan, Relation::GreaterOrEqual)
+        } else {
+            (Relation::Equal, Relation::NotEqual)
+        };
+
+        let success_constraint = Constraint::new(difference.clone(), success_relation);
+        let failure_constraint = Constraint::new(difference, failure_relation);
+
+        let mut failure_state = state.clone();
+
+        failure_state.constraints.push(failure_constraint);
+        failure_state
+            .memory
+            .insert(save_index, SymbolicValue::constant(0));
+
+        state.constraints.push(success_constraint);
+        state.memory.insert(save_index, SymbolicValue::constant(1));
+
+        Ok(Some(failure_state))
+    }
+
+    fn execute_adjust_relative_base(
+        state: &mut SymbolicState,
+        parameter: &Parameter,
+        words: &[SymbolicValue],
+    ) -> Result<Option<SymbolicState>, String> {
+        let adjustment = match state.read(parameter, &words[0])?.as_constant() {
+            Some(adjustment) => adjustment,
+            None => return Err(String::from("Symbolic relative base adjustment")),
+        };
+
+        let next_base_index = match (state.current_base_index as i128).checked_add(adjustment) {
+            Some(next_base_index) => next_base_index,
+            None => return Err(String::from("Relative base overflows")),
+        };
+
+        if next_base_index.is_negative() {
+            return Err(format!(
+                "Relative base {} cannot be negative!",
+                next_base_index
+            ));
+        }
+
+        state.current_base_index = next_base_index as u128;
+        state.current_index += 2;
+
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // outputs input * 3 + 4
+    const LINEAR_PROGRAM: [i128; 15] = [3, 13, 1002, 13, 3, 14, 1001, 14, 4, 14, 4, 14, 99, 0, 0];
+    // outputs 1 if input == 8, otherwise 0
+    const EQUALS_PROGRAM: [i128; 11] = [3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8];
+    // memory[0] = noun * 5 + verb + 7, with noun and verb at 1 and 2
+    const NOUN_VERB_PROGRAM: [i128; 21] = [
+        1, 0, 0, 3, 1, 1, 2, 3, 1002, 1, 4, 0, 1, 0, 3, 0, 1001, 0, 7, 0, 99,
+    ];
+
+    #[test]
+    fn test_linear_expression_arithmetic() {
+        let x = LinearExpression::symbol(Symbol::Input(0));
+        let y = LinearExpression::symbol(Symbol::Input(1));
+
+        let expression = x
+            .scale(3)
+            .and_then(|x_3| x_3.add(&y))
+            .and_then(|sum| sum.sub(&x.scale(3)?))
+            .unwrap();
+
+        let expected = y.clone();
+
+        assert_eq!(expression, expected);
+        assert_eq!(expression.sub(&y).unwrap().as_constant(), Some(0));
+    }
+
+    #[test]
+    fn test_linear_expression_overflow() {
+        let x = LinearExpression::symbol(Symbol::Input(0));
+        let big = x.scale(i128::MAX).unwrap();
+
+        assert_eq!(big.scale(2), None);
+        assert_eq!(big.add(&x), None);
+        assert_eq!(
+            LinearExpression::constant(1).sub(&LinearExpression::constant(i128::MIN)),
+            None
+        );
+
+        let assignment: Assignment = vec![(Symbol::Input(0), 2)].into_iter().collect();
+
+        assert_eq!(big.evaluate(&assignment), None);
+    }
+
+    #[test]
+    fn test_overflowing_output_is_unknown() {
+        let factor: i128 = 1 << 100;
+        // multiplies the input by 2^100 twice, then outputs it
+        let program = [
+            3, 13, 1002, 13, factor, 13, 1002, 13, factor, 13, 4, 13, 99, 0,
+        ];
+        let executor = SymbolicExecutor::new(program.as_ref());
+
+        let paths = executor.explore();
+
+        assert_eq!(paths[0].get_end(), &PathEnd::Terminated);
+        assert_eq!(paths[0].get_outputs(), &[SymbolicValue::Unknown]);
+    }
+
+    #[test]
+    fn test_explore_linear_program() {
+        let executor = SymbolicExecutor::new(LINEAR_PROGRAM.as_ref());
+
+        let expected_output = SymbolicValue::Linear(
+            LinearExpression::symbol(Symbol::Input(0))
+                .scale(3)
+                .and_then(|expression| expression.add(&LinearExpression::constant(4)))
+                .unwrap(),
+        );
+
+        let paths = executor.explore();
+
+        assert_eq!(paths.len(), 1);
+        assert_eq!(*paths[0].get_end(), PathEnd::Terminated);
+        assert_eq!(paths[0].get_outputs(), &[expected_output]);
+        assert_eq!(paths[0].get_number_of_inputs(), 1);
+    }
+
+    #[test]
+    fn test_find_inputs_for_outputs() {
+        let executor = SymbolicExecutor::new(LINEAR_PROGRAM.as_ref());
+
+        let result = executor.find_inputs_for_outputs(&[19]).unwrap();
+
+        assert_eq!(result[&Symbol::Input(0)], 5);
+    }
+
+    #[test]
+    fn test_explore_forks_on_comparison() {
+        let executor = SymbolicExecutor::new(EQUALS_PROGRAM.as_ref());
+
+        let paths = executor.explore();
+
+        let outputs: Vec<Option<i128>> = paths
+            .iter()
+            .map(|path| path.get_outputs()[0].as_constant())
+            .collect();
+
+        assert_eq!(outputs, vec![Some(1), Some(0)]);
+    }
+
+    #[test]
+    fn test_explore_keeps_undecided_forks() {
+        // outputs 1 if 2 * first input + 2 * second input == 7, otherwise 0
+        let program = [
+            3, 19, 3, 20, 1, 19, 20, 21, 1002, 21, 2, 21, 1008, 21, 7, 21, 4, 21, 99, 0, 0, 0,
+        ];
+        let mut executor = SymbolicExecutor::new(program.as_ref());
+
+        executor.set_max_search_nodes(1_000);
+
+        let paths = executor.explore();
+
+        let result: Vec<(Option<i128>, bool)> = paths
+            .iter()
+            .map(|path| {
+                (
+                    path.get_outputs()[0].as_constant(),
+                    path.is_proven_feasible(),
+                )
+            })
+            .collect();
+
+        assert_eq!(result, vec![(Some(1), false), (Some(0), true)]);
+    }
+
+    #[test]
+    fn test_find_inputs_for_branching_outputs() {
+        let executor = SymbolicExecutor::new(EQUALS_PROGRAM.as_ref());
+
+        let equal_result = executor.find_inputs_for_outputs(&[1]).unwrap();
+        let not_equal_result = executor.find_inputs_for_outputs(&[0]).unwrap();
+
+        assert_eq!(equal_result[&Symbol::Input(0)], 8);
+        assert_ne!(not_equal_result[&Symbol::Input(0)], 8);
+    }
+
+    #[test]
+    fn test_find_memory_value_noun_verb() {
+        let target = 101;
+        let mut executor = SymbolicExecutor::new(NOUN_VERB_PROGRAM.as_ref());
+
+        for address in 1..=2 {
+            executor.set_symbolic_memory(address);
+            executor.set_domain(Symbol::Memory(address), 0, 99);
+        }
+
+        let result = executor.find_memory_value(0, target).unwrap();
+
+        let noun = result[&Symbol::Memory(1)];
+        let verb = result[&Symbol::Memory(2)];
+
+        let mut intcode_computer = IntcodeComputer::new(NOUN_VERB_PROGRAM.as_ref());
+
+        intcode_computer.replace_code_in_program(1, noun as i32);
+        intcode_computer.replace_code_in_program(2, verb as i32);
+        intcode_computer.execute_program();
+
+        assert!((0..=99).contains(&noun) && (0..=99).contains(&verb));
+        assert_eq!(intcode_computer.get_current_memory()[&0], target);
+    }
+
+    #[test]
+    fn test_non_linear_output_is_unknown() {
+        // outputs input * input
+        let program = [3, 9, 2, 9, 9, 10, 4, 10, 99, 0, 0];
+        let executor = SymbolicExecutor::new(program.as_ref());
+
+        let paths = executor.explore();
+
+        assert_eq!(paths[0].get_outputs(), &[SymbolicValue::Unknown]);
+        assert!(executor.find_inputs_for_outputs(&[16]).is_none());
+    }
+
+    #[test]
+    fn test_symbolic_write_address_is_unsupported() {
+        // saves 0 at the address given as input
+        let program = [3, 5, 1101, 0, 0, 0, 99];
+        let executor = SymbolicExecutor::new(program.as_ref());
+
+        let paths = executor.explore();
+
+        assert!(matches!(paths[0].get_end(), PathEnd::Unsupported(_)));
+    }
+
+    #[test]
+    fn test_malformed_instructions_are_unsupported() {
+        // an add with parameter mode 3, and an add that writes to an immediate
+        for program in [[301, 0, 0, 0, 99], [11101, 0, 0, 0, 99]].iter() {
+            let executor = SymbolicExecutor::new(program.as_ref());
+
+            let paths = executor.explore();
+
+            assert!(matches!(paths[0].get_end(), PathEnd::Unsupported(_)));
+        }
+    }
+
+    #[test]
+    fn test_relative_address_overflow_is_unsupported() {
+        // moves the relative base to i128::MAX, then reads or adjusts past it
+        let read_program = [109, i128::MAX, 204, 1, 99];
+        let adjust_program = [109, i128::MAX, 109, 1, 99];
+
+        for program in [read_program, adjust_program].iter() {
+            let executor = SymbolicExecutor::new(program.as_ref());
+
+            let paths = executor.explore();
+
+            assert!(matches!(paths[0].get_end(), PathEnd::Unsupported(_)));
+        }
+    }
+
+    #[test]
+    fn test_step_limit() {
+        // jumps to itself forever
+        let program = [1105, 1, 0];
+        let mut executor = SymbolicExecutor::new(program.as_ref());
+
+        executor.set_max_steps(10);
+
+        let paths = executor.explore();
+
+        assert_eq!(*paths[0].get_end(), PathEnd::StepLimitReached);
+    }
+}
@@ -0,0 +1,51 @@
+// The original tests predate clippy and are kept as they were written.
+#![cfg_attr(
+    test,
+    allow(
+        clippy::get_first,
+        clippy::map_clone,
+        clippy::redundant_closure,
+        clippy::redundant_field_names,
+        clippy::redundant_static_lifetimes,
+        clippy::too_many_arguments,
+        clippy::unnecessary_to_owned,
+        clippy::unused_unit,
+        clippy::zero_prefixed_literal
+    )
+)]
+
+pub mod benchmark;
+pub mod cli;
+pub mod compression;
+pub mod cycle;
+pub mod file_reader;
+pub mod graph_search;
+pub mod grid;
+pub mod intcode_computer;
+pub mod location;
+pub mod math;
+pub mod output;
+pub mod scaffold;
+pub mod solution;
+pub mod verify;
+
+pub mod day_1;
+pub mod day_10;
+pub mod day_11;
+pub mod day_12;
+pub mod day_13;
+pub mod day_14;
+pub mod day_15;
+pub mod day_16;
+pub mod day_17;
+pub mod day_18;
+pub mod day_2;
+pub mod day_3;
+pub mod day_4;
+pub mod day_5;
+pub mod day_6;
+pub mod day_7;
+pub mod day_8;
+pub mod day_9;
+
+pub use intcode_computer::{IntcodeComputer, IntcodeComputerStatus};
@@ -1,91 +1,165 @@
-use std::io::{self, Write};
-
-pub mod file_reader;
-pub mod intcode_computer;
-pub mod location;
-
-mod day_1;
-mod day_10;
-mod day_11;
-mod day_12;
-mod day_13;
-mod day_14;
-mod day_2;
-mod day_3;
-mod day_4;
-mod day_5;
-mod day_6;
-mod day_7;
-mod day_8;
-mod day_9;
+use std::env;
+use std::path::Path;
+use std::process;
+
+use adventofcode2019::benchmark::{self, BenchmarkReport};
+use adventofcode2019::cli::{self, BenchOptions, Command, RunOptions};
+use adventofcode2019::file_reader::PuzzleInput;
+use adventofcode2019::output::{self, OutputFormat, PartResult};
+use adventofcode2019::scaffold;
+use adventofcode2019::solution::{self, DaySolution};
+use adventofcode2019::verify::{self, Answers};
 
 fn print_seperator() {
     println!("-------------------------------");
 }
 
-fn run_day(day: i128) {
-    match day {
-        1 => day_1::run_day_1(),
-        2 => day_2::run_day_2(),
-        3 => day_3::run_day_3(),
-        4 => day_4::run_day_4(),
-        5 => day_5::run_day_5(),
-        6 => day_6::run_day_6(),
-        7 => day_7::run_day_7(),
-        8 => day_8::run_day_8(),
-        9 => day_9::run_day_9(),
-        10 => day_10::run_day_10(),
-        11 => day_11::run_day_11(),
-        12 => day_12::run_day_12(),
-        13 => day_13::run_day_13(),
-        14 => day_14::run_day_14(),
-        _ => unimplemented!("I haven't done that day yet :("),
+// Failures are reported in the results rather than stopping the run.
+fn run_day(mut solution: Box<dyn DaySolution>, options: &RunOptions) -> Vec<PartResult> {
+    let day = solution.get_day();
+    let default_input_file = solution::get_default_input_file(day);
+    let input_file = options.get_input_file(&default_input_file);
+    let parts: Vec<u8> = (1..=2)
+        .filter(|&part| options.includes_part(part))
+        .collect();
+
+    let prepared_solution = solution
+        .configure(options)
+        .and_then(|_| Ok(PuzzleInput::from_path_or_stdin(input_file)?))
+        .and_then(|input| solution.prepare(&input));
+
+    match prepared_solution {
+        Ok(prepared_solution) => parts
+            .iter()
+            .map(|&part| output::solve_part(day, part, prepared_solution.as_ref()))
+            .collect(),
+        Err(error) => parts
+            .iter()
+            .map(|&part| PartResult::failed(day, part, &error))
+            .collect(),
     }
 }
 
-pub fn get_user_input() -> i128 {
-    let mut input_buffer = String::new();
+fn run_days(solutions: Vec<Box<dyn DaySolution>>, options: &RunOptions) -> Result<(), String> {
+    let mut results = Vec::new();
 
-    io::stdout().flush().expect("Could not flush stdout!");
+    for solution in solutions {
+        let day_results = run_day(solution, options);
 
-    io::stdin()
-        .read_line(&mut input_buffer)
-        .expect("Failed to read user input!");
+        if options.format == OutputFormat::Text {
+            print_seperator();
+            println!("{}", output::format_results(&day_results, options.format));
+        }
+
+        results.extend(day_results);
+    }
+
+    if options.format == OutputFormat::Text {
+        print_seperator();
+    } else {
+        println!("{}", output::format_results(&results, options.format));
+    }
 
-    input_buffer
-        .trim()
-        .parse::<i128>()
-        .expect("Failed to parse user_input!")
+    if results.iter().all(|result| result.answer.is_ok()) {
+        Ok(())
+    } else {
+        Err(String::from("Not every part ran successfully"))
+    }
 }
 
-pub fn gcd(a: u128, b: u128) -> u128 {
-    match ((a, b), (a & 1, b & 1)) {
-        ((x, y), _) if x == y => y,
-        ((0, x), _) | ((x, 0), _) => x,
-        ((x, y), (0, 1)) | ((y, x), (1, 0)) => gcd(x >> 1, y),
-        ((x, y), (0, 0)) => gcd(x >> 1, y >> 1) << 1,
-        ((x, y), (1, 1)) => {
-            let (x, y) = (x.min(y), x.max(y));
-            gcd((y - x) >> 1, x)
+fn bench(options: &BenchOptions) -> Result<(), String> {
+    let solutions: Vec<Box<dyn DaySolution>> = match options.day {
+        Some(day) => match solution::find_solution(day) {
+            Some(solution) => vec![solution],
+            None => return Err(format!("Day {} hasn't been done yet :(", day)),
+        },
+        None => {
+            // days without an input yet can't be timed, so they're left out
+            let (solutions, missing_days) = solution::partition_by_input(
+                solution::get_solutions(),
+                solution::get_default_input_file,
+            );
+
+            for day in missing_days {
+                eprintln!(
+                    "Skipping day {}, {} doesn't exist",
+                    day,
+                    solution::get_default_input_file(day)
+                );
+            }
+
+            solutions
         }
-        _ => panic!("GCD made it to an unreachable state!"),
+    };
+
+    let mut report = BenchmarkReport::new(options.runs);
+
+    for solution in solutions {
+        let input_file = solution::get_default_input_file(solution.get_day());
+
+        let input = PuzzleInput::from_file(&input_file)?;
+
+        report.add_timings(&benchmark::benchmark_day(
+            solution.as_ref(),
+            &input,
+            options.runs,
+        )?);
     }
-}
 
-pub fn lcm(a: u128, b: u128) -> u128 {
-    a * b / gcd(a, b)
+    if options.json {
+        println!("{}", report.to_json());
+    } else {
+        println!("{}", report);
+    }
+
+    Ok(())
 }
 
 fn main() {
-    print_seperator();
-
-    print!("Please choose a day to run (1-25): ");
-
-    let input = get_user_input();
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let result = match cli::parse_args(&args) {
+        Ok(Command::Run(day, options)) => match solution::find_solution(day) {
+            Some(solution) => run_days(vec![solution], &options),
+            None => Err(format!("Day {} hasn't been done yet :(", day)),
+        },
+        Ok(Command::RunAll(options)) => run_days(solution::get_solutions(), &options),
+        Ok(Command::Verify(answers_file)) => Answers::load(&answers_file).and_then(|answers| {
+            let report = verify::verify_all(
+                &solution::get_solutions(),
+                &answers,
+                solution::get_default_input_file,
+            );
+
+            println!("{}", report);
+
+            if report.is_success() {
+                Ok(())
+            } else {
+                Err(String::from("Some answers didn't match"))
+            }
+        }),
+        Ok(Command::Bench(options)) => bench(&options),
+        Ok(Command::NewDay(day)) => scaffold::create_day(Path::new("."), day).map(|files| {
+            for file in files {
+                println!("Wrote {}", file);
+            }
+        }),
+        Ok(Command::Help) => {
+            println!("{}", cli::USAGE);
+
+            Ok(())
+        }
+        Err(error) => {
+            eprintln!("{}\n\n{}", error, cli::USAGE);
 
-    print_seperator();
+            process::exit(2);
+        }
+    };
 
-    run_day(input);
+    if let Err(error) = result {
+        eprintln!("{}", error);
 
-    print_seperator();
+        process::exit(1);
+    }
 }
@@ -0,0 +1,318 @@
+use std::fmt::Debug;
+use std::ops::{Add, Div, Mul, Rem, Sub};
+
+// The integer operations gcd and lcm need, so they work for every primitive
+// integer type.
+pub trait Integer:
+    Copy
+    + Ord
+    + Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_mul(self, other: Self) -> Option<Self>;
+
+    fn checked_rem(self, other: Self) -> Option<Self>;
+
+    fn checked_abs(self) -> Option<Self>;
+}
+
+macro_rules! impl_unsigned_integer {
+    ($($t:ty),*) => {
+        $(impl Integer for $t {
+            const ZERO: $t = 0;
+            const ONE: $t = 1;
+
+            fn checked_mul(self, other: $t) -> Option<$t> {
+                <$t>::checked_mul(self, other)
+            }
+
+            fn checked_rem(self, other: $t) -> Option<$t> {
+                <$t>::checked_rem(self, other)
+            }
+
+            fn checked_abs(self) -> Option<$t> {
+                Some(self)
+            }
+        })*
+    };
+}
+
+macro_rules! impl_signed_integer {
+    ($($t:ty),*) => {
+        $(impl Integer for $t {
+            const ZERO: $t = 0;
+            const ONE: $t = 1;
+
+            fn checked_mul(self, other: $t) -> Option<$t> {
+                <$t>::checked_mul(self, other)
+            }
+
+            fn checked_rem(self, other: $t) -> Option<$t> {
+                <$t>::checked_rem(self, other)
+            }
+
+            fn checked_abs(self) -> Option<$t> {
+                <$t>::checked_abs(self)
+            }
+        })*
+    };
+}
+
+impl_unsigned_integer!(u8, u16, u32, u64, u128, usize);
+impl_signed_integer!(i8, i16, i32, i64, i128, isize);
+
+// Always non-negative, and gcd(0, 0) is 0. None if a signed T overflows on
+// the way, which only happens when T::MIN is involved.
+pub fn gcd<T: Integer>(a: T, b: T) -> Option<T> {
+    let (mut a, mut b) = (a, b);
+
+    while b != T::ZERO {
+        let remainder = a.checked_rem(b)?;
+
+        a = b;
+        b = remainder;
+    }
+
+    a.checked_abs()
+}
+
+// None if the result doesn't fit in T.
+pub fn lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+
+    (a / gcd(a, b)?).checked_mul(b)?.checked_abs()
+}
+
+// How many steps until every cycle is back at its start at the same time,
+// e.g. each axis of the day 12 moons.
+pub fn combine_cycle_lengths<T: Integer>(cycle_lengths: &[T]) -> Option<T> {
+    cycle_lengths
+        .iter()
+        .try_fold(T::ONE, |combined, &cycle_length| {
+            lcm(combined, cycle_length)
+        })
+}
+
+// Returns (g, x, y) with a * x + b * y = g = gcd(a, b).
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let quotient = old_r / r;
+
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+// (a * b) mod m without overflowing, for any positive modulus that fits in i128.
+// None if the modulus isn't positive.
+pub fn mod_mul(a: i128, b: i128, modulus: i128) -> Option<i128> {
+    if modulus <= 0 {
+        return None;
+    }
+
+    let (a, b) = (a.rem_euclid(modulus), b.rem_euclid(modulus));
+
+    if let Some(product) = a.checked_mul(b) {
+        return Some(product % modulus);
+    }
+
+    // Double and add, so every intermediate value stays below 2 * modulus
+    let (mut a, mut b) = (a as u128, b as u128);
+    let modulus = modulus as u128;
+    let mut product = 0;
+
+    while b > 0 {
+        if b & 1 == 1 {
+            product = (product + a) % modulus;
+        }
+
+        a = (a << 1) % modulus;
+        b >>= 1;
+    }
+
+    Some(product as i128)
+}
+
+// None if the modulus isn't positive.
+pub fn mod_pow(base: i128, exponent: u128, modulus: i128) -> Option<i128> {
+    if modulus <= 0 {
+        return None;
+    }
+
+    let mut base = base.rem_euclid(modulus);
+    let mut exponent = exponent;
+    let mut result = 1 % modulus;
+
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mod_mul(result, base, modulus)?;
+        }
+
+        base = mod_mul(base, base, modulus)?;
+        exponent >>= 1;
+    }
+
+    Some(result)
+}
+
+// None unless a and the modulus are coprime.
+pub fn mod_inverse(a: i128, modulus: i128) -> Option<i128> {
+    if modulus <= 0 {
+        return None;
+    }
+
+    match extended_gcd(a.rem_euclid(modulus), modulus) {
+        (1, x, _) => Some(x.rem_euclid(modulus)),
+        _ => None,
+    }
+}
+
+// Solves x = residue (mod modulus) for every (residue, modulus) pair. The moduli
+// don't need to be coprime. Returns the smallest non-negative x along with the
+// lcm of the moduli, or None if there's no solution.
+pub fn chinese_remainder(congruences: &[(i128, i128)]) -> Option<(i128, i128)> {
+    congruences
+        .iter()
+        .try_fold((0, 1), |(x, combined_modulus), &(residue, modulus)| {
+            if modulus <= 0 {
+                return None;
+            }
+
+            let g = gcd(combined_modulus, modulus)?;
+            let difference = residue.rem_euclid(modulus) - x;
+
+            if difference % g != 0 {
+                return None;
+            }
+
+            let reduced_modulus = modulus / g;
+            let k = mod_mul(
+                difference / g,
+                mod_inverse(combined_modulus / g, reduced_modulus)?,
+                reduced_modulus,
+            )?;
+            let new_modulus = lcm(combined_modulus, modulus)?;
+
+            let step = mod_mul(combined_modulus, k, new_modulus)?;
+
+            // x + step, without going past i128::MAX
+            let x = if x >= new_modulus - step {
+                x - (new_modulus - step)
+            } else {
+                x + step
+            };
+
+            Some((x, new_modulus))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd() {
+        assert_eq!(gcd(48_u128, 18), Some(6));
+        assert_eq!(gcd(-48_i32, 18), Some(6));
+        assert_eq!(gcd(0_u8, 7), Some(7));
+        assert_eq!(gcd(0_i64, 0), Some(0));
+        assert_eq!(gcd(i32::MIN, 2), Some(2));
+    }
+
+    #[test]
+    fn test_gcd_overflow() {
+        assert_eq!(gcd(i64::MIN, 0), None);
+        assert_eq!(gcd(i32::MIN, -1), None);
+        assert_eq!(gcd(i8::MIN, i8::MIN), None);
+    }
+
+    #[test]
+    fn test_lcm() {
+        assert_eq!(lcm(4_u32, 6), Some(12));
+        assert_eq!(lcm(-4_i32, 6), Some(12));
+        assert_eq!(lcm(0_u64, 6), Some(0));
+        assert_eq!(lcm(200_u8, 3), None);
+        assert_eq!(lcm(u128::MAX, u128::MAX), Some(u128::MAX));
+        assert_eq!(lcm(i32::MIN, 1), None);
+        assert_eq!(lcm(i32::MIN, 0), Some(0));
+    }
+
+    #[test]
+    fn test_combine_cycle_lengths() {
+        assert_eq!(combine_cycle_lengths(&[18_u128, 28, 44]), Some(2772));
+        assert_eq!(combine_cycle_lengths::<u64>(&[]), Some(1));
+        assert_eq!(combine_cycle_lengths(&[250_u8, 3]), None);
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        let (g, x, y) = extended_gcd(240, 46);
+
+        assert_eq!(g, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+        assert_eq!(extended_gcd(-6, 4).0, 2);
+    }
+
+    #[test]
+    fn test_mod_mul() {
+        let modulus = 119_315_717_514_047;
+
+        assert_eq!(mod_mul(-3, 5, 7), Some(6));
+        assert_eq!(mod_mul(i128::MAX - 1, i128::MAX - 1, i128::MAX), Some(1));
+        assert_eq!(mod_mul(modulus - 1, modulus - 1, modulus), Some(1));
+        assert_eq!(mod_mul(i128::MIN, i128::MIN, 7), Some(4));
+        assert_eq!(mod_mul(3, 5, 0), None);
+        assert_eq!(mod_mul(3, 5, -7), None);
+    }
+
+    #[test]
+    fn test_mod_pow() {
+        assert_eq!(mod_pow(4, 13, 497), Some(445));
+        assert_eq!(mod_pow(-2, 3, 5), Some(2));
+        assert_eq!(mod_pow(5, 0, 1), Some(0));
+        assert_eq!(mod_pow(2, 127, i128::MAX), Some(1));
+        assert_eq!(mod_pow(i128::MIN, 2, 3), Some(1));
+        assert_eq!(mod_pow(2, 3, 0), None);
+        assert_eq!(mod_pow(2, 3, -5), None);
+    }
+
+    #[test]
+    fn test_mod_inverse() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6, 9), None);
+        assert_eq!(mod_inverse(3, 0), None);
+    }
+
+    #[test]
+    fn test_chinese_remainder() {
+        assert_eq!(
+            chinese_remainder(&[(2, 3), (3, 5), (2, 7)]),
+            Some((23, 105))
+        );
+        assert_eq!(chinese_remainder(&[(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(chinese_remainder(&[(1, 4), (2, 6)]), None);
+        assert_eq!(chinese_remainder(&[(-1, 5)]), Some((4, 5)));
+        assert_eq!(chinese_remainder(&[]), Some((0, 1)));
+    }
+}
@@ -0,0 +1,251 @@
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use crate::solution::PreparedSolution;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<OutputFormat, String> {
+        match format {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "csv" => Ok(OutputFormat::Csv),
+            _ => Err(format!(
+                "Format should be text, json or csv, got {}",
+                format
+            )),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PartResult {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Result<String, String>,
+    pub runtime: Duration,
+}
+
+impl PartResult {
+    // Used when a day fails before any part can run, e.g. a missing input
+    pub fn failed(day: u8, part: u8, error: &str) -> PartResult {
+        PartResult {
+            day,
+            part,
+            answer: Err(String::from(error)),
+            runtime: Duration::default(),
+        }
+    }
+
+    // Rendered answers (days 8 and 11) have one entry per row.
+    pub fn get_lines(&self) -> Vec<&str> {
+        match &self.answer {
+            Ok(answer) => answer.lines().collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+}
+
+pub fn solve_part(day: u8, part: u8, prepared_solution: &dyn PreparedSolution) -> PartResult {
+    let start = Instant::now();
+    let answer = prepared_solution.solve(part);
+
+    PartResult {
+        day,
+        part,
+        answer,
+        runtime: start.elapsed(),
+    }
+}
+
+pub fn format_results(results: &[PartResult], format: OutputFormat) -> String {
+    match format {
+        OutputFormat::Text => format_text(results),
+        OutputFormat::Json => format_json(results),
+        OutputFormat::Csv => format_csv(results),
+    }
+}
+
+fn format_text(results: &[PartResult]) -> String {
+    results
+        .iter()
+        .map(|result| match &result.answer {
+            // rendered answers go on their own lines
+            Ok(answer) if answer.contains('\n') => {
+                format!(
+                    "Day {} Part {} Solution:\n{}",
+                    result.day, result.part, answer
+                )
+            }
+            Ok(answer) => format!(
+                "Day {} Part {} Solution: {}",
+                result.day, result.part, answer
+            ),
+            Err(error) => format!("Day {} Part {} failed: {}", result.day, result.part, error),
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn format_json(results: &[PartResult]) -> String {
+    let objects: Vec<String> = results
+        .iter()
+        .map(|result| {
+            let lines: Vec<String> = result.get_lines().into_iter().map(escape_json).collect();
+
+            let (answer, error) = match &result.answer {
+                Ok(answer) => (escape_json(answer), String::from("null")),
+                Err(error) => (String::from("null"), escape_json(error)),
+            };
+
+            format!(
+                "  {{\"day\": {}, \"part\": {}, \"answer\": {}, \"lines\": [{}], \"runtime_ns\": {}, \"error\": {}}}",
+                result.day,
+                result.part,
+                answer,
+                lines.join(", "),
+                result.runtime.as_nanos(),
+                error
+            )
+        })
+        .collect();
+
+    if objects.is_empty() {
+        String::from("[]")
+    } else {
+        format!("[\n{}\n]", objects.join(",\n"))
+    }
+}
+
+fn format_csv(results: &[PartResult]) -> String {
+    let mut rows = vec![String::from("day,part,answer,runtime_ns,error")];
+
+    for result in results {
+        let (answer, error) = match &result.answer {
+            Ok(answer) => (escape_csv(answer), String::new()),
+            Err(error) => (String::new(), escape_csv(error)),
+        };
+
+        rows.push(format!(
+            "{},{},{},{},{}",
+            result.day,
+            result.part,
+            answer,
+            result.runtime.as_nanos(),
+            error
+        ));
+    }
+
+    rows.join("\n")
+}
+
+pub fn escape_json(value: &str) -> String {
+    let mut escaped = String::from("\"");
+
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped.push('"');
+
+    escaped
+}
+
+// Fields with commas, quotes or newlines are quoted, as in RFC 4180.
+pub fn escape_csv(value: &str) -> String {
+    if value.contains(&[',', '"', '\n', '\r'][..]) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        String::from(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn results() -> Vec<PartResult> {
+        vec![
+            PartResult {
+                day: 1,
+                part: 1,
+                answer: Ok(String::from("34241")),
+                runtime: Duration::from_nanos(1500),
+            },
+            PartResult {
+                day: 8,
+                part: 2,
+                answer: Ok(String::from(" #\n# ")),
+                runtime: Duration::from_nanos(20),
+            },
+            PartResult::failed(3, 1, "The wires don't intersect!"),
+        ]
+    }
+
+    #[test]
+    fn test_output_format_from_str() {
+        assert_eq!("json".parse(), Ok(OutputFormat::Json));
+        assert_eq!("csv".parse(), Ok(OutputFormat::Csv));
+        assert_eq!(
+            "xml".parse::<OutputFormat>(),
+            Err(String::from("Format should be text, json or csv, got xml"))
+        );
+    }
+
+    #[test]
+    fn test_format_text() {
+        let expected = "Day 1 Part 1 Solution: 34241\n\
+                        Day 8 Part 2 Solution:\n #\n# \n\
+                        Day 3 Part 1 failed: The wires don't intersect!";
+
+        assert_eq!(format_results(&results(), OutputFormat::Text), expected);
+    }
+
+    #[test]
+    fn test_format_json() {
+        let expected = "[\n  \
+            {\"day\": 1, \"part\": 1, \"answer\": \"34241\", \"lines\": [\"34241\"], \
+            \"runtime_ns\": 1500, \"error\": null},\n  \
+            {\"day\": 8, \"part\": 2, \"answer\": \" #\\n# \", \"lines\": [\" #\", \"# \"], \
+            \"runtime_ns\": 20, \"error\": null},\n  \
+            {\"day\": 3, \"part\": 1, \"answer\": null, \"lines\": [], \
+            \"runtime_ns\": 0, \"error\": \"The wires don't intersect!\"}\n]";
+
+        assert_eq!(format_results(&results(), OutputFormat::Json), expected);
+        assert_eq!(format_results(&[], OutputFormat::Json), "[]");
+    }
+
+    #[test]
+    fn test_format_csv() {
+        let expected = "day,part,answer,runtime_ns,error\n\
+                        1,1,34241,1500,\n\
+                        8,2,\" #\n# \",20,\n\
+                        3,1,,0,The wires don't intersect!";
+
+        assert_eq!(format_results(&results(), OutputFormat::Csv), expected);
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape_json("a\"b\\c\u{1}"), "\"a\\\"b\\\\c\\u0001\"");
+        assert_eq!(escape_csv("a,\"b\""), "\"a,\"\"b\"\"\"");
+        assert_eq!(escape_csv("plain"), "plain");
+    }
+}
@@ -0,0 +1,369 @@
+use std::fs;
+use std::path::Path;
+
+// Templates for a new day, with __DAY__ standing in for the day number.
+const MOD_TEMPLATE: &str = "pub mod puzzle;
+
+use crate::file_reader::PuzzleInput;
+use crate::solution::Solution;
+
+pub use puzzle::Puzzle;
+
+pub struct Day__DAY__;
+
+impl Solution for Day__DAY__ {
+    const DAY: u8 = __DAY__;
+
+    type Input = Puzzle;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(&self, input: &PuzzleInput) -> Result<Puzzle, String> {
+        Ok(Puzzle::new(
+            input.get_lines().into_iter().map(String::from).collect(),
+        ))
+    }
+
+    fn part_1(&self, _puzzle: &Puzzle) -> Result<i64, String> {
+        Err(String::from(\"Day __DAY__ part 1 hasn't been solved yet\"))
+    }
+
+    fn part_2(&self, _puzzle: &Puzzle) -> Result<i64, String> {
+        Err(String::from(\"Day __DAY__ part 2 hasn't been solved yet\"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load_fixture(file_name: &str) -> Puzzle {
+        let input = PuzzleInput::from_file(&format!(\"test_inputs/{}\", file_name)).unwrap();
+
+        Day__DAY__.parse(&input).unwrap()
+    }
+
+    #[test]
+    #[ignore = \"needs the example from the puzzle description\"]
+    fn test_part_1() {
+        let puzzle = load_fixture(\"day___DAY___part_1.txt\");
+
+        let expected = 0;
+
+        let result = Day__DAY__.part_1(&puzzle).unwrap();
+
+        assert_eq!(result, expected);
+    }
+}
+";
+
+const PUZZLE_TEMPLATE: &str = "#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Puzzle {
+    lines: Vec<String>,
+}
+
+impl Puzzle {
+    pub fn new(lines: Vec<String>) -> Puzzle {
+        Puzzle { lines }
+    }
+
+    pub fn get_lines(&self) -> &[String] {
+        &self.lines
+    }
+}
+";
+
+pub fn render_mod(day: u8) -> String {
+    MOD_TEMPLATE.replace("__DAY__", &day.to_string())
+}
+
+pub fn render_puzzle() -> String {
+    String::from(PUZZLE_TEMPLATE)
+}
+
+// Adds `pub mod day_N;` to lib.rs, keeping the day modules in rustfmt's order.
+pub fn register_module(lib_rs: &str, day: u8) -> Result<String, String> {
+    let module_line = format!("pub mod day_{};", day);
+
+    let mut lines: Vec<String> = lib_rs.lines().map(String::from).collect();
+
+    if lines.contains(&module_line) {
+        return Err(format!("day_{} is already declared in lib.rs", day));
+    }
+
+    let first_index = lines
+        .iter()
+        .position(|line| line.starts_with("pub mod day_"))
+        .ok_or("Couldn't find the day modules in lib.rs")?;
+
+    let number_of_days = lines[first_index..]
+        .iter()
+        .take_while(|line| line.starts_with("pub mod day_"))
+        .count();
+
+    let mut day_lines: Vec<String> = lines
+        .drain(first_index..first_index + number_of_days)
+        .collect();
+
+    day_lines.push(module_line);
+    day_lines.sort_by_key(|line| line.trim_end_matches(';').to_string());
+
+    lines.splice(first_index..first_index, day_lines);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+// Adds the day to the solution registry, after the days before it.
+pub fn register_solution(solution_rs: &str, day: u8) -> Result<String, String> {
+    let solution_line = format!("        Box::new(day_{}::Day{}),", day, day);
+
+    let mut lines: Vec<String> = solution_rs.lines().map(String::from).collect();
+
+    let registered_days: Vec<(usize, u8)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let registered_day = line.trim().strip_prefix("Box::new(day_")?;
+            let registered_day = &registered_day[..registered_day.find("::")?];
+
+            Some((index, registered_day.parse().ok()?))
+        })
+        .collect();
+
+    if registered_days
+        .iter()
+        .any(|&(_, registered_day)| registered_day == day)
+    {
+        return Err(format!("Day {} is already registered", day));
+    }
+
+    let solution_index = match registered_days
+        .iter()
+        .find(|&&(_, registered_day)| registered_day > day)
+    {
+        Some(&(index, _)) => index,
+        None => {
+            registered_days
+                .last()
+                .ok_or("Couldn't find the registry in solution.rs")?
+                .0
+                + 1
+        }
+    };
+
+    lines.insert(solution_index, solution_line);
+
+    let use_line = format!("use crate::day_{};", day);
+
+    let first_use_index = lines
+        .iter()
+        .position(|line| line.starts_with("use crate::"))
+        .ok_or("Couldn't find the imports in solution.rs")?;
+
+    // rustfmt keeps the imports sorted
+    let use_index = lines[first_use_index..]
+        .iter()
+        .position(|line| {
+            !line.starts_with("use crate::")
+                || line.trim_end_matches(';') > use_line.trim_end_matches(';')
+        })
+        .map_or(lines.len(), |index| first_use_index + index);
+
+    lines.insert(use_index, use_line);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+// Creates the files for a new day under `root` and registers it. Returns the
+// files that were created or changed. The real input isn't created, so verify
+// and bench skip the day until it's been downloaded.
+pub fn create_day(root: &Path, day: u8) -> Result<Vec<String>, String> {
+    let day_directory = root.join(format!("src/day_{}", day));
+
+    if day_directory.exists() {
+        return Err(format!("{} already exists", day_directory.display()));
+    }
+
+    let lib_file = root.join("src/lib.rs");
+    let solution_file = root.join("src/solution.rs");
+
+    let lib_rs = register_module(&read(&lib_file)?, day)?;
+    let solution_rs = register_solution(&read(&solution_file)?, day)?;
+
+    let mut changed_files = Vec::new();
+
+    let mut files = vec![
+        (day_directory.join("mod.rs"), render_mod(day)),
+        (day_directory.join("puzzle.rs"), render_puzzle()),
+        (lib_file, lib_rs),
+        (solution_file, solution_rs),
+    ];
+
+    let fixture_file = root.join(format!("test_inputs/day_{}_part_1.txt", day));
+
+    if !fixture_file.exists() {
+        files.push((fixture_file, String::new()));
+    }
+
+    for (file, contents) in files {
+        write(&file, &contents)?;
+
+        changed_files.push(file.display().to_string());
+    }
+
+    Ok(changed_files)
+}
+
+fn read(file: &Path) -> Result<String, String> {
+    fs::read_to_string(file).map_err(|error| format!("Error reading {}: {}", file.display(), error))
+}
+
+fn write(file: &Path, contents: &str) -> Result<(), String> {
+    if let Some(directory) = file.parent() {
+        fs::create_dir_all(directory)
+            .map_err(|error| format!("Error creating {}: {}", directory.display(), error))?;
+    }
+
+    fs::write(file, contents)
+        .map_err(|error| format!("Error writing {}: {}", file.display(), error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::env;
+
+    use crate::solution::find_solution;
+    use crate::verify::{verify_all, Answers};
+
+    const LIB_RS: &str = "pub mod solution;
+
+pub mod day_1;
+pub mod day_10;
+pub mod day_2;
+
+pub use intcode_computer::IntcodeComputer;
+";
+
+    const SOLUTION_RS: &str = "use crate::file_reader::PuzzleInput;
+use crate::{day_1, day_10, day_2};
+
+pub fn get_solutions() -> Vec<Box<dyn DaySolution>> {
+    vec![
+        Box::new(day_1::Day1),
+        Box::new(day_2::Day2),
+        Box::new(day_10::Day10),
+    ]
+}
+";
+
+    #[test]
+    fn test_render_mod() {
+        let result = render_mod(15);
+
+        assert!(result.contains("pub struct Day15;"));
+        assert!(result.contains("const DAY: u8 = 15;"));
+        assert!(result.contains("load_fixture(\"day_15_part_1.txt\")"));
+        assert!(!result.contains("__DAY__"));
+    }
+
+    #[test]
+    fn test_register_module() {
+        let expected = "pub mod solution;
+
+pub mod day_1;
+pub mod day_10;
+pub mod day_2;
+pub mod day_3;
+
+pub use intcode_computer::IntcodeComputer;
+";
+
+        assert_eq!(register_module(LIB_RS, 3), Ok(String::from(expected)));
+        assert_eq!(
+            register_module(LIB_RS, 10),
+            Err(String::from("day_10 is already declared in lib.rs"))
+        );
+    }
+
+    #[test]
+    fn test_register_solution() {
+        let expected = "use crate::day_3;
+use crate::file_reader::PuzzleInput;
+use crate::{day_1, day_10, day_2};
+
+pub fn get_solutions() -> Vec<Box<dyn DaySolution>> {
+    vec![
+        Box::new(day_1::Day1),
+        Box::new(day_2::Day2),
+        Box::new(day_3::Day3),
+        Box::new(day_10::Day10),
+    ]
+}
+";
+
+        assert_eq!(
+            register_solution(SOLUTION_RS, 3),
+            Ok(String::from(expected))
+        );
+        assert!(register_solution(SOLUTION_RS, 11)
+            .unwrap()
+            .contains("        Box::new(day_10::Day10),\n        Box::new(day_11::Day11),\n    ]"));
+        assert_eq!(
+            register_solution(SOLUTION_RS, 2),
+            Err(String::from("Day 2 is already registered"))
+        );
+    }
+
+    #[test]
+    fn test_create_day() {
+        let root = env::temp_dir().join(format!("aoc_scaffold_{}", std::process::id()));
+
+        write(&root.join("src/lib.rs"), LIB_RS).unwrap();
+        write(&root.join("src/solution.rs"), SOLUTION_RS).unwrap();
+        write(&root.join("inputs/day_3.txt"), "my input").unwrap();
+
+        let result = create_day(&root, 3).unwrap();
+
+        assert_eq!(result.len(), 5);
+        assert_eq!(
+            read(&root.join("inputs/day_3.txt")),
+            Ok(String::from("my input"))
+        );
+        assert_eq!(read(&root.join("src/day_3/mod.rs")), Ok(render_mod(3)));
+        assert_eq!(
+            read(&root.join("test_inputs/day_3_part_1.txt")),
+            Ok(String::new())
+        );
+        assert!(read(&root.join("src/lib.rs"))
+            .unwrap()
+            .contains("pub mod day_3;"));
+        assert!(create_day(&root, 3).is_err());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_create_day_then_verify() {
+        let root = env::temp_dir().join(format!("aoc_scaffold_verify_{}", std::process::id()));
+
+        write(&root.join("src/lib.rs"), LIB_RS).unwrap();
+        write(&root.join("src/solution.rs"), SOLUTION_RS).unwrap();
+
+        create_day(&root, 4).unwrap();
+
+        let input_file = root.join("inputs/day_4.txt");
+
+        assert!(!input_file.exists());
+
+        let report = verify_all(&[find_solution(4).unwrap()], &Answers::new(), |_| {
+            input_file.display().to_string()
+        });
+
+        assert!(report.is_success());
+        assert!(report.get_verifications()[0].is_skipped());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
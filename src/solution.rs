@@ -0,0 +1,269 @@
+use std::fmt::Display;
+use std::path::Path;
+
+use crate::cli::RunOptions;
+use crate::day_15;
+use crate::day_16;
+use crate::day_17;
+use crate::day_18;
+use crate::file_reader::PuzzleInput;
+use crate::{day_1, day_10, day_11, day_12, day_13, day_14, day_2};
+use crate::{day_3, day_4, day_5, day_6, day_7, day_8, day_9};
+
+pub trait Solution {
+    const DAY: u8;
+
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(&self, input: &PuzzleInput) -> Result<Self::Input, String>;
+
+    fn part_1(&self, input: &Self::Input) -> Result<Self::Answer1, String>;
+
+    fn part_2(&self, input: &Self::Input) -> Result<Self::Answer2, String>;
+
+    // Days with a puzzle parameter (e.g. the day 5 system ID) override this.
+    // The argument only applies to the given part.
+    fn set_arg(&mut self, _part: u8, arg: &str) -> Result<(), String> {
+        Err(format!(
+            "Day {} doesn't take an argument, got {}",
+            Self::DAY,
+            arg
+        ))
+    }
+}
+
+// Object safe side of Solution, so days with different input and answer types
+// can live in the same registry.
+pub trait DaySolution {
+    fn get_day(&self) -> u8;
+
+    fn configure(&mut self, options: &RunOptions) -> Result<(), String>;
+
+    fn prepare<'a>(&'a self, input: &PuzzleInput)
+        -> Result<Box<dyn PreparedSolution + 'a>, String>;
+}
+
+// A day with its input already parsed.
+pub trait PreparedSolution {
+    fn solve(&self, part: u8) -> Result<String, String>;
+}
+
+struct Prepared<'a, S: Solution> {
+    solution: &'a S,
+    input: S::Input,
+}
+
+impl<'a, S: Solution> PreparedSolution for Prepared<'a, S> {
+    fn solve(&self, part: u8) -> Result<String, String> {
+        match part {
+            1 => self
+                .solution
+                .part_1(&self.input)
+                .map(|answer| answer.to_string()),
+            2 => self
+                .solution
+                .part_2(&self.input)
+                .map(|answer| answer.to_string()),
+            _ => Err(format!("Day {} has no part {}", S::DAY, part)),
+        }
+    }
+}
+
+impl<S: Solution> DaySolution for S {
+    fn get_day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn configure(&mut self, options: &RunOptions) -> Result<(), String> {
+        match (&options.arg, options.part) {
+            (Some(arg), Some(part)) => self.set_arg(part, arg),
+            (Some(_), None) => Err(String::from(ARG_NEEDS_PART)),
+            (None, _) => Ok(()),
+        }
+    }
+
+    fn prepare<'a>(
+        &'a self,
+        input: &PuzzleInput,
+    ) -> Result<Box<dyn PreparedSolution + 'a>, String> {
+        let input = self.parse(input)?;
+
+        Ok(Box::new(Prepared {
+            solution: self,
+            input,
+        }))
+    }
+}
+
+pub const ARG_NEEDS_PART: &str = "--arg only applies to one part, choose it with --part";
+
+pub fn get_default_input_file(day: u8) -> String {
+    format!("inputs/day_{}.txt", day)
+}
+
+pub fn get_solutions() -> Vec<Box<dyn DaySolution>> {
+    vec![
+        Box::new(day_1::Day1),
+        Box::new(day_2::Day2),
+        Box::new(day_3::Day3),
+        Box::new(day_4::Day4),
+        Box::new(day_5::Day5::default()),
+        Box::new(day_6::Day6),
+        Box::new(day_7::Day7),
+        Box::new(day_8::Day8),
+        Box::new(day_9::Day9::default()),
+        Box::new(day_10::Day10),
+        Box::new(day_11::Day11),
+        Box::new(day_12::Day12),
+        Box::new(day_13::Day13),
+        Box::new(day_14::Day14),
+        Box::new(day_15::Day15),
+        Box::new(day_16::Day16),
+        Box::new(day_17::Day17),
+        Box::new(day_18::Day18),
+    ]
+}
+
+pub fn find_solution(day: u8) -> Option<Box<dyn DaySolution>> {
+    get_solutions()
+        .into_iter()
+        .find(|solution| solution.get_day() == day)
+}
+
+// Splits off the days whose input file doesn't exist, returning the solutions
+// that can run and the days that can't.
+pub fn partition_by_input<F>(
+    solutions: Vec<Box<dyn DaySolution>>,
+    input_file_for: F,
+) -> (Vec<Box<dyn DaySolution>>, Vec<u8>)
+where
+    F: Fn(u8) -> String,
+{
+    let (with_input, without_input): (Vec<_>, Vec<_>) = solutions
+        .into_iter()
+        .partition(|solution| Path::new(&input_file_for(solution.get_day())).exists());
+
+    let missing_days = without_input
+        .iter()
+        .map(|solution| solution.get_day())
+        .collect();
+
+    (with_input, missing_days)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::output::{self, OutputFormat};
+
+    #[test]
+    fn test_get_solutions() {
+        // every day module has to be registered, in order
+        let mut expected: Vec<u8> = std::fs::read_dir("src")
+            .unwrap()
+            .filter_map(|entry| {
+                let file_name = entry.unwrap().file_name();
+
+                file_name.to_str()?.strip_prefix("day_")?.parse().ok()
+            })
+            .collect();
+
+        expected.sort_unstable();
+
+        let result: Vec<u8> = get_solutions()
+            .iter()
+            .map(|solution| solution.get_day())
+            .collect();
+
+        assert_eq!(&expected[..14], &(1..=14).collect::<Vec<u8>>()[..]);
+        assert_eq!(result, expected);
+        assert!(find_solution(25).is_none());
+    }
+
+    #[test]
+    fn test_partition_by_input() {
+        let solutions = vec![find_solution(1).unwrap(), find_solution(2).unwrap()];
+
+        let (with_input, missing_days) = partition_by_input(solutions, |day| match day {
+            1 => String::from("test_inputs/day_1_part_1.txt"),
+            _ => String::from("test_inputs/missing.txt"),
+        });
+
+        let result: Vec<u8> = with_input
+            .iter()
+            .map(|solution| solution.get_day())
+            .collect();
+
+        assert_eq!(result, vec![1]);
+        assert_eq!(missing_days, vec![2]);
+    }
+
+    #[test]
+    fn test_prepare_and_solve() {
+        let input = PuzzleInput::from_file("test_inputs/day_1_part_1.txt").unwrap();
+        let solution = find_solution(1).unwrap();
+
+        let prepared = solution.prepare(&input).unwrap();
+
+        assert_eq!(prepared.solve(1), Ok(String::from("34241")));
+        assert_eq!(prepared.solve(2), Ok(String::from("51316")));
+        assert_eq!(prepared.solve(3), Err(String::from("Day 1 has no part 3")));
+    }
+
+    #[test]
+    fn test_configure() {
+        let options = RunOptions {
+            part: Some(1),
+            arg: Some(String::from("5")),
+            ..RunOptions::new()
+        };
+        let options_without_part = RunOptions {
+            part: None,
+            ..options.clone()
+        };
+
+        let mut day_5 = find_solution(5).unwrap();
+        let mut day_6 = find_solution(6).unwrap();
+
+        assert_eq!(day_5.configure(&options), Ok(()));
+        assert_eq!(
+            day_5.configure(&options_without_part),
+            Err(String::from(ARG_NEEDS_PART))
+        );
+        assert_eq!(
+            day_6.configure(&options),
+            Err(String::from("Day 6 doesn't take an argument, got 5"))
+        );
+    }
+
+    #[test]
+    fn test_arg_only_applies_to_its_part() {
+        // outputs the system ID it's given
+        let input = PuzzleInput::from_string("echo", "3,0,4,0,99\n");
+        let options = RunOptions {
+            part: Some(1),
+            arg: Some(String::from("7")),
+            ..RunOptions::new()
+        };
+
+        let mut day_5 = find_solution(5).unwrap();
+
+        day_5.configure(&options).unwrap();
+
+        let prepared = day_5.prepare(&input).unwrap();
+
+        let results = vec![
+            output::solve_part(5, 1, prepared.as_ref()),
+            output::solve_part(5, 2, prepared.as_ref()),
+        ];
+
+        let expected = "Day 5 Part 1 Solution: 7\nDay 5 Part 2 Solution: 5";
+
+        let result = output::format_results(&results, OutputFormat::Text);
+
+        assert_eq!(result, expected);
+    }
+}
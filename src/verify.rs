@@ -0,0 +1,491 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+
+use crate::file_reader::{InputError, PuzzleInput};
+use crate::solution::DaySolution;
+
+pub const DEFAULT_ANSWERS_FILE: &str = "answers.toml";
+
+// Known good answers, kept in a small subset of TOML:
+//
+//     [day_1]
+//     part_1 = 3233481
+//     part_2 = "4847351"
+//
+// Rendered answers can use """ multi-line strings. Trailing whitespace on each
+// line is ignored when comparing.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Answers {
+    answers: HashMap<(u8, u8), String>,
+}
+
+impl Answers {
+    pub fn new() -> Answers {
+        Answers::default()
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn has_day(&self, day: u8) -> bool {
+        self.answers
+            .keys()
+            .any(|&(answer_day, _)| answer_day == day)
+    }
+
+    pub fn insert(&mut self, day: u8, part: u8, answer: &str) {
+        self.answers.insert((day, part), normalize(answer));
+    }
+
+    pub fn load(file_name: &str) -> Result<Answers, String> {
+        let contents = fs::read_to_string(file_name)
+            .map_err(|error| format!("Error reading {}: {}", file_name, error))?;
+
+        Answers::parse(&contents)
+    }
+
+    pub fn parse(contents: &str) -> Result<Answers, String> {
+        let mut answers = Answers::new();
+        let mut day = None;
+        let mut lines = contents.lines().enumerate();
+
+        while let Some((index, line)) = lines.next() {
+            let line_number = index + 1;
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if line.starts_with('[') {
+                day = Some(parse_table_name(line).ok_or_else(|| {
+                    format!(
+                        "Line {}: expected a [day_N] table, got {}",
+                        line_number, line
+                    )
+                })?);
+
+                continue;
+            }
+
+            let (key, value) = match line.find('=') {
+                Some(equals_index) => {
+                    (line[..equals_index].trim(), line[equals_index + 1..].trim())
+                }
+                None => return Err(format!("Line {}: expected key = value", line_number)),
+            };
+
+            let part = match key {
+                "part_1" => 1,
+                "part_2" => 2,
+                _ => return Err(format!("Line {}: unknown key {}", line_number, key)),
+            };
+
+            let day = day.ok_or_else(|| {
+                format!("Line {}: {} is outside a [day_N] table", line_number, key)
+            })?;
+
+            let answer = if let Some(rest) = value.strip_prefix("\"\"\"") {
+                parse_multi_line_string(rest, &mut lines)
+                    .ok_or_else(|| format!("Line {}: unterminated \"\"\" string", line_number))?
+            } else if let Some(rest) = value.strip_prefix('"') {
+                rest.strip_suffix('"')
+                    .map(String::from)
+                    .ok_or_else(|| format!("Line {}: unterminated string", line_number))?
+            } else if value.parse::<i128>().is_ok() {
+                String::from(value)
+            } else {
+                return Err(format!("Line {}: can't parse value {}", line_number, value));
+            };
+
+            answers.insert(day, part, &answer);
+        }
+
+        Ok(answers)
+    }
+}
+
+fn parse_table_name(line: &str) -> Option<u8> {
+    line.strip_prefix("[day_")?.strip_suffix(']')?.parse().ok()
+}
+
+// A newline right after the opening quotes is dropped, like in TOML.
+fn parse_multi_line_string<'a, I>(first_line: &str, lines: &mut I) -> Option<String>
+where
+    I: Iterator<Item = (usize, &'a str)>,
+{
+    if let Some(value) = first_line.strip_suffix("\"\"\"") {
+        return Some(String::from(value));
+    }
+
+    let mut value_lines = Vec::new();
+
+    if !first_line.is_empty() {
+        value_lines.push(String::from(first_line));
+    }
+
+    for (_, line) in lines {
+        match line.find("\"\"\"") {
+            Some(end_index) => {
+                value_lines.push(String::from(&line[..end_index]));
+
+                return Some(value_lines.join("\n"));
+            }
+            None => value_lines.push(String::from(line)),
+        }
+    }
+
+    None
+}
+
+fn normalize(answer: &str) -> String {
+    answer
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<&str>>()
+        .join("\n")
+        .trim_end()
+        .to_string()
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Outcome {
+    Pass,
+    Fail { expected: String, actual: String },
+    // There's no known answer to compare against
+    Unchecked(String),
+    // The day has no input and no answers yet, so there's nothing to check
+    Skipped(String),
+    Error(String),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Verification {
+    pub day: u8,
+    pub part: u8,
+    pub outcome: Outcome,
+}
+
+impl Verification {
+    pub fn is_failure(&self) -> bool {
+        matches!(self.outcome, Outcome::Fail { .. } | Outcome::Error(_))
+    }
+
+    pub fn is_skipped(&self) -> bool {
+        matches!(self.outcome, Outcome::Skipped(_))
+    }
+}
+
+// Runs both parts of a day on `input` and compares them with the answers.
+pub fn verify_day(
+    solution: &dyn DaySolution,
+    input: &PuzzleInput,
+    answers: &Answers,
+) -> Vec<Verification> {
+    let day = solution.get_day();
+
+    let prepared_solution = match solution.prepare(input) {
+        Ok(prepared_solution) => prepared_solution,
+        Err(error) => {
+            return vec![Verification {
+                day,
+                part: 0,
+                outcome: Outcome::Error(error),
+            }]
+        }
+    };
+
+    (1..=2)
+        .map(|part| {
+            let outcome = match (prepared_solution.solve(part), answers.get(day, part)) {
+                (Err(error), _) => Outcome::Error(error),
+                (Ok(actual), None) => Outcome::Unchecked(actual),
+                (Ok(actual), Some(expected)) if normalize(&actual) == expected => Outcome::Pass,
+                (Ok(actual), Some(expected)) => Outcome::Fail {
+                    expected: String::from(expected),
+                    actual,
+                },
+            };
+
+            Verification { day, part, outcome }
+        })
+        .collect()
+}
+
+// Reads each day's input from `input_file_for(day)`. A missing input counts as
+// an error for that day, unless there are no answers for it either.
+pub fn verify_all<F>(
+    solutions: &[Box<dyn DaySolution>],
+    answers: &Answers,
+    input_file_for: F,
+) -> Report
+where
+    F: Fn(u8) -> String,
+{
+    let mut verifications = Vec::new();
+
+    for solution in solutions {
+        let day = solution.get_day();
+        let input_file = input_file_for(day);
+
+        let outcome = match PuzzleInput::from_file(&input_file) {
+            Ok(input) => {
+                verifications.extend(verify_day(solution.as_ref(), &input, answers));
+
+                continue;
+            }
+            Err(error @ InputError::MissingFile { .. }) if !answers.has_day(day) => {
+                Outcome::Skipped(error.to_string())
+            }
+            Err(error) => Outcome::Error(error.to_string()),
+        };
+
+        verifications.push(Verification {
+            day,
+            part: 0,
+            outcome,
+        });
+    }
+
+    Report { verifications }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Report {
+    verifications: Vec<Verification>,
+}
+
+impl Report {
+    pub fn get_verifications(&self) -> &[Verification] {
+        &self.verifications
+    }
+
+    pub fn is_success(&self) -> bool {
+        !self.verifications.iter().any(Verification::is_failure)
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Day | Part | Result    | Details")?;
+        writeln!(f, "----+------+-----------+--------")?;
+
+        for verification in &self.verifications {
+            let part = match verification.part {
+                0 => String::from("-"),
+                part => part.to_string(),
+            };
+
+            let (result, details) = match &verification.outcome {
+                Outcome::Pass => ("pass", String::new()),
+                Outcome::Fail { expected, actual } => (
+                    "FAIL",
+                    format!(
+                        "expected {}, got {}",
+                        summarize(expected),
+                        summarize(actual)
+                    ),
+                ),
+                Outcome::Unchecked(actual) => ("unchecked", summarize(actual)),
+                Outcome::Skipped(reason) => ("skipped", reason.clone()),
+                Outcome::Error(error) => ("ERROR", error.clone()),
+            };
+
+            let row = format!(
+                "{:>3} | {:>4} | {:<9} | {}",
+                verification.day, part, result, details
+            );
+
+            writeln!(f, "{}", row.trim_end())?;
+        }
+
+        let number_failed = self
+            .verifications
+            .iter()
+            .filter(|verification| verification.is_failure())
+            .count();
+        let number_skipped = self
+            .verifications
+            .iter()
+            .filter(|verification| verification.is_skipped())
+            .count();
+
+        // skipped days weren't checked, so they aren't counted as answers
+        write!(
+            f,
+            "{} answers, {} failed",
+            self.verifications.len() - number_skipped,
+            number_failed
+        )?;
+
+        if number_skipped > 0 {
+            write!(f, ", {} skipped", number_skipped)?;
+        }
+
+        Ok(())
+    }
+}
+
+// Rendered answers are shown on a single line, with rows separated by |
+fn summarize(answer: &str) -> String {
+    normalize(answer).replace('\n', "|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::solution::find_solution;
+
+    const ANSWERS: &str = "
+# day 1 with the example input
+[day_1]
+part_1 = 34241
+part_2 = \"51316\"
+
+[day_8]
+part_2 = \"\"\"
+ #
+#  #
+\"\"\"
+";
+
+    #[test]
+    fn test_parse_answers() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+
+        assert_eq!(answers.get(1, 1), Some("34241"));
+        assert_eq!(answers.get(1, 2), Some("51316"));
+        assert_eq!(answers.get(8, 1), None);
+        assert_eq!(answers.get(8, 2), Some(" #\n#  #"));
+    }
+
+    #[test]
+    fn test_parse_answers_errors() {
+        assert_eq!(
+            Answers::parse("part_1 = 1"),
+            Err(String::from("Line 1: part_1 is outside a [day_N] table"))
+        );
+        assert_eq!(
+            Answers::parse("[day_1]\npart_3 = 1"),
+            Err(String::from("Line 2: unknown key part_3"))
+        );
+        assert_eq!(
+            Answers::parse("[day_1]\npart_1 = \"\"\"\n#"),
+            Err(String::from("Line 2: unterminated \"\"\" string"))
+        );
+        assert!(Answers::parse("[day_one]").is_err());
+        assert!(Answers::parse("[day_1]\npart_1 = abc").is_err());
+    }
+
+    #[test]
+    fn test_verify_day() {
+        let input = PuzzleInput::from_file("test_inputs/day_1_part_1.txt").unwrap();
+        let solution = find_solution(1).unwrap();
+
+        let mut answers = Answers::new();
+        answers.insert(1, 1, "34241");
+
+        let expected = vec![
+            Verification {
+                day: 1,
+                part: 1,
+                outcome: Outcome::Pass,
+            },
+            Verification {
+                day: 1,
+                part: 2,
+                outcome: Outcome::Unchecked(String::from("51316")),
+            },
+        ];
+
+        assert_eq!(verify_day(solution.as_ref(), &input, &answers), expected);
+
+        answers.insert(1, 2, "5");
+
+        let result = verify_day(solution.as_ref(), &input, &answers);
+
+        assert!(result[1].is_failure());
+        assert_eq!(
+            result[1].outcome,
+            Outcome::Fail {
+                expected: String::from("5"),
+                actual: String::from("51316")
+            }
+        );
+    }
+
+    #[test]
+    fn test_verify_all_report() {
+        let solutions: Vec<Box<dyn DaySolution>> =
+            vec![find_solution(1).unwrap(), find_solution(3).unwrap()];
+
+        let mut answers = Answers::new();
+        answers.insert(1, 1, "34241");
+        answers.insert(1, 2, "51316");
+        // with an answer, the missing input is an error rather than skipped
+        answers.insert(3, 1, "6");
+
+        let report = verify_all(&solutions, &answers, |day| match day {
+            1 => String::from("test_inputs/day_1_part_1.txt"),
+            _ => String::from("test_inputs/missing.txt"),
+        });
+
+        let expected_table = "Day | Part | Result    | Details\n\
+                              ----+------+-----------+--------\n  \
+                              1 |    1 | pass      |\n  \
+                              1 |    2 | pass      |\n  \
+                              3 |    - | ERROR     | test_inputs/missing.txt: file not found\n\
+                              3 answers, 1 failed";
+
+        assert!(!report.is_success());
+        assert_eq!(report.to_string(), expected_table);
+    }
+
+    #[test]
+    fn test_verify_all_skips_days_without_input_or_answers() {
+        let solutions: Vec<Box<dyn DaySolution>> = vec![
+            find_solution(1).unwrap(),
+            find_solution(3).unwrap(),
+            find_solution(4).unwrap(),
+        ];
+
+        let mut answers = Answers::new();
+        answers.insert(1, 1, "34241");
+        answers.insert(1, 2, "51316");
+        answers.insert(3, 1, "6");
+
+        let report = verify_all(&solutions, &answers, |day| match day {
+            1 => String::from("test_inputs/day_1_part_1.txt"),
+            _ => String::from("test_inputs/missing.txt"),
+        });
+
+        let expected_table = "Day | Part | Result    | Details\n\
+                              ----+------+-----------+--------\n  \
+                              1 |    1 | pass      |\n  \
+                              1 |    2 | pass      |\n  \
+                              3 |    - | ERROR     | test_inputs/missing.txt: file not found\n  \
+                              4 |    - | skipped   | test_inputs/missing.txt: file not found\n\
+                              3 answers, 1 failed, 1 skipped";
+
+        assert!(!report.is_success());
+        assert_eq!(report.to_string(), expected_table);
+    }
+
+    #[test]
+    fn test_skipped_days_are_not_failures() {
+        let solutions: Vec<Box<dyn DaySolution>> = vec![find_solution(4).unwrap()];
+
+        let report = verify_all(&solutions, &Answers::new(), |_| {
+            String::from("test_inputs/missing.txt")
+        });
+
+        assert!(report.is_success());
+        assert!(report.get_verifications()[0].is_skipped());
+        assert!(report
+            .to_string()
+            .ends_with("0 answers, 0 failed, 1 skipped"));
+    }
+}